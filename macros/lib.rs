@@ -1,15 +1,353 @@
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Delimiter, Group, Punct, Spacing, TokenStream as TokenStream2, TokenTree};
 use quote::quote;
 
+/// Evaluates the block, bailing out of the enclosing function on the first `?`
+/// that hits a `None` or an `Err` (which is logged). A `?` inside a closure or
+/// an async block is left alone, since it returns from that instead.
+///
+/// By default it bails out with a bare `return`; start the input with
+/// `else <expr>;` to return `<expr>` instead.
 #[proc_macro]
 pub fn return_with_try(input: TokenStream) -> TokenStream {
-    let input = TokenStream2::from(input);
+    let (fallback, body) = split_fallback(TokenStream2::from(input));
+    let body = rewrite_try(body);
+
+    quote! {
+        {
+            trait __ReturnWithTry<T> {
+                fn __into_option(self) -> Option<T>;
+            }
+            impl<T> __ReturnWithTry<T> for Option<T> {
+                #[inline]
+                fn __into_option(self) -> Option<T> {
+                    self
+                }
+            }
+            impl<T, E: ::core::fmt::Debug> __ReturnWithTry<T> for Result<T, E> {
+                #[inline]
+                fn __into_option(self) -> Option<T> {
+                    self.map_err(|err| ::log::error!("{err:?}")).ok()
+                }
+            }
+
+            match (|| Some({ #body }))() {
+                Some(v) => v,
+                None => return #fallback,
+            }
+        }
+    }
+    .into()
+}
+
+fn split_fallback(input: TokenStream2) -> (TokenStream2, TokenStream2) {
+    let mut tokens = input.into_iter().peekable();
+
+    match tokens.peek() {
+        Some(TokenTree::Ident(ident)) if ident == "else" => {
+            tokens.next();
+        }
+        _ => return (TokenStream2::new(), tokens.collect()),
+    }
+
+    let mut fallback = TokenStream2::new();
+    for token in tokens.by_ref() {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => break,
+            _ => fallback.extend([token]),
+        }
+    }
+
+    (fallback, tokens.collect())
+}
+
+/// Turns every postfix `expr?` into `expr.__into_option()?`, except in closures
+/// and async blocks.
+fn rewrite_try(input: TokenStream2) -> TokenStream2 {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let mut output = Vec::<TokenTree>::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if let Some(end) = nested_body_end(&tokens, i, output.last()) {
+            output.extend(tokens[i..end].iter().cloned());
+            i = end;
+            continue;
+        }
+
+        let token = tokens[i].clone();
+        i += 1;
+        match token {
+            TokenTree::Group(group) => {
+                let mut rewritten = Group::new(group.delimiter(), rewrite_try(group.stream()));
+                rewritten.set_span(group.span());
+                output.push(TokenTree::Group(rewritten));
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '?' && is_postfix(output.last()) => {
+                output.extend(quote!(.__into_option()));
+                output.push(TokenTree::Punct(Punct::new('?', Spacing::Alone)));
+            }
+            token => output.push(token),
+        }
+    }
+
+    output.into_iter().collect()
+}
+
+fn is_postfix(previous: Option<&TokenTree>) -> bool {
+    matches!(
+        previous,
+        Some(TokenTree::Ident(_) | TokenTree::Literal(_) | TokenTree::Group(_))
+    )
+}
+
+/// If a closure or an async block starts at `tokens[start]`, the index just
+/// past it. A closure without braces runs to the next `,` or `;`.
+fn nested_body_end(
+    tokens: &[TokenTree],
+    start: usize,
+    previous: Option<&TokenTree>,
+) -> Option<usize> {
+    let is_punct = |i: usize, c: char| matches!(tokens.get(i), Some(TokenTree::Punct(punct)) if punct.as_char() == c);
+    let is_ident = |i: usize, name: &str| is_ident_named(tokens.get(i), name);
+    let is_block = |i: usize| matches!(tokens.get(i), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace);
+
+    let mut i = start;
+    if is_ident(i, "async") {
+        i += 1;
+    }
+    if is_ident(i, "move") {
+        i += 1;
+    }
+    if i > start && is_block(i) && is_ident(start, "async") {
+        return Some(i + 1);
+    }
+
+    // Right after an operand, `|` is a bitwise or.
+    let after_operand = is_postfix(previous) && !is_ident_named(previous, "return");
+    if !is_punct(i, '|') || (i == start && after_operand) {
+        return None;
+    }
+    i += 1;
+    while !is_punct(i, '|') {
+        if i >= tokens.len() {
+            return None;
+        }
+        i += 1;
+    }
+    i += 1;
+
+    if is_punct(i, '-') && is_punct(i + 1, '>') {
+        while i < tokens.len() && !is_block(i) {
+            i += 1;
+        }
+        return Some((i + 1).min(tokens.len()));
+    }
+    while i < tokens.len() && !is_punct(i, ',') && !is_punct(i, ';') {
+        i += 1;
+    }
+    Some(i)
+}
+
+fn is_ident_named(token: Option<&TokenTree>, name: &str) -> bool {
+    matches!(token, Some(TokenTree::Ident(ident)) if ident == name)
+}
+
+/// `clone!(a, b => move |x| ...)` clones `a` and `b` into fresh bindings before
+/// evaluating the expression after `=>`, which can then `move` them freely.
+#[proc_macro]
+pub fn clone(input: TokenStream) -> TokenStream {
+    let mut tokens = TokenStream2::from(input).into_iter();
+    let mut bindings = Vec::new();
+
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) => bindings.push(ident),
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => {}
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => match tokens.next() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == '>' => break,
+                _ => return compile_error("expected `=>` after the bindings to clone"),
+            },
+            _ => return compile_error("expected `clone!(a, b, ... => expr)`"),
+        }
+    }
+
+    let body: TokenStream2 = tokens.collect();
+
+    quote! {
+        {
+            #(let #bindings = ::core::clone::Clone::clone(&#bindings);)*
+            #body
+        }
+    }
+    .into()
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    quote!(::core::compile_error!(#message);).into()
+}
+
+/// Builds a class string from static fragments and conditional ones:
+/// `classes!("w-16 h-16", solved => "bg-white" else "bg-black", dev_mode() => "ring-1")`.
+///
+/// Whitespace inside fragments is collapsed, so multi-line literals are fine.
+#[proc_macro]
+pub fn classes(input: TokenStream) -> TokenStream {
+    let mut pushes = Vec::new();
+
+    for item in split_on(TokenStream2::from(input), ',') {
+        if item.is_empty() {
+            continue;
+        }
+
+        let Some((condition, branches)) = split_arrow(item.clone()) else {
+            pushes.push(
+                quote!(__push(&mut __classes, ::core::convert::AsRef::<str>::as_ref(&(#item)));),
+            );
+            continue;
+        };
+
+        let (then, otherwise) = split_else(branches);
+        let otherwise = otherwise.map(|otherwise| {
+            quote! {
+                else {
+                    __push(&mut __classes, ::core::convert::AsRef::<str>::as_ref(&(#otherwise)));
+                }
+            }
+        });
+
+        pushes.push(quote! {
+            if #condition {
+                __push(&mut __classes, ::core::convert::AsRef::<str>::as_ref(&(#then)));
+            } #otherwise
+        });
+    }
 
     quote! {
-        match (|| Some({ #input }))() {
-            Some(v) => v,
-            None => return,
+        {
+            fn __push(classes: &mut ::std::string::String, fragment: &str) {
+                for class in fragment.split_whitespace() {
+                    if !classes.is_empty() {
+                        classes.push(' ');
+                    }
+                    classes.push_str(class);
+                }
+            }
+
+            let mut __classes = ::std::string::String::new();
+            #(#pushes)*
+            __classes
+        }
+    }
+    .into()
+}
+
+fn split_on(input: TokenStream2, separator: char) -> Vec<TokenStream2> {
+    let mut items = vec![TokenStream2::new()];
+
+    for token in input {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == separator => {
+                items.push(TokenStream2::new());
+            }
+            _ => items.last_mut().unwrap().extend([token]),
         }
-    }.into()
-}
\ No newline at end of file
+    }
+
+    items
+}
+
+fn split_arrow(input: TokenStream2) -> Option<(TokenStream2, TokenStream2)> {
+    let tokens: Vec<_> = input.into_iter().collect();
+    let arrow = tokens.windows(2).position(|pair| match pair {
+        [TokenTree::Punct(eq), TokenTree::Punct(gt)] => {
+            eq.as_char() == '=' && eq.spacing() == Spacing::Joint && gt.as_char() == '>'
+        }
+        _ => false,
+    })?;
+
+    Some((
+        tokens[..arrow].iter().cloned().collect(),
+        tokens[arrow + 2..].iter().cloned().collect(),
+    ))
+}
+
+fn split_else(input: TokenStream2) -> (TokenStream2, Option<TokenStream2>) {
+    let tokens: Vec<_> = input.into_iter().collect();
+
+    match tokens
+        .iter()
+        .position(|token| matches!(token, TokenTree::Ident(ident) if ident == "else"))
+    {
+        Some(position) => (
+            tokens[..position].iter().cloned().collect(),
+            Some(tokens[position + 1..].iter().cloned().collect()),
+        ),
+        None => (tokens.into_iter().collect(), None),
+    }
+}
+
+/// `memo!(|a, b| ...)` is shorthand for `create_memo(move |_| with!(|a, b| ...))`.
+#[proc_macro]
+pub fn memo(input: TokenStream) -> TokenStream {
+    let input = TokenStream2::from(input);
+
+    quote! {
+        ::leptos::create_memo(move |_| ::leptos::with!(#input))
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    fn rewritten(input: TokenStream2) -> String {
+        rewrite_try(input).to_string()
+    }
+
+    #[test]
+    fn rewrites_the_body() {
+        assert_eq!(
+            rewritten(quote!(let a = b()?; c.d?.e)),
+            quote!(let a = b().__into_option()?; c.d.__into_option()?.e).to_string(),
+        );
+    }
+
+    #[test]
+    fn leaves_closures_alone() {
+        assert_eq!(
+            rewritten(quote!(f(|x| x.parse()?, y?); let g = move || { h()? }; i?)),
+            quote!(f(|x| x.parse()?, y.__into_option()?); let g = move || { h()? }; i.__into_option()?)
+                .to_string(),
+        );
+        assert_eq!(
+            rewritten(quote!(let f = |x: u8| -> Option<u8> { Some(x?) }; g?)),
+            quote!(let f = |x: u8| -> Option<u8> { Some(x?) }; g.__into_option()?).to_string(),
+        );
+        assert_eq!(
+            rewritten(quote!(return |x| x?)),
+            quote!(return |x| x?).to_string(),
+        );
+    }
+
+    #[test]
+    fn leaves_async_blocks_alone() {
+        assert_eq!(
+            rewritten(quote!(spawn(async move { a()? }); b?)),
+            quote!(spawn(async move { a()? }); b.__into_option()?).to_string(),
+        );
+        assert_eq!(
+            rewritten(quote!(async { a? }.await)),
+            quote!(async { a? }.await).to_string(),
+        );
+    }
+
+    #[test]
+    fn bitwise_or_is_not_a_closure() {
+        assert_eq!(
+            rewritten(quote!(let a = b | c?; d || e?)),
+            quote!(let a = b | c.__into_option()?; d || e.__into_option()?).to_string(),
+        );
+    }
+
+    use super::*;
+}
@@ -0,0 +1,182 @@
+/// One stretch of a solve, ending when a breakpoint is reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Phase {
+    pub name: String,
+    pub time: Duration,
+    pub moves: usize,
+}
+
+impl Phase {
+    pub fn tps(&self) -> f64 {
+        match self.time.as_secs_f64() {
+            secs if secs > 0.0 => self.moves as f64 / secs,
+            _ => 0.0,
+        }
+    }
+}
+
+/// A breakpoint after each row but the last two, which are solved together:
+/// `[4, 8, 16]` on a 4x4.
+pub fn row_breakpoints((width, height): (usize, usize)) -> Vec<usize> {
+    (1..height.saturating_sub(1))
+        .map(|row| row * width)
+        .chain([width * height])
+        .collect()
+}
+
+/// Splits `solve` into phases, the `n`th ending the first time tiles
+/// `1..=breakpoints[n]` are all home after the previous phase ended. The last
+/// phase always runs to the end of the solve. `None` if the solve has no
+/// recorded moves.
+pub fn phases(solve: &Solve, breakpoints: &[usize]) -> Option<Vec<Phase>> {
+    let last = solve.replay.last()?;
+    let (width, height) = solve.scramble.shape;
+    let total = width * height;
+
+    let mut breakpoints: Vec<usize> = breakpoints
+        .iter()
+        .copied()
+        .filter(|&n| 0 < n && n < total)
+        .collect();
+    breakpoints.sort_unstable();
+    breakpoints.dedup();
+    breakpoints.push(total);
+
+    let mut puzzle = solve.scramble.puzzle();
+    let mut ends = Vec::new();
+    for (i, timed) in solve.replay.iter().enumerate() {
+        puzzle.slide_from(timed.cell);
+
+        while let Some(&n) = breakpoints.get(ends.len()) {
+            if n == total || !home(&puzzle, n) {
+                break;
+            }
+            ends.push((i + 1, timed.at));
+        }
+    }
+    ends.truncate(breakpoints.len() - 1);
+    ends.push((solve.replay.len(), last.at));
+
+    let mut phases = Vec::new();
+    let (mut moves_before, mut time_before, mut tiles_before) = (0, Duration::ZERO, 0);
+    for (&(moves, time), &tiles) in ends.iter().zip(&breakpoints) {
+        phases.push(Phase {
+            name: phase_name(tiles_before, tiles, width, total),
+            time: time - time_before,
+            moves: moves - moves_before,
+        });
+        (moves_before, time_before, tiles_before) = (moves, time, tiles);
+    }
+
+    Some(phases)
+}
+
+/// Whether tiles `1..=n` are all on their home cells.
+fn home(puzzle: &AnyPuzzle, n: usize) -> bool {
+    puzzle
+        .iter_indexed()
+        .filter(|&(_, piece)| 0 < piece && piece <= n)
+        .all(|(idx, piece)| puzzle.home_of(piece) == idx)
+}
+
+fn phase_name(from: usize, to: usize, width: usize, total: usize) -> String {
+    match (from % width, to % width) {
+        (0, _) if to == total => match (total - from) / width {
+            1 => "Last row".to_string(),
+            rows => format!("Last {rows} rows"),
+        },
+        (0, 0) if to - from == width => format!("Row {}", to / width),
+        (0, 0) => format!("Rows {}-{}", from / width + 1, to / width),
+        _ if to == total => "Rest".to_string(),
+        _ => format!("Tiles {}-{}", from + 1, to),
+    }
+}
+
+/// Per-phase breakdown of the last solve, next to the session's averages.
+#[component]
+pub fn PhaseTable(#[prop(into)] when: Signal<bool>) -> impl IntoView {
+    let settings = use_settings();
+    let solves = use_solves();
+
+    let table = create_memo(move |_| {
+        let custom = settings.with(|settings| settings.phase_breakpoints.clone());
+        let session_start = session_start();
+        solves.with(|solves| {
+            let last = solves.last()?;
+            let breakpoints = match custom.is_empty() {
+                true => row_breakpoints(last.scramble.shape),
+                false => custom,
+            };
+            let current = phases(last, &breakpoints)?;
+
+            // Earlier solves this session on the same shape, split the same way.
+            let earlier: Vec<Vec<Phase>> = solves[..solves.len() - 1]
+                .iter()
+                .filter(|solve| solve.finished_at >= session_start)
+                .filter(|solve| solve.scramble.shape == last.scramble.shape)
+                .filter_map(|solve| phases(solve, &breakpoints))
+                .filter(|phases| phases.len() == current.len())
+                .collect();
+            let averages: Vec<Option<Duration>> = (0..current.len())
+                .map(|i| {
+                    let count = u32::try_from(earlier.len()).ok().filter(|&n| n > 0)?;
+                    Some(
+                        earlier
+                            .iter()
+                            .map(|phases| phases[i].time)
+                            .sum::<Duration>()
+                            / count,
+                    )
+                })
+                .collect();
+
+            Some(current.into_iter().zip(averages).collect::<Vec<_>>())
+        })
+    });
+
+    let row = |(phase, average): (Phase, Option<Duration>)| {
+        let delta = average.map(|average| phase.time.as_secs_f64() - average.as_secs_f64());
+        view! {
+            <tr>
+                <td class="pr-3">{phase.name.clone()}</td>
+                <td class="text-right">{format!("{:.3}", phase.time.as_secs_f64())}</td>
+                <td class="text-right">{phase.moves}</td>
+                <td class="text-right">{format!("{:.2}", phase.tps())}</td>
+                <td class=move || classes!(
+                    "text-right",
+                    delta.is_some_and(|delta| delta < 0.0) => "text-emerald-500",
+                    delta.is_some_and(|delta| delta > 0.0) => "text-rose-500",
+                )>
+                    {delta.map_or("-".to_string(), |delta| format!("{delta:+.3}"))}
+                </td>
+            </tr>
+        }
+    };
+
+    view! {
+        {move || table().filter(|_| when()).map(|table| view! {
+            <table class="mx-auto mt-3 font-mono text-xs child:child:child:px-1">
+                <thead class="opacity-60">
+                    <tr>
+                        <th class="text-left">"phase"</th>
+                        <th>"time"</th>
+                        <th>"moves"</th>
+                        <th>"tps"</th>
+                        <th>"vs avg"</th>
+                    </tr>
+                </thead>
+                <tbody>{table.into_iter().map(row).collect_view()}</tbody>
+            </table>
+        })}
+    }
+}
+
+use std::time::Duration;
+
+use leptos::*;
+
+use macros::classes;
+
+use crate::puzzle::AnyPuzzle;
+use crate::settings::use_settings;
+use crate::stats::{session_start, use_solves, Solve};
@@ -0,0 +1,191 @@
+//! Motion driven frame by frame from `requestAnimationFrame`, for what CSS
+//! transitions can't express: staggering, starting from a computed position,
+//! or being cut short by game state, and physics in place of transitions.
+
+pub type Easing = fn(f64) -> f64;
+
+pub fn ease_out_cubic(t: f64) -> f64 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Progress from 0 to 1 over a fixed duration, restarted by [`Tween::play`].
+/// Frames are only requested while it runs.
+#[derive(Clone, Copy)]
+pub struct Tween {
+    /// Linear progress; easing is applied on read.
+    progress: RwSignal<f64>,
+    /// Bumped by every `play` and `finish`, so a stale frame loop stops.
+    generation: StoredValue<u64>,
+    duration: Duration,
+    easing: Easing,
+    clock: Clock,
+}
+
+impl Tween {
+    /// A tween that starts out finished.
+    pub fn new(duration: Duration, easing: Easing) -> Self {
+        Self {
+            progress: create_rw_signal(1.0),
+            generation: store_value(0),
+            duration,
+            easing,
+            clock: use_clock(),
+        }
+    }
+
+    pub fn play(&self) {
+        let generation = self.generation.get_value() + 1;
+        self.generation.set_value(generation);
+        self.progress.set(0.0);
+
+        let started = self.clock.now();
+        let this = *self;
+        frame_loop(move || {
+            if this.generation.get_value() != generation {
+                return false;
+            }
+            let t = this.clock.elapsed(started).as_secs_f64() / this.duration.as_secs_f64();
+            this.progress.set(t.min(1.0));
+            t < 1.0
+        });
+    }
+
+    /// Jumps to the end.
+    pub fn finish(&self) {
+        self.generation.update_value(|generation| *generation += 1);
+        if self.progress.get_untracked() < 1.0 {
+            self.progress.set(1.0);
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.progress.with(|&progress| progress < 1.0)
+    }
+
+    /// Eased progress, from 0 to 1.
+    pub fn value(&self) -> f64 {
+        (self.easing)(self.progress.get())
+    }
+
+    /// Eased progress of the `index`th of `count` items starting one after
+    /// another, each taking `1 - spread` of the whole duration.
+    pub fn staggered(&self, index: usize, count: usize, spread: f64) -> f64 {
+        let offset = spread * index as f64 / count.max(1) as f64;
+        let t = (self.progress.get() - offset) / (1.0 - spread);
+        (self.easing)(t.clamp(0.0, 1.0))
+    }
+}
+
+/// How stiff [`Spring`]s are, per second squared, and how much of critical
+/// damping they get; a little under it overshoots just visibly.
+const SPRING_STIFFNESS: f64 = 500.0;
+const SPRING_DAMPING_RATIO: f64 = 0.75;
+/// Close enough to the target, in cells and cells per second, to stop.
+const SPRING_REST: f64 = 0.002;
+/// Longer frames, e.g. after the tab was hidden, are integrated as this.
+const MAX_STEP: f64 = 1.0 / 30.0;
+
+/// A point pulled towards a moving target by a damped spring, stepped once
+/// per frame while it's out of place. Disabled, it just sits on the target.
+#[derive(Clone, Copy)]
+pub struct Spring {
+    position: RwSignal<(f64, f64)>,
+    velocity: StoredValue<(f64, f64)>,
+    target: StoredValue<(f64, f64)>,
+    running: StoredValue<bool>,
+    clock: Clock,
+}
+
+impl Spring {
+    pub fn follow(
+        target: impl Fn() -> (f64, f64) + 'static,
+        enabled: impl Fn() -> bool + 'static,
+    ) -> Self {
+        let start = untrack(&target);
+        let spring = Self {
+            position: create_rw_signal(start),
+            velocity: store_value((0.0, 0.0)),
+            target: store_value(start),
+            running: store_value(false),
+            clock: use_clock(),
+        };
+
+        create_effect(move |_| {
+            let target = target();
+            spring.target.set_value(target);
+            match enabled() {
+                true => spring.wake(),
+                false => {
+                    spring.velocity.set_value((0.0, 0.0));
+                    spring.position.set(target);
+                }
+            }
+        });
+
+        spring
+    }
+
+    pub fn get(&self) -> (f64, f64) {
+        self.position.get()
+    }
+
+    fn wake(self) {
+        if self.running.get_value() {
+            return;
+        }
+        self.running.set_value(true);
+
+        let last = store_value(self.clock.now());
+        frame_loop(move || {
+            let now = self.clock.now();
+            let dt = (now - last.get_value()).as_secs_f64().min(MAX_STEP);
+            last.set_value(now);
+
+            let settled = self.step(dt);
+            self.running.set_value(!settled);
+            !settled
+        });
+    }
+
+    /// Semi-implicit Euler; returns whether it has come to rest.
+    fn step(&self, dt: f64) -> bool {
+        let damping = 2.0 * SPRING_DAMPING_RATIO * SPRING_STIFFNESS.sqrt();
+        let (tx, ty) = self.target.get_value();
+        let (mut x, mut y) = self.position.get_untracked();
+        let (mut vx, mut vy) = self.velocity.get_value();
+
+        vx += (SPRING_STIFFNESS * (tx - x) - damping * vx) * dt;
+        vy += (SPRING_STIFFNESS * (ty - y) - damping * vy) * dt;
+        x += vx * dt;
+        y += vy * dt;
+
+        let settled = [tx - x, ty - y, vx, vy]
+            .iter()
+            .all(|value| value.abs() < SPRING_REST);
+        if settled {
+            (x, y, vx, vy) = (tx, ty, 0.0, 0.0);
+        }
+        self.velocity.set_value((vx, vy));
+        self.position.set((x, y));
+        settled
+    }
+}
+
+/// Calls `step` once per frame for as long as it returns `true`.
+fn frame_loop(step: impl Fn() -> bool + 'static) {
+    request_animation_frame(move || {
+        if step() {
+            frame_loop(step);
+        }
+    });
+}
+
+pub fn lerp(from: f64, to: f64, t: f64) -> f64 {
+    from + (to - from) * t
+}
+
+use core::time::Duration;
+
+use leptos::*;
+
+use crate::clock::{use_clock, Clock};
@@ -1,306 +1,753 @@
 #[component]
 pub fn App() -> impl IntoView {
-    let puzzle = create_rw_signal(SeedablePuzzle::<usize>::new((4, 4)));
-    let shape = create_memo(move |_| with!(|puzzle| puzzle.shape()));
-    let seed = create_memo(move |_| with!(|puzzle| *puzzle.seed()));
-    let seed_formatted = create_memo(move |_| {
-        BASE64_URL_SAFE
-            .encode(seed())
-            .chars()
-            .chunks(11)
-            .into_iter()
-            .map(|chunk| chunk.collect::<String>())
-            .join("\n")
+    migrations::run();
+    provide_clock(Clock::system());
+    let clock = use_clock();
+    let settings = provide_settings();
+    let solves = provide_solves();
+    let KeyUsage(key_usage) = provide_key_usage();
+    provide_goals();
+    let toasts = provide_toasts();
+    let session_log = provide_session_log();
+    let marks = provide_scramble_marks();
+    offer_previous_session(toasts);
+    watch_for_updates(toasts);
+    let telemetry = provide_telemetry(settings);
+    let overlays = provide_overlays();
+    let owner = Owner::current().expect("App to have an owner");
+
+    let link = DeepLink::current();
+    // The link's board count is for this visit only, in place of the setting
+    // until that's changed.
+    let linked_boards = create_rw_signal(link.boards);
+    let boards_setting = memo!(|settings| settings.boards);
+    create_effect(move |initialized: Option<()>| {
+        boards_setting.track();
+        if initialized.is_some() {
+            linked_boards.set(None);
+        }
     });
-    let pieces_sorted = create_memo(move |_| {
-        with!(|puzzle| {
-            let (width, height) = puzzle.shape();
-            puzzle.iter_indexed().fold(
-                vec![Default::default(); width * height].into_boxed_slice(),
-                |mut pieces, (idx2, &piece)| {
-                    pieces[piece] = idx2;
-                    pieces
-                },
-            )
-        })
+    if let Some(size) = link.tile_size {
+        embed::set_tile_size(size);
+    }
+    let embedded = link.embed;
+    let linked_replay = create_rw_signal(link.replay.clone());
+
+    let primary = BoardController::new(link.puzzle());
+    let puzzle = primary.puzzle;
+    let shape = memo!(|puzzle| puzzle.shape());
+
+    // Boards beyond the first, which share its shape and the one timer.
+    let extra_boards = create_rw_signal(Vec::<BoardController>::new());
+    let focus = create_rw_signal(0usize);
+    let board_count = create_memo(move |_| match linked_boards() {
+        Some(boards) => boards.clamp(1, MAX_BOARDS) as usize,
+        None => settings.with(Settings::board_count),
+    });
+    let focus_mode = memo!(|settings| settings.focus_mode);
+    let versus = create_rw_signal(link.mode == deep_link::Mode::Versus);
+    let klotski = create_rw_signal(false);
+    let board = move |i: usize| match i {
+        0 => Some(primary),
+        i => extra_boards.with_untracked(|boards| boards.get(i - 1).copied()),
+    };
+    let moves = Signal::derive(move || {
+        primary.moves.get()
+            + extra_boards
+                .with(|boards| boards.iter().map(|board| board.moves.get()).sum::<usize>())
     });
 
-    let history = create_rw_signal(String::new());
+    let history = create_rw_signal(Vec::<LoggedKey>::new());
+    // The day whose challenge is loaded, if it's a daily one.
+    let daily_day = create_rw_signal(link.daily_day());
+    let session = SessionSeed::new();
+    // Where the loaded scramble falls in the seeded session, if it's from one.
+    let session_index = create_rw_signal(None::<u32>);
     let dev_mode = create_rw_signal(false);
+    #[cfg(feature = "devtools")]
+    let dev_unlock = DevUnlock::new();
     let game_state = create_rw_signal(GameState::NotSolving);
+    // When the board could first be played, and how long the first move took
+    // after that.
+    let revealed = store_value(Some(clock.now()));
+    let reaction = store_value(None::<Duration>);
 
     let timer_secs_ref = create_node_ref::<Div>();
     let timer_millis_ref = create_node_ref::<Div>();
-    let input_ref = create_node_ref::<Input>();
+    let log_ref = create_node_ref::<Div>();
 
-    let slide = move |idx| {
-        let moved = match puzzle.update_if_some(move |p| p.slide_from(idx)) {
-            Some(moved @ 1..) => moved,
-            _ => return 0,
+    let dispatch = move |event: GameEvent| {
+        game_state.update_if(move |state| state.apply(event));
+    };
+    let countdown = Countdown::new(move |at| {
+        revealed.set_value(Some(at));
+        dispatch(GameEvent::Start { at });
+    });
+    let competition = Competition::new();
+    let plan = PlanMarkers::new(game_state, overlays);
+
+    // `at` is the time of the input event that caused the slide, not the time
+    // it's processed, so the timer starts and stops on the exact keypress.
+    let slide = move |board: BoardController, idx, at: Instant| {
+        if countdown.is_running() {
+            return 0;
+        }
+        let moved = match board.slide(idx) {
+            0 => return 0,
+            moved => moved,
         };
 
-        #[rustfmt::skip]
-        request_animation_frame(move || return_with_try! {
-            input_ref.get_untracked()?.set_scroll_left(i32::MAX);
-        });
+        let was_finished = game_state.with_untracked(GameState::is_finished);
+        if let GameState::Inspecting { since } = game_state.get_untracked() {
+            competition.inspected(at - since);
+        }
+        let solved = primary.is_solved_untracked()
+            && extra_boards
+                .with_untracked(|boards| boards.iter().all(BoardController::is_solved_untracked));
+        // A paused solve, e.g. one restored after a reload, picks up again
+        // with its first move.
+        if game_state.with_untracked(|state| matches!(state, GameState::Paused { .. })) {
+            dispatch(GameEvent::Resume { at });
+        }
+        dispatch(GameEvent::Move { at, solved });
 
-        game_state.update_guarded(|mut state| match *state {
-            GameState::NotSolving => {
-                *state = GameState::Solving {
-                    since: Instant::now(),
-                };
+        if !was_finished {
+            if moves.get_untracked() == 1 {
+                reaction.set_value(revealed.get_value().map(|revealed| at - revealed));
             }
-            GameState::Solving { since } if puzzle.with_untracked(|puzzle| puzzle.is_solved()) => {
-                *state = GameState::Solved {
-                    took: since.elapsed(),
-                };
+            if let Some(time) = game_state.with_untracked(|state| state.solve_time(at)) {
+                board.record(TimedMove {
+                    at: time,
+                    cell: idx,
+                });
             }
-            _ => {}
+        }
+
+        let (false, GameState::Solved { took }) = (was_finished, game_state.get_untracked()) else {
+            return moved;
+        };
+        session_log.append(session_log::Entry::Solved {
+            scramble: puzzle.with_untracked(|puzzle| puzzle.scramble().encode_base32()),
+            time: took,
+            moves: moves.get_untracked(),
+        });
+        if embedded {
+            embed::report(&embed::Report::Solved {
+                scramble: puzzle.with_untracked(|puzzle| puzzle.scramble().encode_base32()),
+                time_ms: took.as_millis() as u64,
+                moves: moves.get_untracked(),
+            });
+        }
+
+        // Multi-board attempts aren't recorded: a `Solve` is one scramble, and
+        // mixing them in would skew averages and personal bests.
+        let boards = board_count.get_untracked();
+        if boards > 1 {
+            settings.with_untracked(|settings| {
+                let time = format!(
+                    "{}.{}",
+                    took.as_secs(),
+                    settings.timer_precision.format_fraction(took)
+                );
+                toasts.show(format!("Solved {boards} boards in {time}"));
+                speech::announce(&format!("{time} seconds"), &settings.speech);
+            });
+            return moved;
+        }
+
+        let scramble = puzzle.with_untracked(AnyPuzzle::scramble);
+        let (width, height) = scramble.shape;
+        telemetry.record(telemetry::Event::Solve { width, height });
+        let previous_best = solves.with_untracked(|solves| personal_best(solves, scramble.shape));
+
+        let solve = Solve {
+            id: Solve::new_id(),
+            scramble,
+            time: took,
+            moves: primary.moves.get_untracked(),
+            finished_at: js_sys::Date::now(),
+            hand_timed: false,
+            penalty: competition.penalty(),
+            replay: primary.replay.get_untracked(),
+            imported_scramble: None,
+            daily: daily_day.get_untracked().map(DailyAttempt::now),
+            session_index: session_index.get_untracked(),
+            reaction: reaction.get_value(),
+            flags: Vec::new(),
+        };
+        // Checked once here, since the solver makes it too slow for every render.
+        let solve = Solve {
+            flags: integrity::check(&solve),
+            ..solve
+        };
+        competition.record(solve.clone());
+        solves.update(|solves| solves.push(solve));
+
+        settings.with_untracked(|settings| {
+            let mut text = format!(
+                "{}.{} seconds",
+                took.as_secs(),
+                settings.timer_precision.format_fraction(took),
+            );
+            if previous_best.is_some_and(|best| took < best) {
+                text.push_str(". New personal best!");
+            }
+            speech::announce(&text, &settings.speech);
         });
 
         moved
     };
 
+    // Every other board gets a fresh random scramble of the same shape.
+    let reload_extra_boards = move |count: usize| {
+        let (shape, setting) = (
+            shape.get_untracked(),
+            settings.with_untracked(|settings| settings.scramble),
+        );
+        let boards = with_owner(owner, move || {
+            (1..count)
+                .map(|_| BoardController::new(setting.random(shape).puzzle()))
+                .collect::<Vec<_>>()
+        });
+        let previous = extra_boards.get_untracked();
+        extra_boards.set(boards);
+        for board in previous {
+            board.dispose();
+        }
+        focus.set(0);
+    };
+
+    let load_puzzle = move |next: AnyPuzzle| {
+        countdown.cancel();
+        if moves.get_untracked() > 0 && !game_state.with_untracked(GameState::is_finished) {
+            session_log.append(session_log::Entry::Aborted {
+                scramble: puzzle.with_untracked(|puzzle| puzzle.scramble().encode_base32()),
+                moves: moves.get_untracked(),
+                elapsed: game_state.with_untracked(|state| state.solve_time(clock.now())),
+                keys: history.with_untracked(|history| {
+                    history.iter().map(|logged| logged.key.as_str()).collect()
+                }),
+            });
+        }
+        session_log.append(session_log::Entry::Scramble {
+            scramble: next.scramble().encode_base32(),
+        });
+        primary.load(next);
+        daily_day.set(None);
+        session_index.set(None);
+        reload_extra_boards(board_count.get_untracked());
+        history.update(|history| history.clear());
+        dispatch(GameEvent::Reset);
+        revealed.set_value(Some(clock.now()));
+        reaction.set_value(None);
+    };
+
+    let new_scramble = move || {
+        // Blacklisted scrambles of a seeded session are passed over, index and all.
+        let next = iter::from_fn(|| session.next()).find(|(_, scramble)| !marks.skips(scramble));
+        match next {
+            Some((index, scramble)) => {
+                load_puzzle(scramble.puzzle());
+                session_index.set(Some(index));
+            }
+            None => {
+                let setting = settings.with_untracked(|settings| settings.scramble);
+                load_puzzle(marks.random(shape.get_untracked(), setting).puzzle());
+            }
+        }
+    };
+
+    // Competition attempts go straight into inspection.
+    let start_attempt = move |scramble: Scramble| {
+        load_puzzle(scramble.puzzle());
+        dispatch(GameEvent::Inspect { at: clock.now() });
+    };
+
+    if embedded {
+        embed::listen(move |command| match command {
+            embed::Command::Start {
+                scramble: Some(code),
+            } => match Scramble::parse(&code) {
+                Ok(scramble) => load_puzzle(scramble.puzzle()),
+                Err(err) => log::warn!("ignoring scramble from the host page: {err}"),
+            },
+            embed::Command::Start { scramble: None } => new_scramble(),
+            embed::Command::Reset => {
+                load_puzzle(puzzle.with_untracked(AnyPuzzle::scramble).puzzle());
+            }
+        });
+    }
+
+    let hooks = plugin::install(
+        plugin::REGISTERED,
+        puzzle.into(),
+        game_state.into(),
+        overlays,
+    );
+
+    create_effect(move |previous: Option<usize>| {
+        let count = board_count();
+        reload_extra_boards(count);
+        if previous.is_some() {
+            dispatch(GameEvent::Reset);
+        }
+        count
+    });
+
+    // Picks up a solve interrupted by a reload, paused where it was left.
+    if let Some((scramble, saved)) =
+        resume::load().and_then(|saved| Some((saved.scramble()?, saved)))
+    {
+        let mut restored = scramble.puzzle();
+        for timed in &saved.replay {
+            restored.slide_from(timed.cell);
+        }
+        primary.puzzle.set(restored);
+        primary.moves.set(saved.moves);
+        primary.replay.set(saved.replay);
+        history.set(saved.history);
+        game_state.set(GameState::Paused {
+            elapsed: saved.elapsed,
+        });
+        toasts.push(Toast {
+            message: "Restored your unfinished solve".to_owned(),
+            action: Some((
+                "Discard",
+                Callback::new(move |()| load_puzzle(AnyPuzzle::new(shape.get_untracked()))),
+            )),
+        });
+    }
+
+    session_log.append(session_log::Entry::Scramble {
+        scramble: puzzle.with_untracked(|puzzle| puzzle.scramble().encode_base32()),
+    });
+
+    resume::autosave(move || {
+        if board_count() > 1 {
+            return None;
+        }
+        let elapsed = game_state.with(|state| match state {
+            GameState::Solving { .. } | GameState::Paused { .. } => state.solve_time(clock.now()),
+            _ => None,
+        })?;
+        Some(InProgress {
+            scramble: puzzle.with(|puzzle| puzzle.scramble().encode_base32()),
+            replay: primary.replay.get(),
+            history: history.get(),
+            moves: primary.moves.get(),
+            elapsed,
+        })
+    });
+
+    // The Stackmat is stopped by hand after the last move, so its time replaces
+    // the keyboard one of a solve that has only just been recorded.
+    let on_stackmat_stop = move |time: Duration| {
+        solves.update_if(|solves| match solves.last_mut() {
+            Some(solve)
+                if !solve.hand_timed && js_sys::Date::now() - solve.finished_at < 60_000.0 =>
+            {
+                solve.time = time;
+                solve.hand_timed = true;
+                true
+            }
+            _ => false,
+        });
+    };
+
     let on_keydown = move |event: KeyboardEvent| {
+        // While an IME is composing, keys go to the composition (and report
+        // "Process" or keyCode 229 in some browsers), not to us.
+        if event.is_composing() || event.key_code() == 229 {
+            return;
+        }
+
         let key = event.key();
 
-        match key.as_ref() {
-            " " => {
-                puzzle.update(|p| *p = SeedablePuzzle::new(p.shape()));
-                history.update(|history| history.clear());
-                game_state.set(GameState::NotSolving);
-            }
+        #[cfg(feature = "devtools")]
+        dev_unlock.feed(&key);
 
-            "D" => dev_mode.update(|dev_mode| *dev_mode = !*dev_mode),
-            "1" => game_state.set(GameState::NotSolving),
-            "2" => game_state.set(GameState::Solving {
-                since: Instant::now(),
-            }),
-            "3" => {
-                if let Some(since) = game_state.with(|state| match *state {
-                    GameState::Solving { since } => Some(since),
-                    _ => None,
-                }) {
-                    game_state.set(GameState::Solved {
-                        took: since.elapsed(),
-                    });
+        let action = Chord::from_event(&event).and_then(|chord| {
+            settings.with_untracked(|settings| settings.shortcuts.action_for(&chord))
+        });
+        match action {
+            Some(Action::NextBoard) if board_count.get_untracked() > 1 => {
+                event.prevent_default();
+                focus.update(|focus| *focus = (*focus + 1) % board_count.get_untracked());
+            }
+            // One attempt per scramble: moving on forfeits the current one.
+            Some(Action::NewScramble) if competition.is_active() => {
+                event.prevent_default();
+                competition.record_dnf();
+                competition.next_attempt(start_attempt);
+            }
+            Some(Action::RetryScramble) if competition.is_active() => {
+                event.prevent_default();
+                toasts.show("No retries in a competition");
+            }
+            Some(Action::NewScramble) => {
+                event.prevent_default();
+                new_scramble();
+                if settings.with_untracked(|settings| settings.countdown) {
+                    countdown.start();
                 }
             }
+            Some(Action::RetryScramble) => {
+                event.prevent_default();
+                let (day, index) = (daily_day.get_untracked(), session_index.get_untracked());
+                load_puzzle(puzzle.with_untracked(AnyPuzzle::scramble).puzzle());
+                daily_day.set(day);
+                session_index.set(index);
+                if settings.with_untracked(|settings| settings.countdown) {
+                    countdown.start();
+                }
+            }
+
+            #[cfg(feature = "devtools")]
+            Some(Action::ToggleDevMode) if dev_unlock.is_unlocked() => {
+                dev_mode.update(|dev_mode| *dev_mode = !*dev_mode)
+            }
+            #[cfg(feature = "devtools")]
+            Some(Action::ForceReset) if dev_unlock.is_unlocked() => dispatch(GameEvent::Reset),
+            #[cfg(feature = "devtools")]
+            Some(Action::ForceStart) if dev_unlock.is_unlocked() => {
+                dispatch(GameEvent::Start { at: clock.now() })
+            }
+            #[cfg(feature = "devtools")]
+            Some(Action::ForceFinish) if dev_unlock.is_unlocked() => {
+                dispatch(GameEvent::Finish { at: clock.now() })
+            }
+
+            // Leave the browser's own shortcuts alone.
+            _ if event.ctrl_key() || event.alt_key() || event.meta_key() => {}
 
             _ => {
-                if let Some(&idx) = KEY_IDX_MAP.get(&key) {
-                    let moved = slide(idx);
+                let keymap = settings.with_untracked(|settings| settings.keymap);
+                if let Some(idx) = keymap.lookup(&key) {
+                    // Otherwise "/" and "'" open quick-find in Firefox.
+                    event.prevent_default();
+                    key_usage.update(|usage| *usage.entry(key.clone()).or_default() += 1);
+                    let Some(board) = board(focus.get_untracked()) else {
+                        return;
+                    };
+                    let moved = slide(board, idx, clock.event_time(&event));
 
                     if moved > 0 {
-                        update!(|history| history.push_str(&key));
+                        let at = game_state
+                            .with_untracked(|state| state.solve_time(clock.event_time(&event)))
+                            .unwrap_or_default();
+                        history.update(|history| history.push(LoggedKey { key, at }));
                     }
+                } else if hooks.press(&key) {
+                    event.prevent_default();
                 }
             }
         }
     };
 
-    #[rustfmt::skip]
-    let render_piece = move |shape: Memo<(usize, usize)>| move |piece| {
-        let (width, _) = shape();
-        let index = create_memo(move |_| {
-            pieces_sorted.with(move |pieces| pieces[piece])
+    // Keys pressed while focus has wandered off the input (after clicking the
+    // board, say) would otherwise scroll the page, so take them back to it.
+    let keydown_handle = window_event_listener(keydown, move |event| {
+        if versus.get_untracked()
+            || klotski.get_untracked()
+            || linked_replay.with_untracked(Option::is_some)
+        {
+            return;
+        }
+        let target = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlElement>().ok());
+        if target.is_some_and(|target| {
+            matches!(
+                target.tag_name().as_str(),
+                "INPUT" | "SELECT" | "TEXTAREA" | "BUTTON"
+            )
+        }) {
+            return;
+        }
+
+        let handled = settings.with_untracked(|settings| {
+            Chord::from_event(&event)
+                .is_some_and(|chord| settings.shortcuts.action_for(&chord).is_some())
+                || !(event.ctrl_key() || event.alt_key() || event.meta_key())
+                    && (settings.keymap.lookup(&event.key()).is_some() || hooks.binds(&event.key()))
         });
-        let opacity = match piece {
-            0 => "opacity-0",
-            _ => "",
-        };
+        if !handled {
+            return;
+        }
 
-        view! {
-            <div
-                class=move || {
-                    let (x, y) = index();
-                    let ideal_piece = y * width + x + 1;
-                    format!(
-                        "absolute w-16 h-16 rounded-lg flex justify-center items-center
-                        font-mono text-2xl shadow transition-all ease-out-circ duration-[100ms]
-                        translate-x-[calc(var(--x)*4.5rem)] translate-y-[calc(var(--y)*4.5rem)]
-                        pointer-events-none {opacity} {}",
-                        match ideal_piece == piece { // is_solved
-                            true => "bg-neutral-100 dark:bg-neutral-200 text-neutral-800",
-                            false => "bg-neutral-900 dark:bg-neutral-800 text-neutral-200",
-                        },
-                    )
-                }
-                style=("--x", move || index().0)
-                style=("--y", move || index().1)
-            >
-                {piece}
-            </div>
+        event.prevent_default();
+        if let Some(log) = log_ref.get_untracked() {
+            _ = log.focus();
         }
-    };
+        on_keydown(event);
+    });
+    on_cleanup(move || keydown_handle.remove());
 
     #[rustfmt::skip]
-    pre_paint(move || return_with_try! {
+    let paint_timer = move || return_with_try! {
         let time = game_state
-            .with(|state| state.solve_time())
+            .with_untracked(|state| state.solve_time(clock.now()))
             .unwrap_or(Duration::ZERO);
 
         let secs = format!("{:02}", time.as_secs());
-        let millis = format!("{:03}", time.subsec_millis());
+        let millis = settings.with_untracked(|settings| settings.timer_precision.format_fraction(time));
+
+        timer_secs_ref.get_untracked()?.set_text_content(Some(&secs));
+        timer_millis_ref.get_untracked()?.set_text_content(Some(&millis));
+    };
+
+    let visible = create_rw_signal(document().visibility_state() == VisibilityState::Visible);
+    let visibility_handle = window_event_listener(visibilitychange, move |_| {
+        let hidden = document().visibility_state() == VisibilityState::Hidden;
+        visible.set(!hidden);
+        if hidden && settings.with_untracked(|settings| settings.pause_when_hidden) {
+            dispatch(GameEvent::Pause { at: clock.now() });
+        }
+    });
+    on_cleanup(move || visibility_handle.remove());
+
+    // The timer only needs painting every frame while it runs and can be seen;
+    // otherwise once per change is enough, which lets an idle or hidden page
+    // stop requesting frames. Coming back repaints it straight away.
+    let solving = memo!(|game_state, visible| game_state.is_solving() && *visible);
+    let ticking = store_value(false);
+    create_effect(move |_| {
+        game_state.track();
+        visible.track();
+        settings.with(|settings| settings.timer_precision);
+        request_animation_frame(paint_timer);
 
-        timer_secs_ref()?.set_text_content(Some(&secs));
-        timer_millis_ref()?.set_text_content(Some(&millis));
+        if solving() && !ticking.get_value() {
+            ticking.set_value(true);
+            let running = move || {
+                let running = solving.get_untracked();
+                ticking.set_value(running);
+                running
+            };
+            pre_paint(running, paint_timer);
+        }
     });
 
+    let live = Signal::derive(move || game_state.with(GameState::is_live));
+    let solved = Signal::derive(move || matches!(game_state(), GameState::Solved { .. }));
+
+    #[cfg(feature = "devtools")]
+    let dev_panel = view! { <DevPanel dev_mode puzzle game_state /> };
+    #[cfg(not(feature = "devtools"))]
+    let dev_panel = ();
+
     view! {
         <div class="flex h-[100dvh] w-full place-content-evenly">
-            <div class=move || format!(
-                "flex my-auto justify-center items-start
-                ease-out-circ transition-all transform-gpu duration-150 {}",
-                match dev_mode() {
-                    false => "translate-x-0",
-                    true => "-translate-x-16",
-                },
+            <Backdrop />
+            {(!embedded).then(|| view! {
+                <ProfileSwitcher />
+                <SettingsPanel />
+                <Show when=move || !focus_mode() fallback=|| ()>
+                    <StatsPanel />
+                </Show>
+                <ToastList />
+                <SolveSummary />
+                <PluginHud hooks />
+                <ScramblePanel
+                    shape
+                    current=Signal::derive(move || puzzle.with(AnyPuzzle::scramble))
+                    on_load=move |scramble: Scramble| load_puzzle(scramble.puzzle())
+                    session
+                    on_session_start=move |()| new_scramble()
+                />
+                <CrowdPlay
+                    puzzle
+                    on_slide=move |(idx, at): ((usize, usize), Instant)| {
+                        slide(primary, idx, at);
+                    }
+                />
+                <CompetitionPanel competition game_state on_attempt=start_attempt />
+                <DailyCalendar on_play=move |day| {
+                    load_puzzle(daily::scramble(day).puzzle());
+                    daily_day.set(Some(day));
+                } />
+                <button
+                    class="fixed top-3 right-[7.5rem] w-8 h-8 rounded-md text-xl opacity-60 hover:opacity-100"
+                    title="Two-player versus"
+                    on:click=move |event| {
+                        // So Space starts races instead of pressing this again.
+                        _ = event_target::<HtmlElement>(&event).blur();
+                        versus.update(|versus| *versus = !*versus);
+                        klotski.set(false);
+                    }
+                >
+                    "⚔"
+                </button>
+                <button
+                    class="fixed top-3 right-[16.5rem] w-8 h-8 rounded-md text-xl opacity-60 hover:opacity-100"
+                    title="Klotski"
+                    on:click=move |event| {
+                        _ = event_target::<HtmlElement>(&event).blur();
+                        klotski.update(|klotski| *klotski = !*klotski);
+                        versus.set(false);
+                    }
+                >
+                    "▦"
+                </button>
+            })}
+            <div class=move || classes!(
+                "flex justify-center items-start
+                ease-out-circ transition-all transform-gpu duration-150",
+                dev_mode() => "-translate-x-16" else "translate-x-0",
+                focus_mode() => "w-full" else "my-auto",
+                versus() || klotski() => "hidden",
             )>
-                <div class="flex flex-col">
-                    <div class="grid grid-flow-col grid-cols-[1fr_min-content_1fr]
-                                child:font-mono child:flex child:items-end">
-                        <div class="text-5xl justify-end" _ref=timer_secs_ref>"00"</div>
-                        <div class="text-2xl">"."</div>
-                        <div class="text-2xl" _ref=timer_millis_ref>"000"</div>
+                <ScrambleDiagram puzzle />
+                <div class=move || classes!("flex flex-col", focus_mode() => "w-full")>
+                    <div class=move || classes!(
+                        "grid grid-flow-col grid-cols-[1fr_min-content_1fr]
+                        child:font-mono child:flex child:items-end",
+                        focus_mode() => "h-[50dvh] content-center",
+                    )>
+                        <div
+                            class=move || classes!("justify-end", focus_mode() => "text-[18vmin] leading-none" else "text-5xl")
+                            _ref=timer_secs_ref
+                        >
+                            "00"
+                        </div>
+                        <div class=move || classes!(focus_mode() => "text-[9vmin] leading-none" else "text-2xl")>"."</div>
+                        <div
+                            class=move || classes!(focus_mode() => "text-[9vmin] leading-none" else "text-2xl")
+                            _ref=timer_millis_ref
+                        >
+                            "000"
+                        </div>
                     </div>
-                    <div class="mx-auto my-4 grid grid-cols-4 gap-2">
-                        <For
-                            each=move || shape.with(|&(w, h)| (0..w * h))
-                            key=Clone::clone
-                            children=render_piece(shape)
-                        />
-                        <For
-                            each=move || shape.with(|&(w, h)| (0..w * h))
-                            key=Clone::clone
-                            children=move |index| {
-                                let (width, _) = shape();
-                                let slide = move |event: Event| {
-                                    event.prevent_default();
-                                    slide((index % width, index / width));
-                                };
-                                view! {
-                                    <div
-                                        class="w-16 h-16"
-                                        on:mousedown=move |e| slide(e.into())
-                                        on:mousemove=move |e| if e.buttons() & 1 == 1 { slide(e.into()) }
-                                        on:touchstart=move |e| slide(e.into())
-                                        // on:touchmove=move |e| slide(e.into())
-                                    />
+                    <StackmatInput on_stop=on_stackmat_stop />
+                    <Pacer game_state moves />
+                    <PbPace game_state moves shape />
+                    <div class="relative flex justify-center gap-8">
+                        <CountdownBanner countdown />
+                        <BoardView
+                            puzzle
+                            on_slide=move |(idx, at): ((usize, usize), Instant)| {
+                                focus.set(0);
+                                if plan.is_planning() {
+                                    plan.toggle(idx);
+                                } else {
+                                    slide(primary, idx, at);
                                 }
                             }
+                            focused=Signal::derive(move || board_count() > 1 && focus() == 0)
+                            instant=live
+                            celebrate=solved
+                            overlays
                         />
+                        {move || {
+                            extra_boards
+                                .get()
+                                .into_iter()
+                                .enumerate()
+                                .map(move |(i, board)| view! {
+                                    <BoardView
+                                        puzzle=board.puzzle
+                                        on_slide=move |(idx, at): ((usize, usize), Instant)| {
+                                            focus.set(i + 1);
+                                            slide(board, idx, at);
+                                        }
+                                        focused=Signal::derive(move || focus() == i + 1)
+                                        instant=live
+                                        celebrate=solved
+                                    />
+                                })
+                                .collect_view()
+                        }}
                     </div>
-                    <input _ref=input_ref
-                        type="text"
-                        readonly
-                        class=move || format!(
-                            "mx-auto mb-auto w-[17.5rem] p-2 shadow rounded-md outline-none
-                            ring-inset ring-1 focus:ring-2 font-mono bg-neutral-100 dark:bg-neutral-800 
-                            transition-all ease-out-circ duration-[40ms] {}",
-                            match dev_mode() {
-                                false => "ring-neutral-400 dark:ring-neutral-600 focus:ring-violet-400 focus:dark:ring-violet-500",
-                                true => "ring-yellow-500 dark:ring-yellow-600 focus:ring-yellow-500 focus:dark:ring-yellow-500",
-                            }
-                        )
-                        on:keydown=on_keydown
-                        prop:value=history
+                    <MoveLog
+                        node_ref=log_ref
+                        keys=history
+                        puzzle
+                        replay=primary.replay
+                        on_keydown=on_keydown
+                        hidden=focus_mode
+                        dev_mode
                     />
+                    <PhaseTable when=move || !focus_mode() && game_state.with(GameState::is_finished) />
                 </div>
-                <div class=move || format!(
-                    "ease-out-circ transition-all transform-gpu duration-150 {}",
-                    match dev_mode() {
-                        false => "-translate-x-6 opacity-0",
-                        true => "translate-x-0",
-                    },
-                )>
-                    <AnimatedShow when=dev_mode hide_delay=Duration::from_millis(150)>
-                        <div class="absolute ml-6 mt-3">
-                            <pre class="mb-3">{seed_formatted}</pre>
-                            <pre class="text-sm">"is_solved(): "{move || with!(|puzzle| puzzle.is_solved())}</pre>
-                            <pre class="text-sm">"game_state: "{move || format!("{:#?}", game_state())}</pre>
-                        </div>
-                    </AnimatedShow>
-                </div>
+                <GhostBoard puzzle moves=primary.moves />
+                {dev_panel}
             </div>
+            <Show when=versus fallback=|| ()>
+                <Versus shape />
+            </Show>
+            <Show when=klotski fallback=|| ()>
+                <Klotski />
+            </Show>
+            <LinkedReplay file=linked_replay />
         </div>
     }
 }
 
+use std::iter;
 use std::time::Duration;
-use wasm_timer::Instant;
 
 use leptos::{ev::*, html::*, *};
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, VisibilityState};
 
-use base64::{prelude::*, Engine};
-use derive_more::*;
-use itertools::Itertools;
-use jugo::{BoxPuzzle, Piece, Puzzle};
-use macros::return_with_try;
-use rand::{Rng, SeedableRng};
-use rand_xoshiro::Xoshiro256StarStar;
+use macros::{classes, memo, return_with_try};
 
+use crate::analysis::PhaseTable;
+use crate::backdrop::Backdrop;
+use crate::board_view::BoardView;
+use crate::clock::{provide_clock, use_clock, Clock, Instant};
+use crate::competition::{Competition, CompetitionPanel};
+use crate::controller::BoardController;
+use crate::countdown::{Countdown, CountdownBanner};
+use crate::crowd::CrowdPlay;
+use crate::daily::{self, DailyAttempt, DailyCalendar};
+use crate::deep_link::{self, DeepLink};
+#[cfg(feature = "devtools")]
+use crate::devtools::{DevPanel, DevUnlock};
+use crate::embed;
+use crate::favorites::provide_scramble_marks;
+use crate::game_state::{GameEvent, GameState};
+use crate::ghost::GhostBoard;
+use crate::goals::provide_goals;
+use crate::integrity;
+use crate::keymap::{Action, Chord};
+use crate::klotski::Klotski;
+use crate::migrations;
+use crate::move_log::{LoggedKey, MoveLog};
+use crate::overlay::provide_overlays;
+use crate::pacer::{Pacer, PbPace};
+use crate::plan_markers::PlanMarkers;
+use crate::plugin::{self, PluginHud};
+use crate::profile::ProfileSwitcher;
+use crate::puzzle::AnyPuzzle;
+use crate::replay::LinkedReplay;
+use crate::resume::{self, InProgress};
+use crate::scramble_diagram::ScrambleDiagram;
+use crate::scramble_panel::ScramblePanel;
+use crate::seed::Scramble;
+use crate::session_log::{self, provide_session_log};
+use crate::session_seed::SessionSeed;
+use crate::settings::{provide_settings, Settings, SettingsPanel, MAX_BOARDS};
 use crate::signal_ext::SignalUpdateConditional;
-
-#[rustfmt::skip]
-static KEY_IDX_MAP: phf::Map<&'static str, (usize, usize)> = phf::phf_map! {
-    "4" => (0, 0), "5" => (1, 0), "6" => (2, 0), "7" => (3, 0),
-    "r" => (0, 1), "t" => (1, 1), "y" => (2, 1), "u" => (3, 1),
-    "f" => (0, 2), "g" => (1, 2), "h" => (2, 2), "j" => (3, 2),
-    "v" => (0, 3), "b" => (1, 3), "n" => (2, 3), "m" => (3, 3),
-
-    // "R" => (0, 1), "T" => (1, 1), "Y" => (2, 1), "U" => (3, 1),
-    // "F" => (0, 2), "G" => (1, 2), "H" => (2, 2), "J" => (3, 2),
-    // "V" => (0, 3), "B" => (1, 3), "N" => (2, 3), "M" => (3, 3),
+use crate::speech;
+use crate::stackmat::StackmatInput;
+use crate::stats::{
+    offer_previous_session, personal_best, provide_key_usage, provide_solves, KeyUsage, Solve,
+    TimedMove,
 };
+use crate::stats_panel::StatsPanel;
+use crate::summary::SolveSummary;
+use crate::telemetry::{self, provide_telemetry};
+use crate::toast::{provide_toasts, Toast, ToastList};
+use crate::update::watch_for_updates;
+use crate::versus::Versus;
 
-fn pre_paint(callback: impl Clone + Fn() + 'static) {
+/// Calls `callback` before every paint for as long as `running` holds.
+fn pre_paint(running: impl Fn() -> bool + 'static, callback: impl Fn() + 'static) {
     request_animation_frame(move || {
-        untrack(callback.clone());
-        pre_paint(callback); // recurse
-    });
-}
-
-#[derive(Deref, DerefMut)]
-struct SeedablePuzzle<T: Piece> {
-    #[deref]
-    #[deref_mut]
-    puzzle: BoxPuzzle<T>,
-    seed: [u8; 32],
-}
-
-impl<T: Piece> SeedablePuzzle<T> {
-    #[inline]
-    fn new_from_seed(seed: [u8; 32], shape: (usize, usize)) -> Self {
-        Self {
-            puzzle: BoxPuzzle::random_with_rng(&mut Xoshiro256StarStar::from_seed(seed), shape),
-            seed,
-        }
-    }
-    #[inline]
-    fn new(shape: (usize, usize)) -> Self {
-        Self::new_from_seed(rand::thread_rng().gen(), shape)
-    }
-
-    #[inline]
-    fn seed(&self) -> &[u8; 32] {
-        &self.seed
-    }
-}
-
-#[derive(Clone, Debug)]
-enum GameState {
-    NotSolving,
-    Solving { since: Instant },
-    Solved { took: Duration },
-}
-
-impl GameState {
-    pub fn solve_time(&self) -> Option<Duration> {
-        match self {
-            GameState::Solving { since } => Some(since.elapsed()),
-            GameState::Solved { took } => Some(*took),
-            _ => None,
+        if running() {
+            untrack(&callback);
+            pre_paint(running, callback); // recurse
         }
-    }
+    });
 }
@@ -0,0 +1,109 @@
+//! Which arrangement of the pieces counts as solved. Everything that asks where
+//! a piece belongs goes through here: solved detection, in-place colouring,
+//! hints and the solver's target.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Arrangement {
+    /// 1, 2, 3… row by row, with the blank last.
+    #[default]
+    Standard,
+    /// The blank in the top-left corner, then 1, 2, 3… row by row.
+    BlankFirst,
+    /// 1, 2, 3… clockwise around the edge and inwards, with the blank last.
+    Spiral,
+    /// The standard arrangement mirrored left to right: 1, 2, 3… from the
+    /// right of each row, with the blank bottom left. A drill for solving
+    /// without leaning on the usual patterns.
+    Mirrored,
+}
+
+impl Arrangement {
+    pub const ALL: [Arrangement; 4] = [
+        Arrangement::Standard,
+        Arrangement::BlankFirst,
+        Arrangement::Spiral,
+        Arrangement::Mirrored,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Arrangement::Standard => "Standard",
+            Arrangement::BlankFirst => "Blank first",
+            Arrangement::Spiral => "Spiral",
+            Arrangement::Mirrored => "Mirror image",
+        }
+    }
+
+    /// Where `piece` sits once a board of `shape` is solved.
+    pub fn home(self, piece: usize, (width, height): (usize, usize)) -> (usize, usize) {
+        let last = width * height - 1;
+        let rank = match (self, piece) {
+            (Arrangement::BlankFirst, piece) => piece,
+            (_, 0) => last,
+            (_, piece) => piece - 1,
+        };
+        self.cell(rank, (width, height))
+    }
+
+    /// The offset of every piece's home, row by row, indexed by piece.
+    pub fn homes(self, shape: (usize, usize)) -> Vec<usize> {
+        (0..shape.0 * shape.1)
+            .map(|piece| {
+                let (x, y) = self.home(piece, shape);
+                y * shape.0 + x
+            })
+            .collect()
+    }
+
+    /// The `i`th column of a board `width` wide, counted from the side this
+    /// arrangement fills first. Its own inverse.
+    pub fn column(self, i: usize, width: usize) -> usize {
+        match self {
+            Arrangement::Mirrored => width - 1 - i,
+            _ => i,
+        }
+    }
+
+    /// The `rank`th cell, from zero, in this arrangement's reading order.
+    fn cell(self, rank: usize, (width, height): (usize, usize)) -> (usize, usize) {
+        match self {
+            Arrangement::Standard | Arrangement::BlankFirst => (rank % width, rank / width),
+            Arrangement::Spiral => spiral_cell(rank, (width, height)),
+            Arrangement::Mirrored => (width - 1 - rank % width, rank / width),
+        }
+    }
+}
+
+/// Peels off whole rings until `rank` falls in one, then walks its sides.
+fn spiral_cell(mut rank: usize, (mut width, mut height): (usize, usize)) -> (usize, usize) {
+    let mut corner = 0;
+    loop {
+        if height == 1 {
+            return (corner + rank, corner);
+        }
+        if width == 1 {
+            return (corner, corner + rank);
+        }
+
+        let ring = 2 * (width + height) - 4;
+        if rank >= ring {
+            rank -= ring;
+            corner += 1;
+            width -= 2;
+            height -= 2;
+            continue;
+        }
+
+        let (right, bottom) = (corner + width - 1, corner + height - 1);
+        return match rank {
+            rank if rank < width => (corner + rank, corner),
+            rank if rank < width + height - 1 => (right, corner + rank - (width - 1)),
+            rank if rank < 2 * width + height - 2 => {
+                (right - (rank - (width + height - 2)), bottom)
+            }
+            rank => (corner, bottom - (rank - (2 * width + height - 3))),
+        };
+    }
+}
+
+use serde::{Deserialize, Serialize};
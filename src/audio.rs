@@ -0,0 +1,37 @@
+thread_local! {
+    // Created on first use, which is always in response to some input, so
+    // browsers allow it to start.
+    static CONTEXT: OnceCell<Option<AudioContext>> = OnceCell::new();
+}
+
+/// Plays a short sine beep. Silently does nothing without Web Audio.
+pub fn beep(frequency: f32, duration: Duration) {
+    CONTEXT.with(|context| {
+        let Some(context) = context.get_or_init(|| AudioContext::new().ok()) else {
+            return;
+        };
+
+        return_with_try! {
+            let start = context.current_time();
+            let end = start + duration.as_secs_f64();
+
+            let oscillator = context.create_oscillator()?;
+            oscillator.frequency().set_value(frequency);
+            let gain = context.create_gain()?;
+            gain.gain().set_value_at_time(0.3, start)?;
+            gain.gain().exponential_ramp_to_value_at_time(0.001, end)?;
+
+            oscillator.connect_with_audio_node(&gain)?;
+            gain.connect_with_audio_node(&context.destination())?;
+            oscillator.start()?;
+            oscillator.stop_with_when(end)?;
+        }
+    });
+}
+
+use core::cell::OnceCell;
+use core::time::Duration;
+
+use web_sys::AudioContext;
+
+use macros::return_with_try;
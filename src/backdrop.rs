@@ -0,0 +1,21 @@
+/// The custom background from the settings, if any, with its darkening overlay.
+#[component]
+pub fn Backdrop() -> impl IntoView {
+    let settings = use_settings();
+    let background = memo!(|settings| settings.background.css());
+    let dim = memo!(|settings| f64::from(settings.background.dim) / 100.0);
+
+    view! {
+        <Show when=move || background.with(Option::is_some) fallback=|| ()>
+            <div class="fixed inset-0 -z-10 pointer-events-none" style=("background", background)>
+                <div class="absolute inset-0 bg-black" style=("opacity", dim) />
+            </div>
+        </Show>
+    }
+}
+
+use leptos::*;
+
+use macros::memo;
+
+use crate::settings::use_settings;
@@ -0,0 +1,372 @@
+/// What a board cell can hold. `0` is the blank.
+pub trait Tile: Piece + Copy + Eq + Into<usize> + TryFrom<usize> {
+    #[inline]
+    fn from_index(index: usize) -> Self {
+        Self::try_from(index).unwrap_or_else(|_| panic!("{index} doesn't fit in the piece type"))
+    }
+    #[inline]
+    fn index(self) -> usize {
+        self.into()
+    }
+}
+impl<T: Piece + Copy + Eq + Into<usize> + TryFrom<usize>> Tile for T {}
+
+/// A sliding puzzle board, stored row by row.
+///
+/// Scrambles are still generated by `jugo` and copied in, but keeping the board
+/// here lets us build arbitrary states (solved boards, move sequences, ...).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board<T> {
+    pieces: Box<[T]>,
+    width: usize,
+    height: usize,
+    blank: (usize, usize),
+    goal: Arrangement,
+}
+
+impl<T: Tile> Board<T> {
+    pub fn solved(shape: (usize, usize), goal: Arrangement) -> Self {
+        let mut pieces = vec![0; shape.0 * shape.1];
+        for (piece, home) in goal.homes(shape).into_iter().enumerate() {
+            pieces[home] = piece;
+        }
+        Self::from_pieces(shape, pieces.into_iter().map(T::from_index)).with_goal(goal)
+    }
+
+    /// # Panics
+    /// If `pieces` doesn't have exactly one piece per cell, or has no blank.
+    pub fn from_pieces(
+        (width, height): (usize, usize),
+        pieces: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let pieces: Box<[T]> = pieces.into_iter().collect();
+        assert_eq!(pieces.len(), width * height, "wrong number of pieces");
+
+        let blank = pieces
+            .iter()
+            .position(|piece| piece.index() == 0)
+            .expect("board has no blank");
+
+        Self {
+            pieces,
+            width,
+            height,
+            blank: (blank % width, blank / width),
+            goal: Arrangement::Standard,
+        }
+    }
+
+    /// The same pieces, solved when they're in `goal`'s arrangement.
+    pub fn with_goal(self, goal: Arrangement) -> Self {
+        Self { goal, ..self }
+    }
+
+    /// Half of all arrangements can't reach the goal; if this is one of them,
+    /// swaps two tiles so it can. Whether it can is the parity of the tiles'
+    /// permutation away from their homes against how far the blank is from
+    /// its own.
+    pub fn make_solvable(&mut self) {
+        let homes: Vec<usize> = (0..self.pieces.len())
+            .map(|i| {
+                let (x, y) = self.home_of(self.pieces[i]);
+                self.offset((x, y))
+            })
+            .collect();
+        let mut visited = vec![false; homes.len()];
+        let mut cycles = 0;
+        for start in 0..homes.len() {
+            if visited[start] {
+                continue;
+            }
+            cycles += 1;
+            let mut i = start;
+            while !visited[i] {
+                visited[i] = true;
+                i = homes[i];
+            }
+        }
+        let permutation_parity = (homes.len() - cycles) % 2;
+
+        let (home_x, home_y) = self.home_of(T::from_index(0));
+        let blank_distance = self.blank.0.abs_diff(home_x) + self.blank.1.abs_diff(home_y);
+
+        if permutation_parity != blank_distance % 2 {
+            let mut tiles = (0..self.pieces.len()).filter(|&i| self.pieces[i].index() != 0);
+            if let (Some(a), Some(b)) = (tiles.next(), tiles.next()) {
+                self.pieces.swap(a, b);
+            }
+        }
+    }
+
+    /// Builds a solved board and applies `length` random single-tile moves,
+    /// never immediately undoing the previous one.
+    pub fn random_moves(
+        rng: &mut impl Rng,
+        shape: (usize, usize),
+        goal: Arrangement,
+        length: usize,
+    ) -> Self {
+        let mut board = Self::solved(shape, goal);
+        let mut previous_blank = None;
+
+        for _ in 0..length {
+            let candidates: Vec<_> = board
+                .neighbors(board.blank)
+                .filter(|&idx| Some(idx) != previous_blank)
+                .collect();
+            let Some(&idx) = candidates.get(rng.gen_range(0..candidates.len().max(1))) else {
+                break;
+            };
+
+            previous_blank = Some(board.blank);
+            board.slide_from(idx);
+        }
+
+        board
+    }
+
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+    #[inline]
+    pub fn blank(&self) -> (usize, usize) {
+        self.blank
+    }
+    #[inline]
+    pub fn goal(&self) -> Arrangement {
+        self.goal
+    }
+    #[inline]
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<T> {
+        (x < self.width && y < self.height).then(|| self.pieces[y * self.width + x])
+    }
+
+    pub fn iter_indexed(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        let width = self.width;
+        self.pieces
+            .iter()
+            .enumerate()
+            .map(move |(i, piece)| ((i % width, i / width), piece))
+    }
+
+    /// Where `piece` sits once the board is solved.
+    #[inline]
+    pub fn home_of(&self, piece: T) -> (usize, usize) {
+        self.goal.home(piece.index(), self.shape())
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.iter_indexed()
+            .all(|(idx, &piece)| self.home_of(piece) == idx)
+    }
+
+    /// How many leading columns and rows are finished, in the sense of the usual
+    /// row-and-column reduction: a row (or column) counts once every piece in it
+    /// is home and it lies in a prefix of finished rows (columns). The last two
+    /// rows and columns are never counted, since they're solved together.
+    /// Columns lead from the side the goal fills first; see
+    /// [`Arrangement::column`].
+    pub fn solved_prefix(&self) -> (usize, usize) {
+        let (width, height) = self.shape();
+        let (mut columns, mut rows) = (0, 0);
+        let is_home = |(i, y)| {
+            let idx = (self.goal.column(i, width), y);
+            self.get(idx)
+                .is_some_and(|piece| self.home_of(piece) == idx)
+        };
+
+        loop {
+            if height - rows > 2 && (columns..width).all(|i| is_home((i, rows))) {
+                rows += 1;
+            } else if width - columns > 2 && (rows..height).all(|y| is_home((columns, y))) {
+                columns += 1;
+            } else {
+                return (columns, rows);
+            }
+        }
+    }
+
+    /// The in-bounds cells orthogonally adjacent to `(x, y)`.
+    pub fn neighbors(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = self.shape();
+        [
+            x.checked_sub(1).map(|x| (x, y)),
+            (x + 1 < width).then_some((x + 1, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            (y + 1 < height).then_some((x, y + 1)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Slides every piece between `idx` and the blank one cell towards the
+    /// blank, returning how many moved; `None` if `idx` isn't in line with it.
+    pub fn slide_from(&mut self, (x, y): (usize, usize)) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let (bx, by) = self.blank;
+        let cells: Vec<(usize, usize)> = if (x, y) == (bx, by) {
+            return Some(0);
+        } else if y == by && x < bx {
+            (x..=bx).rev().map(|x| (x, y)).collect()
+        } else if y == by {
+            (bx..=x).map(|x| (x, y)).collect()
+        } else if x == bx && y < by {
+            (y..=by).rev().map(|y| (x, y)).collect()
+        } else if x == bx {
+            (by..=y).map(|y| (x, y)).collect()
+        } else {
+            return None;
+        };
+
+        // `cells` runs from the blank to `idx`; pull each piece one step back.
+        for pair in cells.windows(2) {
+            let (to, from) = (self.offset(pair[0]), self.offset(pair[1]));
+            self.pieces.swap(to, from);
+        }
+        self.blank = (x, y);
+
+        Some(cells.len() - 1)
+    }
+
+    #[inline]
+    fn offset(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T: Tile> From<&BoxPuzzle<T>> for Board<T> {
+    fn from(puzzle: &BoxPuzzle<T>) -> Self {
+        Self::from_pieces(
+            puzzle.shape(),
+            puzzle.iter_indexed().map(|(_, &piece)| piece),
+        )
+    }
+}
+
+use jugo::{BoxPuzzle, Piece, Puzzle};
+use rand::Rng;
+
+use crate::arrangement::Arrangement;
+
+#[cfg(test)]
+mod tests {
+    fn board(shape: (usize, usize), goal: Arrangement, pieces: &[u8]) -> Board<u8> {
+        Board::from_pieces(shape, pieces.iter().copied()).with_goal(goal)
+    }
+
+    /// Every arrangement of `goal` reachable from solved, found
+    /// breadth-first.
+    fn reachable(shape: (usize, usize), goal: Arrangement) -> HashSet<Vec<u8>> {
+        let solved = Board::<u8>::solved(shape, goal);
+        let mut seen = HashSet::from([pieces(&solved)]);
+        let mut queue = VecDeque::from([solved]);
+        while let Some(board) = queue.pop_front() {
+            for idx in board.neighbors(board.blank()) {
+                let mut next = board.clone();
+                next.slide_from(idx);
+                if seen.insert(pieces(&next)) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        seen
+    }
+
+    fn pieces(board: &Board<u8>) -> Vec<u8> {
+        board.iter_indexed().map(|(_, &piece)| piece).collect()
+    }
+
+    /// Every ordering of `0..n`.
+    fn permutations(n: u8) -> Vec<Vec<u8>> {
+        match n {
+            0 => vec![Vec::new()],
+            n => permutations(n - 1)
+                .into_iter()
+                .flat_map(|shorter| {
+                    (0..n as usize).map(move |at| {
+                        let mut longer = shorter.clone();
+                        longer.insert(at, n - 1);
+                        longer
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn solved_prefix() {
+        let solved = Board::<u8>::solved((4, 4), Arrangement::Standard);
+        assert_eq!(solved.solved_prefix(), (2, 2));
+
+        #[rustfmt::skip]
+        let left_columns = board((4, 4), Arrangement::Standard, &[
+            1, 3, 2, 4,
+            5, 7, 6, 8,
+            9, 10, 11, 12,
+            13, 14, 15, 0,
+        ]);
+        assert_eq!(left_columns.solved_prefix(), (1, 0));
+    }
+
+    #[test]
+    fn mirrored_solved_prefix() {
+        let solved = Board::<u8>::solved((4, 4), Arrangement::Mirrored);
+        assert_eq!(solved.solved_prefix(), (2, 2));
+
+        // The goal fills each row from the right, so that's where finished
+        // columns start.
+        #[rustfmt::skip]
+        let right_columns = board((4, 4), Arrangement::Mirrored, &[
+            3, 4, 2, 1,
+            7, 8, 6, 5,
+            11, 12, 10, 9,
+            0, 15, 14, 13,
+        ]);
+        assert_eq!(right_columns.solved_prefix(), (2, 0));
+
+        // Home on the left, but the goal doesn't fill that side first.
+        #[rustfmt::skip]
+        let left_columns = board((4, 4), Arrangement::Mirrored, &[
+            4, 3, 1, 2,
+            8, 7, 5, 6,
+            12, 11, 9, 10,
+            0, 15, 13, 14,
+        ]);
+        assert_eq!(left_columns.solved_prefix(), (0, 0));
+
+        #[rustfmt::skip]
+        let top_row = board((4, 4), Arrangement::Mirrored, &[
+            4, 3, 2, 1,
+            8, 7, 5, 6,
+            12, 11, 9, 10,
+            0, 15, 14, 13,
+        ]);
+        assert_eq!(top_row.solved_prefix(), (0, 1));
+    }
+
+    #[test]
+    fn make_solvable() {
+        for goal in Arrangement::ALL {
+            let reachable = reachable((3, 2), goal);
+            for arrangement in permutations(6) {
+                let mut board = board((3, 2), goal, &arrangement);
+                board.make_solvable();
+                assert!(
+                    reachable.contains(&pieces(&board)),
+                    "{goal:?} {arrangement:?}"
+                );
+                if reachable.contains(&arrangement) {
+                    assert_eq!(pieces(&board), arrangement);
+                }
+            }
+        }
+    }
+
+    use std::collections::{HashSet, VecDeque};
+
+    use super::*;
+}
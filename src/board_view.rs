@@ -0,0 +1,256 @@
+pub const TILE_REM: f64 = 4.0;
+const GAP_REM: f64 = 0.5;
+const PITCH_REM: f64 = TILE_REM + GAP_REM;
+const WAVE_STEP: Duration = Duration::from_millis(45);
+
+/// One interactive board: its pieces, their overlays, and the cells that take
+/// mouse and touch input. What a slide does is up to `on_slide`, so the same
+/// component serves however many boards are on screen.
+#[component]
+pub fn BoardView(
+    puzzle: RwSignal<AnyPuzzle>,
+    #[prop(into)] on_slide: Callback<((usize, usize), Instant)>,
+    /// Outlined while true; only meaningful with more than one board.
+    #[prop(optional, into)]
+    focused: Option<Signal<bool>>,
+    /// While true, e.g. with the timer running, the board is shown as it is
+    /// without animating.
+    #[prop(optional, into)]
+    instant: Option<Signal<bool>>,
+    /// Plays the completion effect when it turns true.
+    #[prop(optional, into)]
+    celebrate: Option<Signal<bool>>,
+    /// Where annotations for this board go; its own if not given.
+    #[prop(optional)]
+    overlays: Option<Overlays>,
+) -> impl IntoView {
+    let clock = use_clock();
+    let settings = use_settings();
+    let on_slide = store_value(on_slide);
+
+    let model = BoardViewModel::new(puzzle, instant, overlays.unwrap_or_default());
+    let BoardViewModel {
+        shape,
+        drag,
+        hovered,
+        ..
+    } = model;
+
+    let skin = memo!(|settings| settings.skin.clone());
+    let mirrored = memo!(|settings| settings.mirror_board);
+    let completion_effect = memo!(|settings| settings.completion_effect);
+    let springy = memo!(|settings| settings.motion == Motion::Spring);
+    let celebrating = move || celebrate.is_some_and(|celebrate| celebrate());
+
+    show_hover_hint(model.overlays, puzzle, hovered);
+    show_distance_heat(model.overlays, puzzle);
+
+    let drag_tiles = memo!(|settings| settings.drag_tiles);
+    let container = create_node_ref::<Div>();
+
+    #[rustfmt::skip]
+    let render_piece = move |shape: Memo<(usize, usize)>| move |piece| {
+        let (width, height) = shape();
+        // The goal can change under the same shape when a new scramble loads.
+        let home = create_memo(move |_| model.home_of(piece));
+        let index = create_memo(move |_| model.cell_of(piece));
+        let spring = Spring::follow(move || model.position(piece), springy);
+
+        let in_place = create_memo(move |_| home() == index());
+        let region_style = create_memo(move |_| model.region_style(index()));
+        // Only changes when the tile comes home or leaves, not on every move.
+        let skin_classes = create_memo(move |_| {
+            classes!(
+                "w-full h-full flex justify-center items-center",
+                skin.with(|skin| skin.classes(in_place())),
+                region_style() == SolvedRegionStyle::Dim => "opacity-40",
+                region_style() == SolvedRegionStyle::Outline => "ring-2 ring-inset ring-emerald-500",
+            )
+        });
+        // These follow the tween, the pointer or the spring directly.
+        let direct = move || model.is_driven(piece) || springy();
+
+        view! {
+            <div
+                class=classes!(
+                    "absolute w-[var(--tile)] h-[var(--tile)]
+                    ease-out-circ duration-[100ms]
+                    translate-x-[calc(var(--x)*var(--pitch))] translate-y-[calc(var(--y)*var(--pitch))]
+                    pointer-events-none",
+                    piece == 0 => "opacity-0",
+                )
+                class=("transition-none", direct)
+                class=("transition-all", move || !direct())
+                // Undoes the board's flip so labels still read normally.
+                class=("-scale-x-100", move || mirrored())
+                class=("animate-solve-wave", move || celebrating() && completion_effect() == CompletionEffect::Wave)
+                class=("animate-solve-pulse", move || celebrating() && completion_effect() == CompletionEffect::Pulse)
+                style=("--x", move || spring.get().0)
+                style=("--y", move || spring.get().1)
+                // The wave reaches each piece one diagonal after the last.
+                style=("animation-delay", move || {
+                    let (x, y) = index();
+                    (completion_effect() == CompletionEffect::Wave)
+                        .then(|| format!("{}ms", (x + y) as u64 * WAVE_STEP.as_millis() as u64))
+                })
+                style=("opacity", move || (piece != 0 && model.entering()).then(|| model.opacity(piece).to_string()))
+            >
+                <div
+                    class=move || skin_classes()
+                    style=("background", move || skin.with(|skin| skin.background(home(), (width, height))))
+                >
+                    {move || (piece != 0).then(|| skin.with(|skin| skin.label(piece)))}
+                </div>
+            </div>
+        }
+    };
+
+    // One layer takes all pointer input and works out the cell from where it
+    // landed, rather than a listening element per cell.
+    let cell_at = move |event: &PointerEvent| -> Option<((usize, usize), f64)> {
+        let rect = container.get_untracked()?.get_bounding_client_rect();
+        let (width, height) = shape.get_untracked();
+        let pitch = rect.width() / (width as f64 - GAP_REM / PITCH_REM);
+        let x = (event.client_x() as f64 - rect.left()) / pitch;
+        let y = (event.client_y() as f64 - rect.top()) / pitch;
+        // The gaps between tiles don't count as either neighbour.
+        let in_tile = |at: f64| at >= 0.0 && at.fract() < TILE_REM / PITCH_REM;
+        if !in_tile(x) || !in_tile(y) || x as usize >= width || y as usize >= height {
+            return None;
+        }
+        let x = match mirrored.get_untracked() {
+            true => width - 1 - x as usize,
+            false => x as usize,
+        };
+        Some(((x, y as usize), pitch))
+    };
+    let slide_at = move |cell: (usize, usize), at: Instant| {
+        on_slide.with_value(|on_slide| on_slide.call((cell, at)));
+    };
+
+    let on_pointerdown = move |event: PointerEvent| {
+        if event.button() != 0 {
+            return;
+        }
+        event.prevent_default();
+        let Some((cell, pitch)) = cell_at(&event) else {
+            return;
+        };
+        let at = clock.event_time(&event);
+        if !drag_tiles() {
+            slide_at(cell, at);
+            return;
+        }
+
+        return_with_try! {
+            let target: Element = event.current_target()?.dyn_into()?;
+            target.set_pointer_capture(event.pointer_id())?;
+            let pointer = (event.client_x() as f64, event.client_y() as f64);
+            drag.set(puzzle.with_untracked(|puzzle| {
+                Drag::start(puzzle, cell, pointer, pitch, mirrored.get_untracked(), at)
+            }));
+        }
+    };
+    let on_pointermove = move |event: PointerEvent| {
+        let at = clock.event_time(&event);
+        let dragging = drag.update_if(|drag| match drag {
+            Some(drag) => {
+                drag.update((event.client_x() as f64, event.client_y() as f64), at);
+                true
+            }
+            None => false,
+        });
+        if dragging {
+            return;
+        }
+
+        let cell = cell_at(&event).map(|(cell, _)| cell);
+        if cell == hovered.get_untracked() {
+            return;
+        }
+        hovered.set(cell);
+        // Sweeping over tiles with the mouse button held slides each in turn.
+        let sweeping = event.pointer_type() == "mouse" && event.buttons() & 1 == 1;
+        if let Some(cell) = cell.filter(|_| sweeping && !drag_tiles()) {
+            slide_at(cell, at);
+        }
+    };
+    let on_pointerup = move |event: PointerEvent| {
+        let Some(released) = drag.get_untracked() else {
+            return;
+        };
+        drag.set(None);
+        let at = clock.event_time(&event);
+        match released.release(at) {
+            Release::Tap => slide_at(released.grabbed, released.started_at),
+            Release::Slide { from } => slide_at(from, at),
+            Release::Cancel => {}
+        }
+    };
+
+    startup::mark_board_painted();
+
+    let large = memo!(|shape| shape.0 * shape.1 > LARGE_BOARD);
+    let dom_board = move || {
+        view! {
+            <div
+                _ref=container
+                class=move || classes!(
+                    "relative mx-auto my-4 rounded-xl
+                    w-[calc(var(--w)*var(--pitch)_-_var(--gap))] h-[calc(var(--h)*var(--pitch)_-_var(--gap))]",
+                    focused.is_some_and(|focused| focused()) => "ring-2 ring-offset-8 ring-violet-400/60 ring-offset-transparent",
+                    mirrored() => "-scale-x-100",
+                )
+                style=("--tile", format!("{TILE_REM}rem"))
+                style=("--gap", format!("{GAP_REM}rem"))
+                style=("--w", move || shape().0)
+                style=("--h", move || shape().1)
+                style=("--pitch", "calc(var(--tile) + var(--gap))")
+            >
+                <For
+                    each=move || shape.with(|&(w, h)| (0..w * h))
+                    key=Clone::clone
+                    children=render_piece(shape)
+                />
+                <OverlayLayer overlays=model.overlays mirrored />
+                <GridOverlay shape />
+                <div
+                    class="absolute inset-0 touch-none"
+                    on:pointerdown=on_pointerdown
+                    on:pointermove=on_pointermove
+                    on:pointerup=on_pointerup
+                    on:pointercancel=move |_| drag.set(None)
+                    on:pointerleave=move |_| hovered.set(None)
+                />
+            </div>
+        }
+    };
+
+    view! {
+        <Show when=large fallback=dom_board>
+            <CanvasBoard model on_slide=on_slide.get_value() />
+        </Show>
+    }
+}
+
+use core::time::Duration;
+
+use leptos::{ev::PointerEvent, html::Div, *};
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+
+use macros::{classes, memo, return_with_try};
+
+use crate::animation::Spring;
+use crate::canvas_board::{CanvasBoard, LARGE_BOARD};
+use crate::clock::{use_clock, Instant};
+use crate::distance_heat::show_distance_heat;
+use crate::drag::{Drag, Release};
+use crate::grid_overlay::GridOverlay;
+use crate::hover_hint::show_hover_hint;
+use crate::overlay::{OverlayLayer, Overlays};
+use crate::puzzle::AnyPuzzle;
+use crate::settings::{use_settings, CompletionEffect, Motion, SolvedRegionStyle};
+use crate::signal_ext::SignalUpdateConditional;
+use crate::startup;
+use crate::view_model::BoardViewModel;
@@ -0,0 +1,249 @@
+//! Everything a profile has stored, as one JSON file for backups and moving
+//! between devices.
+//!
+//! Bundles carry a `version`. Older ones are upgraded step by step on import,
+//! so a bundle exported today can be read by any later release.
+
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Upgrades of a bundle, as run by [`migrations::upgrade`]; empty while there
+/// has only been one version.
+const MIGRATIONS: &[fn(&mut Value)] = &[];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    /// Milliseconds since the Unix epoch.
+    pub exported_at: f64,
+    pub profile: String,
+    pub settings: Settings,
+    #[serde(deserialize_with = "deserialize_each")]
+    pub solves: Vec<Solve>,
+    #[serde(default)]
+    pub goals: Goals,
+    #[serde(default)]
+    pub key_usage: BTreeMap<String, u64>,
+}
+
+#[derive(Debug)]
+pub enum BundleError {
+    Json(serde_json::Error),
+    MissingVersion,
+    /// Written by a newer release than this one.
+    TooNew(u64),
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::Json(err) => write!(f, "not a valid backup: {err}"),
+            BundleError::MissingVersion => f.write_str("not a backup file"),
+            BundleError::TooNew(version) => {
+                write!(
+                    f,
+                    "backup version {version} is newer than this app supports"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+impl From<serde_json::Error> for BundleError {
+    fn from(err: serde_json::Error) -> Self {
+        BundleError::Json(err)
+    }
+}
+
+pub fn parse(text: &str) -> Result<Bundle, BundleError> {
+    let mut value: Value = serde_json::from_str(text)?;
+    let version = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or(BundleError::MissingVersion)?;
+    match version {
+        0 => return Err(BundleError::MissingVersion),
+        version if version > u64::from(BUNDLE_VERSION) => return Err(BundleError::TooNew(version)),
+        _ => {}
+    }
+
+    migrations::upgrade(MIGRATIONS, version as u32, |migrate| migrate(&mut value));
+    value["version"] = BUNDLE_VERSION.into();
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Export and restore buttons for the whole active profile.
+#[component]
+pub fn BundleControls() -> impl IntoView {
+    let settings = use_settings();
+    let solves = use_solves();
+    let goals = use_goals();
+    let KeyUsage(key_usage) = use_key_usage();
+    let toasts = use_toasts();
+
+    let export = move |_| {
+        let bundle = Bundle {
+            version: BUNDLE_VERSION,
+            exported_at: js_sys::Date::now(),
+            profile: active_profile(),
+            settings: settings.get_untracked(),
+            solves: solves.get_untracked(),
+            goals: goals.get_untracked(),
+            key_usage: key_usage.get_untracked(),
+        };
+        match serde_json::to_string_pretty(&bundle) {
+            Ok(json) => download_text("jugo-backup.json", &json, "application/json"),
+            Err(err) => toasts.show(format!("Export failed: {err}")),
+        }
+    };
+
+    let file_input = create_node_ref::<html::Input>();
+    let restore = move |_| {
+        spawn_local(async move {
+            let Some(text) = read_selected_file(file_input, toasts).await else {
+                return;
+            };
+            match parse(&text) {
+                Ok(bundle) => {
+                    let previous = (
+                        settings.get_untracked(),
+                        solves.get_untracked(),
+                        goals.get_untracked(),
+                        key_usage.get_untracked(),
+                    );
+                    settings.set(bundle.settings);
+                    solves.set(bundle.solves);
+                    goals.set(bundle.goals);
+                    key_usage.set(bundle.key_usage);
+                    toasts.show_undo(
+                        format!("Restored the backup of \"{}\"", bundle.profile),
+                        move || {
+                            let (
+                                previous_settings,
+                                previous_solves,
+                                previous_goals,
+                                previous_usage,
+                            ) = previous.clone();
+                            settings.set(previous_settings);
+                            solves.set(previous_solves);
+                            goals.set(previous_goals);
+                            key_usage.set(previous_usage);
+                        },
+                    );
+                }
+                Err(err) => toasts.show(format!("Restore failed: {err}")),
+            }
+        });
+    };
+
+    view! {
+        <div class="flex gap-2">
+            <input _ref=file_input type="file" accept=".json,application/json" class="hidden" on:change=restore />
+            <button class="px-2 rounded-md opacity-60 hover:opacity-100" on:click=export>
+                "Export this profile"
+            </button>
+            <button
+                class="px-2 rounded-md opacity-60 hover:opacity-100"
+                on:click=move |_| {
+                    if let Some(input) = file_input.get_untracked() {
+                        input.click();
+                    }
+                }
+            >
+                "Restore backup"
+            </button>
+        </div>
+    }
+}
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::goals::{use_goals, Goals};
+use crate::location::{download_text, read_selected_file};
+use crate::migrations;
+use crate::profile::active_profile;
+use crate::settings::{use_settings, Settings};
+use crate::stats::{deserialize_each, use_key_usage, use_solves, KeyUsage, Solve};
+use crate::toast::use_toasts;
+
+#[cfg(test)]
+mod tests {
+    fn solve() -> Solve {
+        Solve {
+            id: 1,
+            scramble: Scramble::new((4, 4), [0; 32]),
+            time: Duration::from_secs(10),
+            moves: 50,
+            finished_at: 1_697_450_000_000.0,
+            hand_timed: false,
+            penalty: Penalty::None,
+            replay: Vec::new(),
+            imported_scramble: None,
+            daily: None,
+            session_index: None,
+            reaction: None,
+            flags: Vec::new(),
+        }
+    }
+
+    fn bundle(version: Value, solves: Vec<Value>) -> String {
+        json!({
+            "version": version,
+            "exported_at": 0.0,
+            "profile": "default",
+            "settings": {},
+            "solves": solves,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn versions() {
+        let solves = || vec![serde_json::to_value(solve()).unwrap()];
+        assert!(matches!(
+            parse(&bundle(Value::Null, solves())),
+            Err(BundleError::MissingVersion)
+        ));
+        assert!(matches!(
+            parse(&bundle(0.into(), solves())),
+            Err(BundleError::MissingVersion)
+        ));
+        let newer = u64::from(BUNDLE_VERSION) + 1;
+        assert!(matches!(
+            parse(&bundle(newer.into(), solves())),
+            Err(BundleError::TooNew(version)) if version == newer
+        ));
+        assert!(matches!(parse("[]"), Err(BundleError::MissingVersion)));
+        assert!(matches!(parse("{"), Err(BundleError::Json(_))));
+
+        let bundle = parse(&bundle(1.into(), solves())).unwrap();
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        assert_eq!(bundle.solves, [solve()]);
+    }
+
+    #[test]
+    fn unreadable_solves_are_left_out() {
+        let solves = vec![
+            serde_json::to_value(solve()).unwrap(),
+            json!({ "time": "soon" }),
+        ];
+        let bundle = parse(&bundle(1.into(), solves)).unwrap();
+        assert_eq!(bundle.solves, [solve()]);
+    }
+
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::seed::Scramble;
+    use crate::stats::Penalty;
+
+    use super::*;
+}
@@ -0,0 +1,129 @@
+//! Boards too big for a DOM node per piece, drawn onto one canvas instead.
+//! There's no skin or transition here: the whole board is redrawn whenever
+//! the view model changes, which at a few thousand cells is still well within
+//! a frame.
+
+/// Boards with more cells than this are drawn on a canvas.
+pub const LARGE_BOARD: usize = 400;
+/// The largest the board is drawn, in CSS pixels; cells shrink to fit.
+const MAX_SIZE: f64 = 800.0;
+const GAP_RATIO: f64 = 0.1;
+
+#[component]
+pub fn CanvasBoard(
+    model: BoardViewModel,
+    #[prop(into)] on_slide: Callback<((usize, usize), Instant)>,
+) -> impl IntoView {
+    let clock = use_clock();
+    let settings = use_settings();
+    let mirrored = memo!(|settings| settings.mirror_board);
+    let canvas = create_node_ref::<Canvas>();
+
+    let shape = model.shape;
+    let pitch = memo!(|shape| MAX_SIZE / shape.0.max(shape.1) as f64);
+    let size = move || {
+        let ((width, height), pitch) = (shape(), pitch());
+        (width as f64 * pitch, height as f64 * pitch)
+    };
+
+    create_effect(move |_| {
+        return_with_try! {
+            let canvas = canvas()?;
+            let scale = window().device_pixel_ratio();
+            let (css_width, css_height) = size();
+            canvas.set_width((css_width * scale) as u32);
+            canvas.set_height((css_height * scale) as u32);
+
+            let context: CanvasRenderingContext2d = canvas.get_context("2d")??.dyn_into()?;
+            context.scale(scale, scale)?;
+            draw(&context, model, pitch(), mirrored());
+        }
+    });
+
+    // The cell under the pointer, which is drawn flipped if mirrored.
+    let cell_at = move |event: &MouseEvent| -> Option<(usize, usize)> {
+        let rect = canvas.get_untracked()?.get_bounding_client_rect();
+        let (width, height) = shape.get_untracked();
+        let pitch = rect.width() / width as f64;
+        let x = (event.client_x() as f64 - rect.left()) / pitch;
+        let y = (event.client_y() as f64 - rect.top()) / pitch;
+        let x = match mirrored.get_untracked() {
+            true => width as f64 - 1.0 - x.floor(),
+            false => x.floor(),
+        };
+        (x >= 0.0 && y >= 0.0 && (x as usize) < width && (y as usize) < height)
+            .then(|| (x as usize, y as usize))
+    };
+    let slide = move |event: &MouseEvent| {
+        let Some(cell) = cell_at(event) else {
+            return;
+        };
+        event.prevent_default();
+        on_slide.call((cell, clock.event_time(event)));
+    };
+
+    view! {
+        <canvas
+            _ref=canvas
+            class="mx-auto my-4 touch-none"
+            style=("width", move || format!("{}px", size().0))
+            style=("height", move || format!("{}px", size().1))
+            on:mousedown=move |event| slide(&event)
+            on:mousemove=move |event| if event.buttons() & 1 == 1 { slide(&event) }
+            on:pointerdown=move |event| if event.pointer_type() != "mouse" { slide(&event) }
+        />
+    }
+}
+
+fn draw(context: &CanvasRenderingContext2d, model: BoardViewModel, pitch: f64, mirrored: bool) {
+    let gap = pitch * GAP_RATIO;
+    let tile = pitch - gap;
+    let (width, height) = model.shape.get();
+    context.clear_rect(0.0, 0.0, width as f64 * pitch, height as f64 * pitch);
+
+    // Labels only while they're big enough to read.
+    let labelled = tile >= 16.0;
+    context.set_font(&format!(
+        "{}px ui-monospace, monospace",
+        (tile * 0.4).round()
+    ));
+    context.set_text_align("center");
+    context.set_text_baseline("middle");
+
+    for piece in 1..width * height {
+        let cell = model.cell_of(piece);
+        let in_place = model.home_of(piece) == cell;
+        let (x, y) = model.position(piece);
+        let x = if mirrored { (width - 1) as f64 - x } else { x };
+        let (left, top) = (x * pitch + gap / 2.0, y * pitch + gap / 2.0);
+
+        context.set_global_alpha(match model.region_style(cell) {
+            SolvedRegionStyle::Dim => 0.4 * model.opacity(piece),
+            _ => model.opacity(piece),
+        });
+
+        context.set_fill_style(&JsValue::from_str(match in_place {
+            true => "#f5f5f5",
+            false => "#171717",
+        }));
+        context.fill_rect(left, top, tile, tile);
+
+        if labelled {
+            context.set_fill_style(&JsValue::from_str(match in_place {
+                true => "#262626",
+                false => "#e5e5e5",
+            }));
+            _ = context.fill_text(&piece.to_string(), left + tile / 2.0, top + tile / 2.0);
+        }
+    }
+}
+
+use leptos::{ev::MouseEvent, html::Canvas, *};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::CanvasRenderingContext2d;
+
+use macros::{memo, return_with_try};
+
+use crate::clock::{use_clock, Instant};
+use crate::settings::{use_settings, SolvedRegionStyle};
+use crate::view_model::BoardViewModel;
@@ -0,0 +1,219 @@
+/// A point in time, measured from an arbitrary clock-specific origin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instant(Duration);
+
+impl Instant {
+    #[inline]
+    pub fn from_millis_f64(millis: f64) -> Self {
+        Self(Duration::from_secs_f64(millis.max(0.0) / 1000.0))
+    }
+    #[inline]
+    pub fn since_origin(self) -> Duration {
+        self.0
+    }
+    #[inline]
+    pub fn saturating_duration_since(self, earlier: Instant) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+    fn add(self, rhs: Duration) -> Self::Output {
+        Instant(self.0 + rhs)
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Instant(self.0.saturating_sub(rhs))
+    }
+}
+
+impl Sub for Instant {
+    type Output = Duration;
+    fn sub(self, rhs: Instant) -> Self::Output {
+        self.saturating_duration_since(rhs)
+    }
+}
+
+pub trait TimeSource {
+    fn now(&self) -> Instant;
+
+    /// When `event` happened, as close to the physical input as the source allows.
+    fn event_time(&self, _event: &Event) -> Instant {
+        self.now()
+    }
+}
+
+/// `performance.now()` in the browser.
+#[cfg(target_arch = "wasm32")]
+pub struct PerformanceTimeSource;
+
+#[cfg(target_arch = "wasm32")]
+impl TimeSource for PerformanceTimeSource {
+    fn now(&self) -> Instant {
+        let millis = window()
+            .performance()
+            .map_or_else(js_sys::Date::now, |performance| performance.now());
+        Instant::from_millis_f64(millis)
+    }
+
+    /// `Event.timeStamp` shares its origin with `performance.now()` and is taken
+    /// when the input is dispatched, so it doesn't include the time spent in
+    /// signal updates and rendering before the handler reads the clock. Browsers
+    /// coarsen both to somewhere between 5µs and 1ms, so where they're coarsest
+    /// a solve time can be off by up to 1ms either way, which shows in the last
+    /// digit when the timer is set to milliseconds.
+    fn event_time(&self, event: &Event) -> Instant {
+        stamped(event.time_stamp(), self.now())
+    }
+}
+
+/// When an event stamped `time_stamp` milliseconds after the clock's origin
+/// happened, read at `now`. Events without a stamp are taken to happen now,
+/// and none can have happened later than that.
+#[cfg(any(target_arch = "wasm32", test))]
+fn stamped(time_stamp: f64, now: Instant) -> Instant {
+    match time_stamp {
+        millis if millis > 0.0 => Instant::from_millis_f64(millis).min(now),
+        _ => now,
+    }
+}
+
+/// `std::time::Instant` on native targets, e.g. while rendering on a server.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct StdTimeSource(std::time::Instant);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TimeSource for StdTimeSource {
+    fn now(&self) -> Instant {
+        Instant(self.0.elapsed())
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time.
+#[derive(Clone, Default)]
+pub struct ManualTimeSource(Rc<Cell<Duration>>);
+
+impl ManualTimeSource {
+    pub fn advance(&self, by: Duration) {
+        self.0.set(self.0.get() + by);
+    }
+}
+
+impl TimeSource for ManualTimeSource {
+    fn now(&self) -> Instant {
+        Instant(self.0.get())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Clock(StoredValue<Rc<dyn TimeSource>>);
+
+impl Clock {
+    pub fn new(source: impl TimeSource + 'static) -> Self {
+        Self(store_value(Rc::new(source) as Rc<dyn TimeSource>))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn system() -> Self {
+        Self::new(PerformanceTimeSource)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn system() -> Self {
+        Self::new(StdTimeSource(std::time::Instant::now()))
+    }
+
+    #[inline]
+    pub fn now(&self) -> Instant {
+        self.0.with_value(|source| source.now())
+    }
+    #[inline]
+    pub fn event_time(&self, event: &Event) -> Instant {
+        self.0.with_value(|source| source.event_time(event))
+    }
+    #[inline]
+    pub fn elapsed(&self, since: Instant) -> Duration {
+        self.now() - since
+    }
+}
+
+pub fn provide_clock(clock: Clock) {
+    provide_context(clock);
+}
+
+/// The clock provided via context, or the system clock if there is none.
+pub fn use_clock() -> Clock {
+    use_context::<Clock>().unwrap_or_else(Clock::system)
+}
+
+use core::cell::Cell;
+use core::ops::{Add, Sub};
+use core::time::Duration;
+use std::rc::Rc;
+
+use leptos::*;
+use web_sys::Event;
+
+#[cfg(test)]
+mod tests {
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn manual_time_moves_only_when_advanced() {
+        let source = ManualTimeSource::default();
+        let start = source.now();
+        assert_eq!(source.now(), start);
+
+        source.advance(millis(250));
+        let later = source.now();
+        assert!(later > start);
+        assert_eq!(later - start, millis(250));
+    }
+
+    #[test]
+    fn manual_time_is_shared_between_clones() {
+        let source = ManualTimeSource::default();
+        let clone = source.clone();
+        clone.advance(millis(40));
+        assert_eq!(source.now(), clone.now());
+        assert_eq!(source.now().since_origin(), millis(40));
+    }
+
+    #[test]
+    fn events_never_happen_after_now() {
+        let source = ManualTimeSource::default();
+        source.advance(millis(1_000));
+        let now = source.now();
+
+        assert_eq!(stamped(900.0, now), now - millis(100));
+        assert_eq!(stamped(1_000.0, now), now);
+        assert_eq!(stamped(1_000.4, now), now);
+        // unstamped
+        assert_eq!(stamped(0.0, now), now);
+        assert_eq!(stamped(-5.0, now), now);
+    }
+
+    #[test]
+    fn instant_arithmetic() {
+        let start = Instant::from_millis_f64(1_500.0);
+        assert_eq!(start.since_origin(), millis(1_500));
+        assert_eq!(start + millis(500) - start, millis(500));
+        assert_eq!(start - millis(500), Instant::from_millis_f64(1_000.0));
+
+        // never before the origin, and durations never negative
+        assert_eq!(start - millis(2_000), Instant::default());
+        assert_eq!(Instant::from_millis_f64(-1.0), Instant::default());
+        assert_eq!(start - (start + millis(1)), Duration::ZERO);
+        assert_eq!(
+            (start + millis(1)).saturating_duration_since(start),
+            millis(1)
+        );
+    }
+
+    use super::*;
+}
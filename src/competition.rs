@@ -0,0 +1,435 @@
+//! Competition mode: a fixed set of scrambles, pasted or fetched from a URL,
+//! attempted once each and in order with WCA-style inspection, ending in an
+//! average. Enough to run an informal online competition from a shared list.
+//!
+//! For ranked play a server can hand out the scrambles instead, each only once
+//! its attempt starts, so nobody can look at one early. The protocol is two
+//! plain-text requests: `GET <url>` answers the number of attempts, and
+//! `POST <url>/attempts/<n>` (from 0) the scramble code for attempt `n`, which
+//! the server takes as the moment it started.
+
+const INSPECTION: Duration = Duration::from_secs(15);
+/// Past this, the attempt is a DNF; between the two it's +2.
+const INSPECTION_LIMIT: Duration = Duration::from_secs(17);
+const TICK: Duration = Duration::from_millis(100);
+
+pub fn inspection_penalty(inspected: Duration) -> Penalty {
+    match inspected {
+        inspected if inspected > INSPECTION_LIMIT => Penalty::Dnf,
+        inspected if inspected > INSPECTION => Penalty::PlusTwo,
+        _ => Penalty::None,
+    }
+}
+
+/// A mean for fewer than five attempts, as for a mo3, otherwise the trimmed
+/// average of them all.
+fn final_result(results: &[Solve]) -> Option<Option<Duration>> {
+    match results.len() {
+        0 => None,
+        n @ 5.. => average_of(results, n),
+        n => Some(
+            results
+                .iter()
+                .map(Solve::result)
+                .sum::<Option<Duration>>()
+                .map(|sum| sum / n as u32),
+        ),
+    }
+}
+
+/// Every non-empty line as a scramble code.
+fn parse_list(text: &str) -> Result<Vec<Scramble>, String> {
+    text.lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| Scramble::parse(line).map_err(|err| format!("line {}: {err}", i + 1)))
+        .collect()
+}
+
+async fn fetch_text(url: &str, method: &str) -> Result<String, JsValue> {
+    let mut init = RequestInit::new();
+    init.method(method).cache(RequestCache::NoStore);
+    let response: Response = JsFuture::from(window().fetch_with_str_and_init(url, &init))
+        .await?
+        .dyn_into()?;
+    if !response.ok() {
+        return Err(format!("{url} answered {}", response.status()).into());
+    }
+    Ok(JsFuture::from(response.text()?)
+        .await?
+        .as_string()
+        .unwrap_or_default())
+}
+
+#[derive(Clone, Copy)]
+pub struct Competition {
+    /// All of them for a list; from a server, those handed out so far.
+    scrambles: RwSignal<Vec<Scramble>>,
+    total: RwSignal<usize>,
+    /// Where ranked scrambles come from.
+    server: StoredValue<Option<String>>,
+    error: RwSignal<Option<String>>,
+    results: RwSignal<Vec<Solve>>,
+    /// Whether the next scramble has been handed out and not yet finished.
+    attempting: RwSignal<bool>,
+    /// From the inspection of the attempt in progress.
+    penalty: StoredValue<Penalty>,
+}
+
+impl Competition {
+    pub fn new() -> Self {
+        Self {
+            scrambles: create_rw_signal(Vec::new()),
+            total: create_rw_signal(0),
+            server: store_value(None),
+            error: create_rw_signal(None),
+            results: create_rw_signal(Vec::new()),
+            attempting: create_rw_signal(false),
+            penalty: store_value(Penalty::None),
+        }
+    }
+
+    /// Whether there are attempts left.
+    pub fn is_active(&self) -> bool {
+        let total = self.total.get_untracked();
+        total > 0 && self.results.with_untracked(Vec::len) < total
+    }
+
+    pub fn is_attempting(&self) -> bool {
+        self.attempting.get_untracked()
+    }
+
+    /// Begins the next attempt, if there is one, calling `on_attempt` with its
+    /// scramble as soon as it's known.
+    pub fn next_attempt(&self, on_attempt: impl FnOnce(Scramble) + 'static) {
+        if self.is_attempting() || !self.is_active() {
+            return;
+        }
+        let index = self.results.with_untracked(Vec::len);
+        self.penalty.set_value(Penalty::None);
+        self.attempting.set(true);
+
+        let Some(server) = self.server.get_value() else {
+            if let Some(scramble) = self
+                .scrambles
+                .with_untracked(|scrambles| scrambles.get(index).copied())
+            {
+                on_attempt(scramble);
+            }
+            return;
+        };
+        let this = *self;
+        spawn_local(async move {
+            let issued = fetch_text(&format!("{server}/attempts/{index}"), "POST")
+                .await
+                .map_err(|err| format!("{err:?}"))
+                .and_then(|code| Scramble::parse(code.trim()).map_err(|err| err.to_string()));
+            match issued {
+                Ok(scramble) => {
+                    this.scrambles.update(|scrambles| scrambles.push(scramble));
+                    on_attempt(scramble);
+                }
+                Err(err) => {
+                    this.error
+                        .set(Some(format!("Couldn't get the scramble: {err}")));
+                    this.attempting.set(false);
+                }
+            }
+        });
+    }
+
+    fn current(&self) -> Option<Scramble> {
+        let index = self.results.with_untracked(Vec::len);
+        self.scrambles
+            .with_untracked(|scrambles| scrambles.get(index).copied())
+            .filter(|_| self.is_attempting())
+    }
+
+    /// Notes how long the attempt in progress was inspected for.
+    pub fn inspected(&self, took: Duration) {
+        if self.is_attempting() {
+            self.penalty.set_value(inspection_penalty(took));
+        }
+    }
+
+    /// The penalty the attempt in progress has earned so far.
+    pub fn penalty(&self) -> Penalty {
+        match self.is_attempting() {
+            true => self.penalty.get_value(),
+            false => Penalty::None,
+        }
+    }
+
+    /// Takes `solve` as the result of the attempt in progress, if it was of
+    /// the attempt's scramble.
+    pub fn record(&self, solve: Solve) {
+        if self.current() != Some(solve.scramble) {
+            return;
+        }
+        self.results.update(|results| results.push(solve));
+        self.attempting.set(false);
+    }
+
+    /// Ends the attempt in progress as a DNF.
+    pub fn record_dnf(&self) {
+        let Some(scramble) = self.current() else {
+            return;
+        };
+        self.record(Solve {
+            id: Solve::new_id(),
+            scramble,
+            time: Duration::ZERO,
+            moves: 0,
+            finished_at: js_sys::Date::now(),
+            hand_timed: false,
+            penalty: Penalty::Dnf,
+            replay: Vec::new(),
+            imported_scramble: None,
+            daily: None,
+            session_index: None,
+            reaction: None,
+            flags: Vec::new(),
+        });
+    }
+
+    fn start(&self, scrambles: Vec<Scramble>) {
+        self.results.set(Vec::new());
+        self.attempting.set(false);
+        self.error.set(None);
+        self.server.set_value(None);
+        self.total.set(scrambles.len());
+        self.scrambles.set(scrambles);
+    }
+
+    fn start_ranked(&self, server: String, total: usize) {
+        self.start(Vec::new());
+        self.server.set_value(Some(server));
+        self.total.set(total);
+    }
+
+    fn end(&self) {
+        self.start(Vec::new());
+    }
+}
+
+impl Default for Competition {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sets up a competition and walks through it; `on_attempt` is called with
+/// each scramble in turn, and should load it and start inspection.
+#[component]
+pub fn CompetitionPanel(
+    competition: Competition,
+    game_state: RwSignal<GameState>,
+    #[prop(into)] on_attempt: Callback<Scramble>,
+) -> impl IntoView {
+    let clock = use_clock();
+    let open = create_rw_signal(false);
+    let input = create_rw_signal(String::new());
+    let ranked = create_rw_signal(false);
+    let now = create_rw_signal(clock.now());
+
+    let Competition {
+        total,
+        error,
+        results,
+        attempting,
+        ..
+    } = competition;
+    let done = memo!(|results| results.len());
+
+    let on_attempt = store_value(on_attempt);
+    let next = move || {
+        competition.next_attempt(move |scramble| {
+            on_attempt.with_value(|on_attempt| on_attempt.call(scramble))
+        })
+    };
+
+    let start = move |text: String| match parse_list(&text) {
+        Ok(list) if list.is_empty() => error.set(Some("No scrambles".to_owned())),
+        Ok(list) => {
+            error.set(None);
+            competition.start(list);
+            next();
+        }
+        Err(err) => error.set(Some(err)),
+    };
+    let load = move || {
+        let text = input.get_untracked();
+        let trimmed = text.trim();
+        let is_url = trimmed.starts_with("https://") || trimmed.starts_with("http://");
+        if ranked.get_untracked() && !is_url {
+            error.set(Some("Ranked play needs the server's URL".to_owned()));
+            return;
+        }
+        if !is_url {
+            start(text);
+            return;
+        }
+        let url = trimmed.trim_end_matches('/').to_owned();
+        spawn_local(async move {
+            match fetch_text(&url, "GET").await {
+                Ok(text) if ranked.get_untracked() => match text.trim().parse() {
+                    Ok(0) | Err(_) => {
+                        error.set(Some("The server sent no attempt count".to_owned()))
+                    }
+                    Ok(count) => {
+                        competition.start_ranked(url, count);
+                        next();
+                    }
+                },
+                Ok(list) => start(list),
+                Err(err) => error.set(Some(format!("Couldn't fetch the list: {err:?}"))),
+            }
+        });
+    };
+
+    // Inspection runs out into a DNF, as at a real competition.
+    let inspecting = memo!(|game_state| match game_state {
+        GameState::Inspecting { since } => Some(*since),
+        _ => None,
+    });
+    let interval = store_value(None::<IntervalHandle>);
+    create_effect(move |_| {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+        let Some(since) = inspecting() else {
+            interval.set_value(None);
+            return;
+        };
+        let tick = move || {
+            let time = clock.now();
+            now.set(time);
+            if competition.is_attempting() && time - since > INSPECTION_LIMIT {
+                game_state.update_if(|state| state.apply(GameEvent::GiveUp { at: time }));
+                competition.record_dnf();
+            }
+        };
+        interval.set_value(set_interval_with_handle(tick, TICK).ok());
+    });
+    on_cleanup(move || {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+    });
+
+    let status = move || {
+        let inspection = inspecting()
+            .filter(|_| attempting())
+            .map(|since| {
+                let left = INSPECTION.as_secs_f64() - (now() - since).as_secs_f64();
+                match left {
+                    left if left > 0.0 => format!(" — inspection {}", left.ceil()),
+                    _ => " — inspection over, +2".to_owned(),
+                }
+            })
+            .unwrap_or_default();
+        match (done(), total()) {
+            (done, total) if done < total => format!("Attempt {} of {total}{inspection}", done + 1),
+            _ => "Finished".to_owned(),
+        }
+    };
+
+    let result_rows = move || {
+        results.with(|results| {
+            results
+                .iter()
+                .enumerate()
+                .map(|(i, solve)| {
+                    view! { <li>{format!("{}. {}", i + 1, format_result(solve))} {flag_mark(solve)}</li> }
+                })
+                .collect_view()
+        })
+    };
+    let summary = move || {
+        let result = results.with(|results| final_result(results))?;
+        let label = match total() {
+            5.. => "Average",
+            _ => "Mean",
+        };
+        let result = result.map_or("DNF".to_string(), format_time);
+        Some(format!("{label}: {result}"))
+    };
+
+    view! {
+        <button
+            class="fixed top-3 right-[12rem] w-8 h-8 rounded-md text-xl opacity-60 hover:opacity-100"
+            title="Competition"
+            on:click=move |_| open.update(|open| *open = !*open)
+        >
+            "🏆"
+        </button>
+        <Show when=open fallback=|| ()>
+            <div class="fixed top-12 right-3 w-72 p-4 rounded-lg shadow-lg bg-neutral-100 dark:bg-neutral-800
+                        flex flex-col gap-2 text-sm">
+                <Show
+                    when=move || total() > 0
+                    fallback=move || view! {
+                        <textarea
+                            class="h-32 p-2 rounded-md bg-transparent ring-1 ring-neutral-400 dark:ring-neutral-600 outline-none font-mono"
+                            placeholder="One scramble code per line, or a URL to a list"
+                            prop:value=input
+                            on:input=move |event| input.set(event_target_value(&event))
+                        />
+                        <label title="The server hands out each scramble only when its attempt starts">
+                            "Ranked "
+                            <input
+                                type="checkbox"
+                                prop:checked=ranked
+                                on:change=move |event| ranked.set(event_target_checked(&event))
+                            />
+                        </label>
+                        {move || error().map(|error| view! { <p class="text-red-500">{error}</p> })}
+                        <button
+                            class="self-end px-3 py-1 rounded-md bg-violet-500 text-white"
+                            on:click=move |_| load()
+                        >
+                            "Start"
+                        </button>
+                    }
+                >
+                    <p>{status}</p>
+                    {move || error().map(|error| view! { <p class="text-red-500">{error}</p> })}
+                    <ol class="font-mono">{result_rows}</ol>
+                    <p class="font-bold">{summary}</p>
+                    <div class="flex justify-end gap-2">
+                        <button class="px-3 py-1 rounded-md ring-1 ring-violet-500" on:click=move |_| competition.end()>
+                            {move || match done() < total() {
+                                true => "Abandon",
+                                false => "Close",
+                            }}
+                        </button>
+                        <button
+                            class="px-3 py-1 rounded-md bg-violet-500 text-white disabled:opacity-40"
+                            disabled=move || attempting() || done() >= total()
+                            on:click=move |_| next()
+                        >
+                            "Next attempt"
+                        </button>
+                    </div>
+                </Show>
+            </div>
+        </Show>
+    }
+}
+
+use core::time::Duration;
+
+use leptos::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{RequestCache, RequestInit, Response};
+
+use macros::memo;
+
+use crate::clock::use_clock;
+use crate::game_state::{GameEvent, GameState};
+use crate::integrity::flag_mark;
+use crate::seed::Scramble;
+use crate::signal_ext::SignalUpdateConditional;
+use crate::stats::{average_of, format_result, format_time, Penalty, Solve};
@@ -0,0 +1,57 @@
+/// The state of one board during an attempt. Several can share one timer.
+#[derive(Clone, Copy)]
+pub struct BoardController {
+    pub puzzle: RwSignal<AnyPuzzle>,
+    pub moves: RwSignal<usize>,
+    pub replay: RwSignal<Vec<TimedMove>>,
+}
+
+impl BoardController {
+    pub fn new(puzzle: AnyPuzzle) -> Self {
+        Self {
+            puzzle: create_rw_signal(puzzle),
+            moves: create_rw_signal(0),
+            replay: create_rw_signal(Vec::new()),
+        }
+    }
+
+    /// Slides from `idx`, returning how many pieces moved.
+    pub fn slide(&self, idx: (usize, usize)) -> usize {
+        match self
+            .puzzle
+            .update_if_some(move |puzzle| puzzle.slide_from(idx))
+        {
+            Some(moved @ 1..) => {
+                self.moves.update(|moves| *moves += 1);
+                moved
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn record(&self, timed: TimedMove) {
+        self.replay.update(|replay| replay.push(timed));
+    }
+
+    pub fn load(&self, puzzle: AnyPuzzle) {
+        self.puzzle.set(puzzle);
+        self.moves.set(0);
+        self.replay.update(|replay| replay.clear());
+    }
+
+    pub fn is_solved_untracked(&self) -> bool {
+        self.puzzle.with_untracked(AnyPuzzle::is_solved)
+    }
+
+    pub fn dispose(self) {
+        self.puzzle.dispose();
+        self.moves.dispose();
+        self.replay.dispose();
+    }
+}
+
+use leptos::*;
+
+use crate::puzzle::AnyPuzzle;
+use crate::signal_ext::SignalUpdateConditional;
+use crate::stats::TimedMove;
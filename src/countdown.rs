@@ -0,0 +1,100 @@
+//! An optional 3-2-1 between asking for a scramble and being able to play it,
+//! beeping on each count, so friends on a call can start at the same moment.
+
+const LENGTH: Duration = Duration::from_secs(3);
+const TICK: Duration = Duration::from_millis(16);
+
+#[derive(Clone, Copy)]
+pub struct Countdown {
+    clock: Clock,
+    go: RwSignal<Option<Instant>>,
+    remaining: RwSignal<Option<u64>>,
+}
+
+impl Countdown {
+    /// `on_go` is called with the exact moment the countdown ran out.
+    pub fn new(on_go: impl Fn(Instant) + 'static) -> Self {
+        let clock = use_clock();
+        let go = create_rw_signal(None::<Instant>);
+        let remaining = create_rw_signal(None::<u64>);
+        let on_go = Rc::new(on_go);
+
+        let interval = store_value(None::<IntervalHandle>);
+        let stop = move || {
+            if let Some(handle) = interval.get_value() {
+                handle.clear();
+            }
+            interval.set_value(None);
+        };
+        create_effect(move |_| {
+            stop();
+            let Some(at) = go() else {
+                remaining.set(None);
+                return;
+            };
+
+            let on_go = on_go.clone();
+            let tick = move || {
+                let now = clock.now();
+                if now >= at {
+                    go.set(None);
+                    audio::beep(880.0, Duration::from_millis(300));
+                    on_go(at);
+                    return;
+                }
+                let count = ((at - now).as_millis().div_ceil(1000) as u64).max(1);
+                if remaining.get_untracked() != Some(count) {
+                    audio::beep(440.0, Duration::from_millis(120));
+                    remaining.set(Some(count));
+                }
+            };
+            tick();
+            interval.set_value(set_interval_with_handle(tick, TICK).ok());
+        });
+        on_cleanup(stop);
+
+        Self {
+            clock,
+            go,
+            remaining,
+        }
+    }
+
+    pub fn start(&self) {
+        self.go.set(Some(self.clock.now() + LENGTH));
+    }
+
+    pub fn cancel(&self) {
+        self.go.set(None);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.go.with_untracked(Option::is_some)
+    }
+
+    /// Whole seconds left, rounded up; `None` unless counting down.
+    pub fn remaining(&self) -> Option<u64> {
+        self.remaining.get()
+    }
+}
+
+/// The count, large, over whatever it's placed in.
+#[component]
+pub fn CountdownBanner(countdown: Countdown) -> impl IntoView {
+    view! {
+        <Show when=move || countdown.remaining().is_some() fallback=|| ()>
+            <div class="absolute inset-0 z-10 flex items-center justify-center rounded-xl pointer-events-none
+                        font-mono text-8xl font-bold bg-neutral-100/60 dark:bg-neutral-900/60">
+                {move || countdown.remaining()}
+            </div>
+        </Show>
+    }
+}
+
+use core::time::Duration;
+use std::rc::Rc;
+
+use leptos::*;
+
+use crate::audio;
+use crate::clock::{use_clock, Clock, Instant};
@@ -0,0 +1,273 @@
+//! Crowd play: the board is played by majority vote over commands arriving on
+//! a WebSocket, e.g. from a bridge relaying a stream's chat. Each round the
+//! direction with the most votes is played, with one vote per user.
+//!
+//! Messages are either plain text or `{"user": "...", "text": "..."}`; the
+//! first word of the text is the command: `up`, `down`, `left` or `right`, or
+//! just their initials, optionally after a `!`.
+
+const DEFAULT_ROUND_SECS: u32 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Vote {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Vote {
+    const ALL: [Vote; 4] = [Vote::Up, Vote::Down, Vote::Left, Vote::Right];
+
+    fn parse(text: &str) -> Option<Self> {
+        let command = text.split_whitespace().next()?.trim_start_matches('!');
+        match command.to_lowercase().as_str() {
+            "up" | "u" => Some(Vote::Up),
+            "down" | "d" => Some(Vote::Down),
+            "left" | "l" => Some(Vote::Left),
+            "right" | "r" => Some(Vote::Right),
+            _ => None,
+        }
+    }
+
+    fn arrow(self) -> &'static str {
+        match self {
+            Vote::Up => "↑",
+            Vote::Down => "↓",
+            Vote::Left => "←",
+            Vote::Right => "→",
+        }
+    }
+
+    /// The cell whose tile would move this way into the blank.
+    fn cell(self, puzzle: &AnyPuzzle) -> Option<(usize, usize)> {
+        let (width, height) = puzzle.shape();
+        let (x, y) = puzzle.blank();
+        match self {
+            Vote::Up => (y + 1 < height).then(|| (x, y + 1)),
+            Vote::Down => y.checked_sub(1).map(|y| (x, y)),
+            Vote::Left => (x + 1 < width).then(|| (x + 1, y)),
+            Vote::Right => x.checked_sub(1).map(|x| (x, y)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Message {
+    user: Option<String>,
+    text: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Disconnected,
+    Connecting,
+    Connected,
+}
+
+#[component]
+pub fn CrowdPlay(
+    puzzle: RwSignal<AnyPuzzle>,
+    #[prop(into)] on_slide: Callback<((usize, usize), Instant)>,
+) -> impl IntoView {
+    let on_slide = store_value(on_slide);
+    let clock = use_clock();
+    let open = create_rw_signal(false);
+    let url = create_rw_signal(String::new());
+    let round_secs = create_rw_signal(DEFAULT_ROUND_SECS);
+    let status = create_rw_signal(Status::Disconnected);
+    // The latest vote of each user; anonymous ones are numbered.
+    let votes = create_rw_signal(HashMap::<String, Vote>::new());
+    let anonymous = store_value(0u64);
+
+    let socket = store_value(None::<WebSocket>);
+    let handlers = store_value(Vec::<Closure<dyn Fn(JsValue)>>::new());
+
+    let disconnect = move || {
+        if let Some(socket) = socket.get_value() {
+            // Detached first: the handlers are dropped below, and the socket
+            // would otherwise call them as it closes.
+            socket.set_onopen(None);
+            socket.set_onclose(None);
+            socket.set_onmessage(None);
+            _ = socket.close();
+        }
+        socket.set_value(None);
+        handlers.set_value(Vec::new());
+        votes.update(HashMap::clear);
+        status.set(Status::Disconnected);
+    };
+
+    let on_message = move |data: String| {
+        let (user, text) = match serde_json::from_str::<Message>(&data) {
+            Ok(Message { user, text }) => (user, text),
+            Err(_) => (None, data),
+        };
+        let Some(vote) = Vote::parse(&text) else {
+            return;
+        };
+        let user = user.unwrap_or_else(|| {
+            let n = anonymous.get_value();
+            anonymous.set_value(n + 1);
+            format!("anonymous {n}")
+        });
+        votes.update(|votes| _ = votes.insert(user, vote));
+    };
+
+    let connect = move || {
+        disconnect();
+        let socket_url = url.get_untracked();
+        let opened = match WebSocket::new(socket_url.trim()) {
+            Ok(opened) => opened,
+            Err(err) => {
+                log::error!("couldn't open {socket_url:?}: {err:?}");
+                return;
+            }
+        };
+        status.set(Status::Connecting);
+
+        let on_open = Closure::<dyn Fn(JsValue)>::new(move |_| status.set(Status::Connected));
+        let on_close = Closure::<dyn Fn(JsValue)>::new(move |_| status.set(Status::Disconnected));
+        let on_data = Closure::<dyn Fn(JsValue)>::new(move |event: JsValue| {
+            let data = event
+                .dyn_into::<MessageEvent>()
+                .ok()
+                .and_then(|event| event.data().as_string());
+            if let Some(data) = data {
+                on_message(data);
+            }
+        });
+        opened.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        opened.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+        opened.set_onmessage(Some(on_data.as_ref().unchecked_ref()));
+
+        socket.set_value(Some(opened));
+        handlers.set_value(vec![on_open, on_close, on_data]);
+    };
+    on_cleanup(disconnect);
+
+    let tally = move || {
+        votes.with(|votes| {
+            Vote::ALL.map(|vote| (vote, votes.values().filter(|&&cast| cast == vote).count()))
+        })
+    };
+
+    // Each round plays the most popular direction that can move, earlier
+    // directions in `Vote::ALL` winning ties, and starts the vote afresh.
+    let interval = store_value(None::<IntervalHandle>);
+    create_effect(move |_| {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+        if status() != Status::Connected {
+            interval.set_value(None);
+            return;
+        }
+
+        let play_round = move || {
+            let mut counts = untrack(tally);
+            counts.sort_by_key(|&(_, count)| Reverse(count));
+            votes.update(HashMap::clear);
+            let winner = counts
+                .into_iter()
+                .filter(|&(_, count)| count > 0)
+                .find_map(|(vote, _)| puzzle.with_untracked(|puzzle| vote.cell(puzzle)));
+            if let Some(cell) = winner {
+                on_slide.with_value(|on_slide| on_slide.call((cell, clock.now())));
+            }
+        };
+        let round = Duration::from_secs(round_secs().max(1).into());
+        interval.set_value(set_interval_with_handle(play_round, round).ok());
+    });
+    on_cleanup(move || {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+    });
+
+    view! {
+        <button
+            class="fixed top-3 right-[14.25rem] w-8 h-8 rounded-md text-xl opacity-60 hover:opacity-100"
+            title="Crowd play"
+            on:click=move |_| open.update(|open| *open = !*open)
+        >
+            "👥"
+        </button>
+        <Show when=open fallback=|| ()>
+            <div class="fixed top-12 right-3 w-72 p-4 rounded-lg shadow-lg bg-neutral-100 dark:bg-neutral-800
+                        flex flex-col gap-2 text-sm">
+                <input
+                    type="url"
+                    class="p-2 rounded-md bg-transparent ring-1 ring-neutral-400 dark:ring-neutral-600 outline-none"
+                    placeholder="wss://… command feed"
+                    prop:value=url
+                    on:input=move |event| url.set(event_target_value(&event))
+                />
+                <label>
+                    "Seconds per vote "
+                    <input
+                        type="number"
+                        min="1"
+                        max="60"
+                        class="w-16 bg-transparent"
+                        prop:value=move || round_secs().to_string()
+                        on:change=move |event| {
+                            if let Ok(secs) = event_target_value(&event).parse() {
+                                round_secs.set(secs);
+                            }
+                        }
+                    />
+                </label>
+                <div class="flex items-center justify-between">
+                    <span class="opacity-60">
+                        {move || match status() {
+                            Status::Disconnected => "Not connected",
+                            Status::Connecting => "Connecting…",
+                            Status::Connected => "Connected",
+                        }}
+                    </span>
+                    <button
+                        class="px-3 py-1 rounded-md bg-violet-500 text-white"
+                        on:click=move |_| match status.get_untracked() {
+                            Status::Disconnected => connect(),
+                            _ => disconnect(),
+                        }
+                    >
+                        {move || match status() {
+                            Status::Disconnected => "Connect",
+                            _ => "Disconnect",
+                        }}
+                    </button>
+                </div>
+            </div>
+        </Show>
+        <Show when=move || status() == Status::Connected fallback=|| ()>
+            <div class="fixed left-3 top-1/2 -translate-y-1/2 p-3 rounded-lg bg-neutral-100 dark:bg-neutral-800
+                        flex flex-col gap-1 font-mono text-sm">
+                {move || {
+                    tally()
+                        .into_iter()
+                        .map(|(vote, count)| view! {
+                            <div class="flex justify-between gap-4">
+                                <span>{vote.arrow()}</span>
+                                <span>{count}</span>
+                            </div>
+                        })
+                        .collect_view()
+                }}
+            </div>
+        </Show>
+    }
+}
+
+use core::cmp::Reverse;
+use core::time::Duration;
+use std::collections::HashMap;
+
+use leptos::*;
+use serde::Deserialize;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::{MessageEvent, WebSocket};
+
+use crate::clock::{use_clock, Instant};
+use crate::puzzle::AnyPuzzle;
@@ -0,0 +1,156 @@
+//! The daily challenge: one 4x4 scramble per date, the same for everyone,
+//! derived from the date itself. Past days stay playable from a calendar;
+//! solves made after the day are kept apart as late attempts.
+
+const SHAPE: (usize, usize) = (4, 4);
+const DAY_MILLIS: f64 = 86_400_000.0;
+
+/// Which day's challenge a solve was, and whether it came after that day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyAttempt {
+    pub day: i64,
+    pub late: bool,
+}
+
+impl DailyAttempt {
+    /// An attempt at `day`'s challenge finished now.
+    pub fn now(day: i64) -> Self {
+        Self {
+            day,
+            late: day != local_day(js_sys::Date::now()),
+        }
+    }
+}
+
+/// `2024-03-09` for a day number from [`local_day`].
+fn date(day: i64) -> String {
+    let iso = String::from(js_sys::Date::new(&(day as f64 * DAY_MILLIS).into()).to_iso_string());
+    iso[..10].to_string()
+}
+
+pub fn scramble(day: i64) -> Scramble {
+    Scramble::from_phrase(&format!("jugo daily {}", date(day)), SHAPE)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Completion {
+    OnTheDay,
+    Late,
+}
+
+/// A month at a time of past daily challenges, marked by whether (and when)
+/// they were solved. Picking a day calls `on_play` with it.
+#[component]
+pub fn DailyCalendar(#[prop(into)] on_play: Callback<i64>) -> impl IntoView {
+    let on_play = store_value(on_play);
+    let solves = use_solves();
+    let open = create_rw_signal(false);
+    // Months back from the current one.
+    let months_back = create_rw_signal(0u32);
+
+    let completed = create_memo(move |_| {
+        solves.with(|solves| {
+            let mut completed = HashMap::new();
+            for attempt in solves.iter().filter_map(|solve| solve.daily) {
+                let completion = match attempt.late {
+                    false => Completion::OnTheDay,
+                    true => Completion::Late,
+                };
+                let entry = completed.entry(attempt.day).or_insert(completion);
+                if completion == Completion::OnTheDay {
+                    *entry = completion;
+                }
+            }
+            completed
+        })
+    });
+
+    let month = move || {
+        let today = js_sys::Date::new_0();
+        let first = js_sys::Date::new_with_year_month_day(
+            today.get_full_year(),
+            today.get_month() as i32 - months_back() as i32,
+            1,
+        );
+        let length = js_sys::Date::new_with_year_month_day(
+            first.get_full_year(),
+            first.get_month() as i32 + 1,
+            0,
+        )
+        .get_date();
+        let label = String::from(first.to_locale_date_string(
+            "default",
+            &js_sys::JSON::parse(r#"{"year":"numeric","month":"long"}"#).unwrap_or_default(),
+        ));
+        (label, local_day(first.get_time()), first.get_day(), length)
+    };
+
+    let days = move || {
+        let today = local_day(js_sys::Date::now());
+        let (_, first, weekday, length) = month();
+        let padding = (0..weekday).map(|_| view! { <span /> }.into_view());
+        let days = (0..length as i64).map(move |offset| {
+            let day = first + offset;
+            let completion = completed.with(|completed| completed.get(&day).copied());
+            view! {
+                <button
+                    class=classes!(
+                        "w-8 h-8 rounded-md disabled:opacity-30",
+                        completion == Some(Completion::OnTheDay) => "bg-green-500/60",
+                        completion == Some(Completion::Late) => "bg-amber-500/60",
+                        day == today => "ring-1 ring-violet-500",
+                    )
+                    title=date(day)
+                    disabled=day > today
+                    on:click=move |_| {
+                        on_play.with_value(|on_play| on_play.call(day));
+                        open.set(false);
+                    }
+                >
+                    {offset + 1}
+                </button>
+            }
+            .into_view()
+        });
+        padding.chain(days).collect_view()
+    };
+
+    view! {
+        <button
+            class="fixed top-3 right-[9.75rem] w-8 h-8 rounded-md text-xl opacity-60 hover:opacity-100"
+            title="Daily challenge"
+            on:click=move |_| open.update(|open| *open = !*open)
+        >
+            "☀"
+        </button>
+        <Show when=open fallback=|| ()>
+            <div class="fixed top-12 right-3 p-4 rounded-lg shadow-lg bg-neutral-100 dark:bg-neutral-800
+                        flex flex-col gap-2 text-sm">
+                <div class="flex items-center justify-between">
+                    <button class="px-2" on:click=move |_| months_back.update(|back| *back += 1)>"‹"</button>
+                    {move || month().0}
+                    <button
+                        class="px-2 disabled:opacity-30"
+                        disabled=move || months_back() == 0
+                        on:click=move |_| months_back.update(|back| *back = back.saturating_sub(1))
+                    >
+                        "›"
+                    </button>
+                </div>
+                <div class="grid grid-cols-7 gap-1 text-center">{days}</div>
+                <p class="opacity-60">"Green: solved on the day. Amber: solved late."</p>
+            </div>
+        </Show>
+    }
+}
+
+use std::collections::HashMap;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use macros::classes;
+
+use crate::goals::local_day;
+use crate::seed::Scramble;
+use crate::stats::use_solves;
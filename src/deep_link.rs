@@ -0,0 +1,154 @@
+//! What the page's query string asks to start with, so a tutorial or a post
+//! can link straight into a configured board:
+//!
+//! - `s`: a scramble code, as from [`scramble_url`](crate::location::scramble_url).
+//! - `shape`: e.g. `5x5`, for a random scramble of that shape.
+//! - `moves`: scramble only this many random moves away from solved.
+//! - `mode`: `daily` for today's daily challenge, or `versus`.
+//! - `boards`: how many boards to solve at once, this visit only.
+//! - `embed`: `1` for the bare board, see [`embed`](crate::embed).
+//! - `size`: the edge of a tile in pixels.
+//!
+//! `s` and `mode=daily` fix the scramble, so `shape` and `moves` are ignored
+//! next to them. Anything that doesn't parse is skipped with a warning.
+//!
+//! A replay goes in the fragment instead, as `#r=<code>` from
+//! [`replay_url`](crate::location::replay_url), and opens over a board
+//! loaded with its scramble.
+
+const DEFAULT_SHAPE: (usize, usize) = (4, 4);
+/// Larger boards aren't practical to play, and the link was likely a typo.
+const MAX_SIDE: usize = 20;
+const MIN_TILE_SIZE: f64 = 16.0;
+const MAX_TILE_SIZE: f64 = 256.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Solo,
+    Daily,
+    Versus,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DeepLink {
+    pub scramble: Option<Scramble>,
+    pub shape: Option<(usize, usize)>,
+    pub moves: Option<u16>,
+    pub mode: Mode,
+    pub boards: Option<u8>,
+    pub embed: bool,
+    pub tile_size: Option<f64>,
+    pub replay: Option<ReplayFile>,
+}
+
+impl DeepLink {
+    /// The link this page was opened with.
+    pub fn current() -> Self {
+        let scramble = query_param("s").and_then(|code| match Scramble::parse(&code) {
+            Ok(scramble) => Some(scramble),
+            Err(err) => {
+                log::warn!("ignoring linked scramble: {err}");
+                None
+            }
+        });
+        let shape = query_param("shape").and_then(|shape| {
+            let parsed = parse_shape(&shape);
+            if parsed.is_none() {
+                log::warn!("ignoring linked shape {shape:?}");
+            }
+            parsed
+        });
+        let moves = query_param("moves").and_then(|moves| match moves.parse() {
+            Ok(moves) if moves > 0 => Some(moves),
+            _ => {
+                log::warn!("ignoring linked scramble length {moves:?}");
+                None
+            }
+        });
+        let mode = match query_param("mode").as_deref() {
+            None | Some("solo") => Mode::Solo,
+            Some("daily") => Mode::Daily,
+            Some("versus") => Mode::Versus,
+            Some(mode) => {
+                log::warn!("ignoring unknown mode {mode:?}");
+                Mode::Solo
+            }
+        };
+        let boards = query_param("boards").and_then(|boards| match boards.parse() {
+            Ok(boards) if (1..=MAX_BOARDS).contains(&boards) => Some(boards),
+            _ => {
+                log::warn!("ignoring linked board count {boards:?}");
+                None
+            }
+        });
+        let embed = query_param("embed").is_some_and(|embed| embed != "0");
+        let tile_size = query_param("size").and_then(|size| match size.parse() {
+            Ok(size) if (MIN_TILE_SIZE..=MAX_TILE_SIZE).contains(&size) => Some(size),
+            _ => {
+                log::warn!("ignoring linked tile size {size:?}");
+                None
+            }
+        });
+
+        let replay = fragment_param("r").and_then(|code| match ReplayFile::decode(&code) {
+            Ok(replay) => Some(replay),
+            Err(err) => {
+                log::warn!("ignoring linked replay: {err}");
+                None
+            }
+        });
+
+        Self {
+            scramble,
+            shape,
+            moves,
+            mode,
+            boards,
+            embed,
+            tile_size,
+            replay,
+        }
+    }
+
+    /// The day whose challenge the link asks for, if any.
+    pub fn daily_day(&self) -> Option<i64> {
+        (self.mode == Mode::Daily && self.scramble.is_none())
+            .then(|| local_day(js_sys::Date::now()))
+    }
+
+    /// The board to start on: the linked scramble, the replay's, the daily
+    /// one, or a random one of the linked shape, 4x4 by default.
+    pub fn puzzle(&self) -> AnyPuzzle {
+        if let Some(scramble) = self.scramble {
+            return scramble.puzzle();
+        }
+        if let Some(replay) = &self.replay {
+            return replay.scramble.puzzle();
+        }
+        if let Some(day) = self.daily_day() {
+            return daily::scramble(day).puzzle();
+        }
+
+        let shape = self.shape.unwrap_or(DEFAULT_SHAPE);
+        match self.moves {
+            Some(length) => Scramble::random(shape, ScrambleMode::RandomMoves { length }).puzzle(),
+            None => AnyPuzzle::new(shape),
+        }
+    }
+}
+
+fn parse_shape(text: &str) -> Option<(usize, usize)> {
+    let (width, height) = text.split_once(['x', 'X'])?;
+    let shape = (width.parse().ok()?, height.parse().ok()?);
+    let valid = |side| (2..=MAX_SIDE).contains(&side);
+    (valid(shape.0) && valid(shape.1)).then_some(shape)
+}
+
+use crate::daily;
+use crate::goals::local_day;
+use crate::location::{fragment_param, query_param};
+use crate::puzzle::AnyPuzzle;
+use crate::replay_file::ReplayFile;
+use crate::seed::{Scramble, ScrambleMode};
+use crate::settings::MAX_BOARDS;
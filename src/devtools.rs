@@ -0,0 +1,182 @@
+#[component]
+pub fn DevPanel(
+    dev_mode: RwSignal<bool>,
+    puzzle: RwSignal<AnyPuzzle>,
+    game_state: RwSignal<GameState>,
+) -> impl IntoView {
+    let scramble_code = create_memo(move |_| {
+        with!(|puzzle| puzzle.scramble().encode())
+            .chars()
+            .chunks(13)
+            .into_iter()
+            .map(|chunk| chunk.collect::<String>())
+            .join("\n")
+    });
+    let scramble_code_base32 = memo!(|puzzle| puzzle.scramble().encode_base32());
+
+    // Evaluated a slice at a time, yielding to the page between slices, and
+    // only while the panel is open, so a move waits for at most one slice.
+    let evaluation = create_rw_signal(None::<(Evaluation, Option<MoveQuality>)>);
+    let previous = store_value(None::<(Vec<usize>, Evaluation)>);
+    create_effect(move |_| {
+        if !dev_mode() {
+            previous.set_value(None);
+            return;
+        }
+
+        let (pieces, shape, goal) =
+            with!(|puzzle| (puzzle.pieces(), puzzle.shape(), puzzle.goal()));
+        spawn_local(async move {
+            let stale = || puzzle.with_untracked(AnyPuzzle::pieces) != pieces;
+            let Some(current) =
+                solver::evaluate_in_slices(&pieces, shape, goal, solver::DEFAULT_BUDGET, stale)
+                    .await
+            else {
+                return; // a newer run is under way
+            };
+
+            let quality = previous.with_value(|previous| {
+                let (previous_pieces, previous) = previous.as_ref()?;
+                let moved = pieces
+                    .iter()
+                    .zip(previous_pieces)
+                    .filter(|&(&now, &before)| now != before && now != 0)
+                    .count();
+                // A lower bound on either side says nothing about the move.
+                (previous_pieces.len() == pieces.len()
+                    && moved > 0
+                    && previous.exact
+                    && current.exact)
+                    .then(|| MoveQuality::classify(previous.distance, current.distance, moved))
+            });
+
+            previous.set_value(Some((pieces, current.clone())));
+            evaluation.set(Some((current, quality)));
+        });
+    });
+    let distance = move || {
+        evaluation.with(|evaluation| match evaluation {
+            Some((
+                Evaluation {
+                    distance,
+                    exact: true,
+                    ..
+                },
+                _,
+            )) => distance.to_string(),
+            Some((Evaluation { distance, .. }, _)) => format!(">= {distance}"),
+            None => "...".to_string(),
+        })
+    };
+    let last_move = move || {
+        evaluation.with(|evaluation| match evaluation {
+            Some((_, Some(MoveQuality::Optimal))) => "optimal",
+            Some((_, Some(MoveQuality::Neutral))) => "neutral",
+            Some((_, Some(MoveQuality::Regressive))) => "regressive",
+            _ => "-",
+        })
+    };
+
+    // Read when the panel opens; by then startup is long over.
+    let startup_report = move || {
+        let mut report = startup::milestones()
+            .into_iter()
+            .map(|(milestone, at)| format!("{}: {at:.0}ms\n", milestone.label()))
+            .collect::<String>();
+        if let Some((transferred, decoded)) = startup::wasm_size() {
+            report += &format!(
+                "wasm: {:.0}kB ({:.0}kB unpacked)",
+                transferred / 1024.0,
+                decoded / 1024.0
+            );
+        }
+        report
+    };
+
+    view! {
+        <div class=move || classes!(
+            "ease-out-circ transition-all transform-gpu duration-150",
+            dev_mode() => "translate-x-0" else "-translate-x-6 opacity-0",
+        )>
+            <AnimatedShow when=dev_mode hide_delay=Duration::from_millis(150)>
+                <div class="absolute ml-6 mt-3">
+                    <pre class="mb-3">{scramble_code}</pre>
+                    <p class="mb-3 max-w-[11rem] font-mono text-sm">{scramble_code_base32}</p>
+                    <pre class="text-sm">"is_solved(): "{move || with!(|puzzle| puzzle.is_solved())}</pre>
+                    <pre class="text-sm">"distance: "{distance}</pre>
+                    <pre class="text-sm">"last move: "{last_move}</pre>
+                    <pre class="text-sm">"game_state: "{move || format!("{:#?}", game_state())}</pre>
+                    <pre class="mt-3 text-sm">{startup_report}</pre>
+                    <div class="mt-3 max-w-xs max-h-48 overflow-y-auto">
+                        <SessionLogList limit=20 />
+                    </div>
+                </div>
+            </AnimatedShow>
+        </div>
+    }
+}
+
+const UNLOCK_SEQUENCE: &str = match option_env!("JUGO_DEVTOOLS_SEQUENCE") {
+    Some(sequence) => sequence,
+    None => "idkfa",
+};
+const UNLOCK_PARAM: &str = match option_env!("JUGO_DEVTOOLS_PARAM") {
+    Some(param) => param,
+    None => "dev",
+};
+
+/// Keeps dev controls locked in release builds until [`UNLOCK_SEQUENCE`] is typed
+/// or the page is opened with [`UNLOCK_PARAM`] in its query string.
+#[derive(Clone, Copy)]
+pub struct DevUnlock {
+    unlocked: RwSignal<bool>,
+    typed: StoredValue<String>,
+}
+
+impl DevUnlock {
+    pub fn new() -> Self {
+        let unlocked = cfg!(debug_assertions) || query_param(UNLOCK_PARAM).is_some();
+
+        Self {
+            unlocked: create_rw_signal(unlocked),
+            typed: store_value(String::new()),
+        }
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked.get_untracked()
+    }
+
+    pub fn feed(&self, key: &str) {
+        if self.is_unlocked() || key.chars().count() != 1 {
+            return;
+        }
+
+        let matched = self.typed.try_update_value(|typed| {
+            typed.push_str(key);
+            while typed.len() > UNLOCK_SEQUENCE.len() {
+                typed.remove(0);
+            }
+            typed == UNLOCK_SEQUENCE
+        });
+
+        if let Some(true) = matched {
+            log::info!("dev controls unlocked");
+            self.unlocked.set(true);
+        }
+    }
+}
+
+use std::time::Duration;
+
+use leptos::*;
+
+use itertools::Itertools;
+use macros::{classes, memo};
+
+use crate::game_state::GameState;
+use crate::location::query_param;
+use crate::puzzle::AnyPuzzle;
+use crate::session_log::SessionLogList;
+use crate::solver::{self, Evaluation, MoveQuality};
+use crate::startup;
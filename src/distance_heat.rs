@@ -0,0 +1,38 @@
+//! Tints every tile by how far it is from home, from cool when it's there to
+//! hot at the far corner, so a scramble's hard spots show at a glance.
+
+/// Tints each tile of `puzzle` on `overlays` while the setting is on.
+pub fn show_distance_heat(overlays: Overlays, puzzle: RwSignal<AnyPuzzle>) {
+    let settings = use_settings();
+    let enabled = memo!(|settings| settings.distance_heat);
+
+    overlays.register("distance_heat", move || {
+        if !enabled() {
+            return Vec::new();
+        }
+        with!(|puzzle| {
+            let (width, height) = puzzle.shape();
+            let farthest = (width + height - 2) as f64;
+            puzzle
+                .iter_indexed()
+                .filter(|&(_, piece)| piece != 0)
+                .map(|(cell, piece)| {
+                    let home = puzzle.home_of(piece);
+                    let distance = cell.0.abs_diff(home.0) + cell.1.abs_diff(home.1);
+                    Annotation::Heat {
+                        cell,
+                        heat: distance as f64 / farthest,
+                    }
+                })
+                .collect()
+        })
+    });
+}
+
+use leptos::*;
+
+use macros::memo;
+
+use crate::overlay::{Annotation, Overlays};
+use crate::puzzle::AnyPuzzle;
+use crate::settings::use_settings;
@@ -0,0 +1,159 @@
+//! Dragging tiles with the pointer: the grabbed tile, and any between it and
+//! the blank, follow the pointer part of the way to the next cell and only
+//! move for real once let go past halfway.
+//!
+//! Letting go while still moving fast is a flick, which carries on into the
+//! tiles behind the grabbed one, as if one further back had been dragged.
+
+/// Pointer travel, in pixels, below which a drag counts as a tap.
+const TAP_SLOP: f64 = 4.0;
+/// Release speed, in cells per second, that counts as a flick; each further
+/// multiple of it takes one more tile along.
+const FLICK_SPEED: f64 = 6.0;
+/// A pointer that stopped this long before letting go has no momentum left.
+const FLICK_WINDOW: Duration = Duration::from_millis(80);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drag {
+    pub grabbed: (usize, usize),
+    /// The cells whose tiles move, from `grabbed` to just before the blank.
+    cells: Vec<(usize, usize)>,
+    /// One cell towards the blank.
+    direction: (isize, isize),
+    /// How many cells lie behind `grabbed`, away from the blank, up to the edge.
+    behind: usize,
+    /// Whether the board is drawn flipped, so the pointer moves the other way.
+    mirrored: bool,
+    start: (f64, f64),
+    pitch: f64,
+    pub started_at: Instant,
+    travel: f64,
+    /// The last pointer sample, as time and distance along `direction`.
+    last: (Instant, f64),
+    /// Along `direction`, in pixels per second.
+    velocity: f64,
+    /// How far the tiles have moved, in cells from 0 to 1.
+    pub offset: f64,
+}
+
+pub enum Release {
+    /// Barely moved; treat it like a click at `started_at`.
+    Tap,
+    /// Slide from `from`: the grabbed cell, or one behind it after a flick.
+    Slide {
+        from: (usize, usize),
+    },
+    Cancel,
+}
+
+impl Drag {
+    /// `None` unless `grabbed` is in line with the blank. `pitch` is the
+    /// distance between neighbouring cells in pixels.
+    pub fn start(
+        puzzle: &AnyPuzzle,
+        grabbed: (usize, usize),
+        pointer: (f64, f64),
+        pitch: f64,
+        mirrored: bool,
+        at: Instant,
+    ) -> Option<Self> {
+        let (bx, by) = puzzle.blank();
+        let (x, y) = grabbed;
+        if (x != bx) == (y != by) {
+            return None;
+        }
+
+        let (width, height) = puzzle.shape();
+        let (dx, dy) = (sign(bx, x), sign(by, y));
+        let (cells, behind) = match (dx, dy) {
+            (0, -1) => (between(y, by).map(|y| (x, y)).collect(), height - 1 - y),
+            (0, _) => (between(y, by).map(|y| (x, y)).collect(), y),
+            (-1, _) => (between(x, bx).map(|x| (x, y)).collect(), width - 1 - x),
+            (_, _) => (between(x, bx).map(|x| (x, y)).collect(), x),
+        };
+
+        Some(Self {
+            grabbed,
+            cells,
+            direction: (dx, dy),
+            behind,
+            mirrored,
+            start: pointer,
+            pitch,
+            started_at: at,
+            travel: 0.0,
+            last: (at, 0.0),
+            velocity: 0.0,
+            offset: 0.0,
+        })
+    }
+
+    pub fn update(&mut self, (px, py): (f64, f64), at: Instant) {
+        let (dx, dy) = (px - self.start.0, py - self.start.1);
+        self.travel = self.travel.max(dx.hypot(dy));
+        let dx = if self.mirrored { -dx } else { dx };
+        let along = dx * self.direction.0 as f64 + dy * self.direction.1 as f64;
+        self.offset = (along / self.pitch).clamp(0.0, 1.0);
+
+        let elapsed = (at - self.last.0).as_secs_f64();
+        if elapsed > 0.0 {
+            // Smoothed, since pointer samples come in unevenly.
+            let velocity = (along - self.last.1) / elapsed;
+            self.velocity = (self.velocity + velocity) / 2.0;
+            self.last = (at, along);
+        }
+    }
+
+    pub fn release(&self, at: Instant) -> Release {
+        let speed = match at - self.last.0 {
+            idle if idle > FLICK_WINDOW => 0.0,
+            _ => self.velocity / self.pitch,
+        };
+        let extra = match speed >= FLICK_SPEED {
+            true => ((speed / FLICK_SPEED) as usize - 1).min(self.behind),
+            false => 0,
+        };
+        let (x, y) = self.grabbed;
+        let (dx, dy) = self.direction;
+        let from = (
+            x.wrapping_add_signed(-dx * extra as isize),
+            y.wrapping_add_signed(-dy * extra as isize),
+        );
+
+        match () {
+            _ if self.travel < TAP_SLOP => Release::Tap,
+            _ if self.offset >= 0.5 || speed >= FLICK_SPEED => Release::Slide { from },
+            _ => Release::Cancel,
+        }
+    }
+
+    /// How far the tile at `idx` is pulled along, in board cells.
+    pub fn displacement(&self, idx: (usize, usize)) -> Option<(f64, f64)> {
+        let (dx, dy) = self.direction;
+        self.cells
+            .contains(&idx)
+            .then(|| (dx as f64 * self.offset, dy as f64 * self.offset))
+    }
+}
+
+fn sign(to: usize, from: usize) -> isize {
+    match to.cmp(&from) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+/// From `from` up to, but not including, `to`, in either direction.
+fn between(from: usize, to: usize) -> Box<dyn Iterator<Item = usize>> {
+    match from < to {
+        true => Box::new(from..to),
+        false => Box::new((to + 1..=from).rev()),
+    }
+}
+
+use core::cmp::Ordering;
+use core::time::Duration;
+
+use crate::clock::Instant;
+use crate::puzzle::AnyPuzzle;
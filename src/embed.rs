@@ -0,0 +1,93 @@
+//! Embedding the board in another page with `?embed=1`: everything but the
+//! timer, the board and its move log is left out, and the host page drives it
+//! with `postMessage`.
+//!
+//! The host sends `{"type": "start"}` for a random scramble, optionally with
+//! `"scramble": "<code>"`, and `{"type": "reset"}` to start the current one
+//! over. Messages from anywhere but the parent page are ignored. The board
+//! posts `{"type": "ready"}` once it's listening, and
+//! `{"type": "solved", "scramble", "time_ms", "moves"}` after each solve.
+//!
+//! `?size=` sets the edge of a tile in pixels, scaling the rest to match.
+
+/// The host's requests.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Command {
+    Start { scramble: Option<String> },
+    Reset,
+}
+
+/// What the board tells the host.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Report {
+    Ready,
+    Solved {
+        scramble: String,
+        time_ms: u64,
+        moves: usize,
+    },
+}
+
+/// Scales the page so tiles are `size` pixels across; everything is sized in
+/// `rem`, tiles included.
+pub fn set_tile_size(size: f64) {
+    return_with_try! {
+        let root = document().document_element()?.dyn_into::<HtmlElement>()?;
+        root.style().set_property("font-size", &format!("{}px", size / TILE_REM))?;
+    }
+}
+
+/// Passes the parent page's commands to `on_command` and tells it the board
+/// is ready for them.
+pub fn listen(on_command: impl Fn(Command) + 'static) {
+    let handle = window_event_listener(message, move |event| {
+        let from_parent = match (event.source(), window().parent()) {
+            (Some(source), Ok(Some(parent))) => Object::is(&source, &parent),
+            _ => false,
+        };
+        if !from_parent {
+            return;
+        }
+
+        // Hosts may send either JSON text or a plain object.
+        let data = event.data();
+        let text = match data.as_string() {
+            Some(text) => text,
+            None => match JSON::stringify(&data) {
+                Ok(text) => text.into(),
+                Err(_) => return,
+            },
+        };
+        match serde_json::from_str(&text) {
+            Ok(command) => on_command(command),
+            Err(err) => log::warn!("ignoring message from the host page: {err}"),
+        }
+    });
+    on_cleanup(move || handle.remove());
+
+    report(&Report::Ready);
+}
+
+/// Posts `report` to the parent page, if there is one.
+pub fn report(report: &Report) {
+    return_with_try! {
+        let parent = window().parent().ok()??;
+        // Outside a frame the page is its own parent.
+        if parent != window() {
+            let text = serde_json::to_string(report)?;
+            parent.post_message(&JSON::parse(&text)?, "*")?;
+        }
+    }
+}
+
+use js_sys::{Object, JSON};
+use leptos::{ev::message, *};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+use macros::return_with_try;
+
+use crate::board_view::TILE_REM;
@@ -0,0 +1,79 @@
+//! Scrambles marked for later, per profile: starred ones to come back to for
+//! targeted practice, and blacklisted ones that new scrambles skip.
+
+/// A random scramble that's blacklisted anyway is retried at most this often.
+const MAX_REROLLS: usize = 100;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Marks {
+    starred: Vec<Scramble>,
+    blacklisted: Vec<Scramble>,
+}
+
+#[derive(Clone, Copy)]
+pub struct ScrambleMarks(RwSignal<Marks>);
+
+impl ScrambleMarks {
+    pub fn is_starred(&self, scramble: &Scramble) -> bool {
+        self.0.with(|marks| marks.starred.contains(scramble))
+    }
+
+    pub fn is_blacklisted(&self, scramble: &Scramble) -> bool {
+        self.0.with(|marks| marks.blacklisted.contains(scramble))
+    }
+
+    /// Newest first.
+    pub fn starred(&self) -> Vec<Scramble> {
+        self.0
+            .with(|marks| marks.starred.iter().rev().copied().collect())
+    }
+
+    pub fn toggle_star(&self, scramble: Scramble) {
+        self.0.update(|marks| toggle(&mut marks.starred, scramble));
+    }
+
+    pub fn toggle_blacklist(&self, scramble: Scramble) {
+        self.0
+            .update(|marks| toggle(&mut marks.blacklisted, scramble));
+    }
+
+    /// Whether new scrambles should pass over `scramble`.
+    pub fn skips(&self, scramble: &Scramble) -> bool {
+        self.0
+            .with_untracked(|marks| marks.blacklisted.contains(scramble))
+    }
+
+    /// A random scramble that isn't blacklisted.
+    pub fn random(&self, shape: (usize, usize), setting: ScrambleSetting) -> Scramble {
+        iter::repeat_with(|| setting.random(shape))
+            .take(MAX_REROLLS)
+            .find(|scramble| !self.skips(scramble))
+            .unwrap_or_else(|| setting.random(shape))
+    }
+}
+
+fn toggle(list: &mut Vec<Scramble>, scramble: Scramble) {
+    match list.iter().position(|marked| *marked == scramble) {
+        Some(index) => _ = list.remove(index),
+        None => list.push(scramble),
+    }
+}
+
+pub fn provide_scramble_marks() -> ScrambleMarks {
+    let marks = ScrambleMarks(create_persisted_signal("scramble_marks", Marks::default()));
+    provide_context(marks);
+    marks
+}
+
+pub fn use_scramble_marks() -> ScrambleMarks {
+    expect_context()
+}
+
+use std::iter;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::seed::Scramble;
+use crate::settings::ScrambleSetting;
+use crate::signal_ext::create_persisted_signal;
@@ -0,0 +1,330 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameState {
+    #[default]
+    NotSolving,
+    Inspecting {
+        since: Instant,
+    },
+    Solving {
+        since: Instant,
+    },
+    Paused {
+        elapsed: Duration,
+    },
+    Solved {
+        took: Duration,
+    },
+    Dnf {
+        after: Duration,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameEvent {
+    /// Back to a fresh, untimed board.
+    Reset,
+    Inspect {
+        at: Instant,
+    },
+    /// Starts the timer without a move, e.g. at the end of inspection.
+    Start {
+        at: Instant,
+    },
+    /// A move has been made; `solved` is whether the board is solved after it.
+    Move {
+        at: Instant,
+        solved: bool,
+    },
+    Pause {
+        at: Instant,
+    },
+    Resume {
+        at: Instant,
+    },
+    /// Stops the timer as if the board had been solved.
+    Finish {
+        at: Instant,
+    },
+    GiveUp {
+        at: Instant,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub from: GameState,
+    pub event: GameEvent,
+}
+
+impl fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot apply {:?} to {:?}", self.event, self.from)
+    }
+}
+
+impl std::error::Error for InvalidTransition {}
+
+impl GameState {
+    pub fn transition(&self, event: GameEvent) -> Result<GameState, InvalidTransition> {
+        use GameEvent as E;
+        use GameState as S;
+
+        let next = match (*self, event) {
+            (_, E::Reset) => S::NotSolving,
+
+            (S::NotSolving, E::Inspect { at }) => S::Inspecting { since: at },
+
+            (S::NotSolving | S::Inspecting { .. }, E::Start { at }) => S::Solving { since: at },
+            (S::NotSolving | S::Inspecting { .. }, E::Move { solved: true, .. }) => S::Solved {
+                took: Duration::ZERO,
+            },
+            (S::NotSolving | S::Inspecting { .. }, E::Move { at, .. }) => S::Solving { since: at },
+
+            (S::Solving { since }, E::Move { at, solved: true } | E::Finish { at }) => {
+                S::Solved { took: at - since }
+            }
+            (S::Solving { since }, E::Move { .. }) => S::Solving { since },
+            (S::Solving { since }, E::Pause { at }) => S::Paused {
+                elapsed: at - since,
+            },
+            (S::Paused { elapsed }, E::Resume { at }) => S::Solving {
+                since: at - elapsed,
+            },
+
+            // moves after the end of a solve don't affect its result
+            (finished @ (S::Solved { .. } | S::Dnf { .. }), E::Move { .. }) => finished,
+
+            (S::Inspecting { .. }, E::GiveUp { .. }) => S::Dnf {
+                after: Duration::ZERO,
+            },
+            (S::Solving { since }, E::GiveUp { at }) => S::Dnf { after: at - since },
+            (S::Paused { elapsed }, E::GiveUp { .. }) => S::Dnf { after: elapsed },
+
+            (from, event) => return Err(InvalidTransition { from, event }),
+        };
+
+        Ok(next)
+    }
+
+    /// Applies `event` in place, returning whether the state changed.
+    /// Invalid transitions are logged and leave the state as is.
+    pub fn apply(&mut self, event: GameEvent) -> bool {
+        match self.transition(event) {
+            Ok(next) if next == *self => false,
+            Ok(next) => {
+                *self = next;
+                true
+            }
+            Err(err) => {
+                log::debug!("{err}");
+                false
+            }
+        }
+    }
+
+    pub fn solve_time(&self, now: Instant) -> Option<Duration> {
+        match *self {
+            GameState::Solving { since } => Some(now - since),
+            GameState::Paused { elapsed } => Some(elapsed),
+            GameState::Solved { took } => Some(took),
+            GameState::Dnf { after } => Some(after),
+            GameState::NotSolving | GameState::Inspecting { .. } => None,
+        }
+    }
+
+    #[inline]
+    pub fn is_solving(&self) -> bool {
+        matches!(self, GameState::Solving { .. })
+    }
+    /// Whether a solve is underway, timed or about to be, so the board should
+    /// show exactly where everything is.
+    #[inline]
+    pub fn is_live(&self) -> bool {
+        matches!(
+            self,
+            GameState::Inspecting { .. } | GameState::Solving { .. } | GameState::Paused { .. }
+        )
+    }
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        matches!(self, GameState::Solved { .. } | GameState::Dnf { .. })
+    }
+}
+
+use core::fmt;
+use core::time::Duration;
+
+use crate::clock::Instant;
+
+#[cfg(test)]
+mod tests {
+    const START: u64 = 1_000;
+    const NOW: u64 = 4_000;
+
+    fn at(millis: u64) -> Instant {
+        Instant::default() + Duration::from_millis(millis)
+    }
+
+    /// One of each event, all at `NOW`, in the order `check` expects them.
+    fn events() -> [GameEvent; 9] {
+        let at = at(NOW);
+        [
+            E::Reset,
+            E::Inspect { at },
+            E::Start { at },
+            E::Move { at, solved: false },
+            E::Move { at, solved: true },
+            E::Pause { at },
+            E::Resume { at },
+            E::Finish { at },
+            E::GiveUp { at },
+        ]
+    }
+
+    /// Applies every event to `from`; `None` means the transition is invalid.
+    fn check(from: GameState, expected: [Option<GameState>; 9]) {
+        for (event, expected) in events().into_iter().zip(expected) {
+            let result = from.transition(event);
+            match expected {
+                Some(to) => assert_eq!(result, Ok(to), "{from:?} + {event:?}"),
+                None => assert_eq!(result, Err(InvalidTransition { from, event })),
+            }
+        }
+    }
+
+    #[test]
+    fn not_solving() {
+        check(
+            S::NotSolving,
+            [
+                Some(S::NotSolving),
+                Some(S::Inspecting { since: at(NOW) }),
+                Some(S::Solving { since: at(NOW) }),
+                Some(S::Solving { since: at(NOW) }),
+                Some(S::Solved {
+                    took: Duration::ZERO,
+                }),
+                None,
+                None,
+                None,
+                None,
+            ],
+        );
+    }
+
+    #[test]
+    fn inspecting() {
+        check(
+            S::Inspecting { since: at(START) },
+            [
+                Some(S::NotSolving),
+                None,
+                Some(S::Solving { since: at(NOW) }),
+                Some(S::Solving { since: at(NOW) }),
+                Some(S::Solved {
+                    took: Duration::ZERO,
+                }),
+                None,
+                None,
+                None,
+                Some(S::Dnf {
+                    after: Duration::ZERO,
+                }),
+            ],
+        );
+    }
+
+    #[test]
+    fn solving() {
+        let took = Duration::from_millis(NOW - START);
+        check(
+            S::Solving { since: at(START) },
+            [
+                Some(S::NotSolving),
+                None,
+                None,
+                Some(S::Solving { since: at(START) }),
+                Some(S::Solved { took }),
+                Some(S::Paused { elapsed: took }),
+                None,
+                Some(S::Solved { took }),
+                Some(S::Dnf { after: took }),
+            ],
+        );
+    }
+
+    /// A paused solve only picks up again on `Resume`; moves alone are refused.
+    #[test]
+    fn paused() {
+        let elapsed = Duration::from_secs(2);
+        check(
+            S::Paused { elapsed },
+            [
+                Some(S::NotSolving),
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(S::Solving {
+                    since: at(NOW) - elapsed,
+                }),
+                None,
+                Some(S::Dnf { after: elapsed }),
+            ],
+        );
+    }
+
+    #[test]
+    fn finished() {
+        let took = Duration::from_secs(3);
+        for finished in [S::Solved { took }, S::Dnf { after: took }] {
+            check(
+                finished,
+                [
+                    Some(S::NotSolving),
+                    None,
+                    None,
+                    Some(finished),
+                    Some(finished),
+                    None,
+                    None,
+                    None,
+                    None,
+                ],
+            );
+        }
+    }
+
+    #[test]
+    fn resumed_solve_keeps_its_elapsed_time() {
+        let mut state = S::Paused {
+            elapsed: Duration::from_secs(2),
+        };
+        assert!(state.apply(E::Resume { at: at(NOW) }));
+        assert!(state.apply(E::Move {
+            at: at(NOW + 500),
+            solved: true,
+        }));
+        assert_eq!(
+            state,
+            S::Solved {
+                took: Duration::from_millis(2_500),
+            }
+        );
+    }
+
+    #[test]
+    fn apply_reports_changes_only() {
+        let mut state = S::Solving { since: at(START) };
+        assert!(!state.apply(E::Move {
+            at: at(NOW),
+            solved: false,
+        }));
+        assert!(!state.apply(E::Inspect { at: at(NOW) }));
+        assert_eq!(state, S::Solving { since: at(START) });
+        assert!(state.apply(E::Pause { at: at(NOW) }));
+    }
+
+    use super::{GameEvent as E, GameState as S, *};
+}
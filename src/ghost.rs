@@ -0,0 +1,82 @@
+/// A small board that starts from the scramble and makes one move of the
+/// solver's line for every move of the player's, to race against.
+///
+/// The line is optimal when the solver finishes within its budget, which on
+/// anything past 3x3 it often won't; then the ghost plays greedily instead.
+#[component]
+pub fn GhostBoard(
+    #[prop(into)] puzzle: Signal<AnyPuzzle>,
+    #[prop(into)] moves: Signal<usize>,
+) -> impl IntoView {
+    let settings = use_settings();
+    let shown = memo!(|settings| settings.ghost);
+
+    let scramble = memo!(|puzzle| puzzle.scramble());
+    let line = create_rw_signal(None::<(Scramble, Vec<(usize, usize)>, bool)>);
+
+    // Searched a slice at a time, so neither loading a scramble nor the moves
+    // after it wait on the search.
+    create_effect(move |_| {
+        if !shown() {
+            return;
+        }
+        let target = scramble();
+        spawn_local(async move {
+            let pieces = target.puzzle().pieces();
+            let stale = || !shown.get_untracked() || scramble.get_untracked() != target;
+            let evaluation = solver::evaluate_in_slices(
+                &pieces,
+                target.shape,
+                target.goal,
+                solver::DEFAULT_BUDGET,
+                stale,
+            )
+            .await;
+            if let Some(evaluation) = evaluation {
+                line.set(Some((target, evaluation.solution, evaluation.exact)));
+            }
+        });
+    });
+
+    let ghost = create_memo(move |_| {
+        let scramble = scramble();
+        let steps = moves();
+        line.with(|line| {
+            let mut ghost = scramble.puzzle();
+            let solution = match line {
+                Some((for_scramble, solution, true)) if *for_scramble == scramble => {
+                    solution.clone()
+                }
+                _ => solver::greedy_line(&ghost.pieces(), scramble.shape, scramble.goal, steps),
+            };
+            for &cell in solution.iter().take(steps) {
+                ghost.slide_from(cell);
+            }
+            ghost.pieces()
+        })
+    });
+    let exact = move || {
+        line.with(
+            |line| matches!(line, Some((for_scramble, _, true)) if *for_scramble == scramble()),
+        )
+    };
+
+    view! {
+        <Show when=shown fallback=|| ()>
+            <div class="flex flex-col items-center gap-2 my-auto ml-8 text-sm">
+                <span class="opacity-60">{move || if exact() { "Optimal ghost" } else { "Greedy ghost" }}</span>
+                {move || view! { <MiniBoard pieces=ghost shape=scramble().shape /> }}
+            </div>
+        </Show>
+    }
+}
+
+use leptos::*;
+
+use macros::memo;
+
+use crate::puzzle::AnyPuzzle;
+use crate::replay::MiniBoard;
+use crate::seed::Scramble;
+use crate::settings::use_settings;
+use crate::solver;
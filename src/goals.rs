@@ -0,0 +1,198 @@
+/// Personal targets, persisted next to the solves.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Goals {
+    /// Solves per day needed to keep a streak going.
+    pub daily_solves: u32,
+    /// Target times per board shape, keyed like `"4x4"`.
+    pub target_times: BTreeMap<String, Duration>,
+}
+
+impl Default for Goals {
+    fn default() -> Self {
+        Self {
+            daily_solves: 10,
+            target_times: BTreeMap::new(),
+        }
+    }
+}
+
+pub fn shape_key((width, height): (usize, usize)) -> String {
+    format!("{width}x{height}")
+}
+
+/// Days since the Unix epoch in local time.
+pub fn local_day(millis: f64) -> i64 {
+    let offset_minutes = js_sys::Date::new(&millis.into()).get_timezone_offset();
+    ((millis - offset_minutes * 60_000.0) / 86_400_000.0).floor() as i64
+}
+
+/// Consecutive days, ending today, with at least `target` solves. Today only
+/// breaks the streak once it's over, so it counts from yesterday until then.
+pub fn streak(solves: &[Solve], today: i64, target: u32) -> u32 {
+    let mut per_day = BTreeMap::<i64, u32>::new();
+    for solve in solves {
+        *per_day.entry(local_day(solve.finished_at)).or_default() += 1;
+    }
+    let met = |day| {
+        per_day
+            .get(&day)
+            .is_some_and(|&count| count >= target.max(1))
+    };
+
+    let start = if met(today) { today } else { today - 1 };
+    (0..).take_while(|&back| met(start - back)).count() as u32
+}
+
+pub fn provide_goals() -> RwSignal<Goals> {
+    let goals = create_persisted_signal("goals", Goals::default());
+    provide_context(goals);
+    goals
+}
+
+pub fn use_goals() -> RwSignal<Goals> {
+    expect_context()
+}
+
+/// Today's progress, the current streak, and how each shape is doing against
+/// its target time.
+#[component]
+pub fn GoalsDashboard() -> impl IntoView {
+    let solves = use_solves();
+    let goals = use_goals();
+
+    let today = move || {
+        let today = local_day(js_sys::Date::now());
+        solves.with(|solves| {
+            let count = solves
+                .iter()
+                .filter(|solve| local_day(solve.finished_at) == today)
+                .count() as u32;
+            let streak = goals.with(|goals| streak(solves, today, goals.daily_solves));
+            (count, streak)
+        })
+    };
+    let daily = move || goals.with(|goals| goals.daily_solves.max(1));
+
+    let shapes = move || {
+        solves.with(|solves| {
+            let mut shapes: Vec<(usize, usize)> =
+                solves.iter().map(|solve| solve.scramble.shape).collect();
+            shapes.sort_unstable();
+            shapes.dedup();
+            shapes
+        })
+    };
+    let shape_row = move |shape: (usize, usize)| {
+        let key = shape_key(shape);
+        let target = {
+            let key = key.clone();
+            move || goals.with(|goals| goals.target_times.get(&key).copied())
+        };
+        let best = move || solves.with(|solves| personal_best(solves, shape));
+        let under = {
+            let target = target.clone();
+            move || {
+                let target = target()?;
+                Some(solves.with(|solves| {
+                    solves
+                        .iter()
+                        .filter(|solve| solve.scramble.shape == shape)
+                        .filter(|solve| solve.result().is_some_and(|time| time <= target))
+                        .count()
+                }))
+            }
+        };
+
+        view! {
+            <tr>
+                <td>{key.clone()}</td>
+                <td class="text-right">{move || best().map_or("-".to_string(), format_time)}</td>
+                <td class="text-right">
+                    <input
+                        type="number"
+                        min="0"
+                        step="0.1"
+                        placeholder="-"
+                        class="w-16 bg-transparent text-right"
+                        prop:value={
+                            let target = target.clone();
+                            move || target().map_or(String::new(), |target| target.as_secs_f64().to_string())
+                        }
+                        on:change=move |event| {
+                            let secs = event_target_value(&event).parse::<f64>().ok().filter(|&secs| secs > 0.0);
+                            goals.update(|goals| match secs {
+                                Some(secs) => {
+                                    goals.target_times.insert(key.clone(), Duration::from_secs_f64(secs));
+                                }
+                                None => {
+                                    goals.target_times.remove(&key);
+                                }
+                            });
+                        }
+                    />
+                </td>
+                <td class=move || classes!(
+                    "text-right",
+                    best().zip(target()).is_some_and(|(best, target)| best <= target) => "text-emerald-500",
+                )>
+                    {move || under().map_or("-".to_string(), |under| format!("{under} under"))}
+                </td>
+            </tr>
+        }
+    };
+
+    view! {
+        <div class="flex flex-col gap-2">
+            <h2 class="font-bold">"Goals"</h2>
+            <div class="flex items-center gap-3 font-mono text-xs">
+                <span>{move || format!("today {}/{}", today().0, daily())}</span>
+                <div class="flex-1 h-1.5 rounded-full bg-neutral-300 dark:bg-neutral-700 overflow-hidden">
+                    <div
+                        class="h-full bg-violet-500"
+                        style=("width", move || format!("{}%", (today().0 * 100 / daily()).min(100)))
+                    />
+                </div>
+                <span>{move || format!("streak {} days", today().1)}</span>
+                <label class="flex items-center gap-1 opacity-70">
+                    "per day"
+                    <input
+                        type="number"
+                        min="1"
+                        class="w-12 bg-transparent text-right"
+                        prop:value=daily
+                        on:change=move |event| {
+                            if let Ok(daily @ 1..) = event_target_value(&event).parse::<u32>() {
+                                goals.update(|goals| goals.daily_solves = daily);
+                            }
+                        }
+                    />
+                </label>
+            </div>
+            <table class="self-start font-mono text-xs child:child:child:px-1">
+                <thead class="opacity-60">
+                    <tr>
+                        <th class="text-left">"shape"</th>
+                        <th class="text-right">"best"</th>
+                        <th class="text-right">"target"</th>
+                        <th class="text-right">"progress"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    <For each=shapes key=|&shape| shape children=shape_row />
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use macros::classes;
+
+use crate::signal_ext::create_persisted_signal;
+use crate::stats::{format_time, personal_best, use_solves, Solve};
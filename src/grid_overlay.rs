@@ -0,0 +1,92 @@
+/// Row/column labels and faint gridlines around the board, sized from the
+/// board's `--tile`, `--gap` and `--pitch` variables.
+#[component]
+pub fn GridOverlay(#[prop(into)] shape: Signal<(usize, usize)>) -> impl IntoView {
+    let settings = use_settings();
+    let shown = memo!(|settings| settings.coordinates);
+    let mirrored = memo!(|settings| settings.mirror_board);
+
+    let columns = move || {
+        let (width, height) = shape();
+        (0..width)
+            .map(|x| {
+                view! {
+                    <div
+                        class=move || classes!(
+                            "absolute -top-6 left-0 w-[var(--tile)] text-center
+                            translate-x-[calc(var(--x)*var(--pitch))]",
+                            mirrored() => "-scale-x-100",
+                        )
+                        style=("--x", x)
+                    >
+                        {column_label(x)}
+                    </div>
+                }
+            })
+            .chain((0..=width).map(|x| {
+                view! {
+                    <div
+                        class="absolute top-0 left-0 w-px bg-current opacity-10
+                           h-[calc(var(--h)*var(--pitch)_-_var(--gap))]
+                           translate-x-[calc(var(--x)*var(--pitch)_-_var(--gap)/2)]"
+                        style=("--x", x)
+                        style=("--h", height)
+                    />
+                }
+            }))
+            .collect_view()
+    };
+    let rows = move || {
+        let (width, height) = shape();
+        (0..height)
+            .map(|y| {
+                view! {
+                    <div
+                        class=move || classes!(
+                            "absolute top-0 -left-6 h-[var(--tile)] flex items-center
+                            translate-y-[calc(var(--y)*var(--pitch))]",
+                            mirrored() => "-scale-x-100",
+                        )
+                        style=("--y", y)
+                    >
+                        {y + 1}
+                    </div>
+                }
+            })
+            .chain((0..=height).map(|y| {
+                view! {
+                    <div
+                        class="absolute top-0 left-0 h-px bg-current opacity-10
+                           w-[calc(var(--w)*var(--pitch)_-_var(--gap))]
+                           translate-y-[calc(var(--y)*var(--pitch)_-_var(--gap)/2)]"
+                        style=("--y", y)
+                        style=("--w", width)
+                    />
+                }
+            }))
+            .collect_view()
+    };
+
+    view! {
+        <Show when=shown fallback=|| ()>
+            <div class="contents font-mono text-sm text-neutral-500 pointer-events-none">
+                {columns}
+                {rows}
+            </div>
+        </Show>
+    }
+}
+
+/// `a`, `b`, ... like chess files, falling back to numbers past `z`.
+fn column_label(x: usize) -> String {
+    match u8::try_from(x) {
+        Ok(x @ ..26) => char::from(b'a' + x).to_string(),
+        _ => (x + 1).to_string(),
+    }
+}
+
+use leptos::*;
+
+use macros::{classes, memo};
+
+use crate::settings::use_settings;
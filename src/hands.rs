@@ -0,0 +1,121 @@
+//! How a solve's moves fell between the hands, from which keys slide which
+//! cells. Alternating hands is faster than running one hand on its own, so
+//! this is what to look at when tuning a keymap.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HandStats {
+    /// The share of consecutive moves made with different hands, 0 to 1.
+    pub alternation: f64,
+    /// The most moves in a row with each hand.
+    pub longest_left: usize,
+    pub longest_right: usize,
+}
+
+/// How `replay` divides between the hands under `keymap`. Moves no key makes,
+/// such as clicks past the keymap's 4x4, are left out. `None` with fewer than
+/// two moves left.
+pub fn hand_stats(replay: &[TimedMove], keymap: Keymap) -> Option<HandStats> {
+    let hands: Vec<Hand> = replay
+        .iter()
+        .filter_map(|timed| keymap.hand_for(timed.cell))
+        .collect();
+    if hands.len() < 2 {
+        return None;
+    }
+
+    let switches = hands.windows(2).filter(|pair| pair[0] != pair[1]).count();
+    let (mut longest_left, mut longest_right, mut streak) = (0, 0, 0);
+    for (i, &hand) in hands.iter().enumerate() {
+        streak = if i > 0 && hands[i - 1] == hand {
+            streak + 1
+        } else {
+            1
+        };
+        let longest = match hand {
+            Hand::Left => &mut longest_left,
+            Hand::Right => &mut longest_right,
+        };
+        *longest = (*longest).max(streak);
+    }
+
+    Some(HandStats {
+        alternation: switches as f64 / (hands.len() - 1) as f64,
+        longest_left,
+        longest_right,
+    })
+}
+
+use crate::keymap::{Hand, Keymap};
+use crate::stats::TimedMove;
+
+#[cfg(test)]
+mod tests {
+    fn replay(cells: &[(usize, usize)]) -> Vec<TimedMove> {
+        cells
+            .iter()
+            .zip(1..)
+            .map(|(&cell, i)| TimedMove {
+                at: Duration::from_millis(150 * i),
+                cell,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn alternating() {
+        // The standard keymap's left two columns are under the left hand.
+        let moves = replay(&[(0, 0), (3, 0), (1, 1), (2, 1), (0, 2)]);
+        assert_eq!(
+            hand_stats(&moves, Keymap::Standard),
+            Some(HandStats {
+                alternation: 1.0,
+                longest_left: 1,
+                longest_right: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn one_hand_runs() {
+        let moves = replay(&[(0, 0), (1, 0), (0, 1), (1, 1), (3, 3)]);
+        assert_eq!(
+            hand_stats(&moves, Keymap::Standard),
+            Some(HandStats {
+                alternation: 0.25,
+                longest_left: 4,
+                longest_right: 1,
+            })
+        );
+
+        // Every key of the left-hand keymap is, of course.
+        assert_eq!(
+            hand_stats(&moves, Keymap::LeftHand),
+            Some(HandStats {
+                alternation: 0.0,
+                longest_left: 5,
+                longest_right: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn moves_without_keys() {
+        // Clicks past the keymap's 4x4, as on a bigger board.
+        let clicks = replay(&[(4, 0), (4, 1), (0, 4), (4, 4)]);
+        assert_eq!(hand_stats(&clicks, Keymap::Standard), None);
+
+        let one_key = replay(&[(4, 0), (0, 0), (4, 1)]);
+        assert_eq!(hand_stats(&one_key, Keymap::Standard), None);
+
+        let mixed = replay(&[(4, 0), (0, 0), (4, 1), (3, 0)]);
+        assert_eq!(
+            hand_stats(&mixed, Keymap::Standard).map(|stats| stats.alternation),
+            Some(1.0)
+        );
+        assert_eq!(hand_stats(&[], Keymap::Standard), None);
+    }
+
+    use std::time::Duration;
+
+    use super::*;
+}
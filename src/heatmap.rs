@@ -0,0 +1,79 @@
+/// How often each mapped key is pressed, laid out over the cells the keys
+/// slide from.
+#[component]
+pub fn KeyHeatmap() -> impl IntoView {
+    let KeyUsage(usage) = use_key_usage();
+    let settings = use_settings();
+    let keymap = memo!(|settings| settings.keymap);
+    let layout = use_layout_map();
+    let width = move || {
+        keymap()
+            .entries()
+            .map(|(_, (x, _))| x + 1)
+            .max()
+            .unwrap_or(0)
+    };
+    let height = move || {
+        keymap()
+            .entries()
+            .map(|(_, (_, y))| y + 1)
+            .max()
+            .unwrap_or(0)
+    };
+
+    let cells = move || {
+        usage.with(|usage| {
+            let max = usage.values().copied().max().unwrap_or(0).max(1);
+            let (width, height) = (width(), height());
+            let mut cells = vec![None; width * height];
+            for (key, (x, y)) in keymap().entries() {
+                cells[y * width + x] = Some((key, usage.get(key).copied().unwrap_or(0)));
+            }
+
+            cells
+                .into_iter()
+                .map(|cell| match cell {
+                    Some((key, count)) => view! {
+                        <div
+                            class="w-12 h-12 rounded-md flex flex-col items-center justify-center ring-1 ring-neutral-400/40"
+                            style=("background-color", format!("rgb(139 92 246 / {:.2})", count as f64 / max as f64))
+                        >
+                            <span class="text-base">{layout.with(|layout| display_key(key, layout.as_ref()))}</span>
+                            <span class="opacity-70">{count}</span>
+                        </div>
+                    }
+                    .into_view(),
+                    None => view! { <div /> }.into_view(),
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <div class="flex flex-col gap-2">
+            <div class="flex justify-between items-center">
+                <h2 class="font-bold">"Key usage"</h2>
+                <button
+                    class="px-2 rounded-md opacity-60 hover:opacity-100"
+                    on:click=move |_| usage.update(|usage| usage.clear())
+                >
+                    "Clear"
+                </button>
+            </div>
+            <div
+                class="grid gap-1 self-start font-mono text-xs"
+                style=("grid-template-columns", move || format!("repeat({}, 3rem)", width()))
+            >
+                {cells}
+            </div>
+        </div>
+    }
+}
+
+use leptos::*;
+
+use macros::memo;
+
+use crate::layout_map::{display_key, use_layout_map};
+use crate::settings::use_settings;
+use crate::stats::{use_key_usage, KeyUsage};
@@ -0,0 +1,123 @@
+const BINS: usize = 12;
+
+/// A histogram of solve times for one board size, with median and 90th
+/// percentile markers.
+#[component]
+pub fn TimeHistogram() -> impl IntoView {
+    let solves = use_solves();
+    let session_only = create_rw_signal(false);
+
+    let shapes = move || {
+        solves.with(|solves| {
+            let mut shapes: Vec<(usize, usize)> =
+                solves.iter().map(|solve| solve.scramble.shape).collect();
+            shapes.sort_unstable();
+            shapes.dedup();
+            shapes
+        })
+    };
+    let chosen = create_rw_signal(None::<(usize, usize)>);
+    // Defaults to the most recently solved size.
+    let shape =
+        move || chosen().or_else(|| solves.with(|solves| Some(solves.last()?.scramble.shape)));
+
+    let times = create_memo(move |_| {
+        let shape = shape();
+        let since = match session_only() {
+            true => session_start(),
+            false => f64::NEG_INFINITY,
+        };
+        let mut times: Vec<Duration> = solves.with(|solves| {
+            solves
+                .iter()
+                .filter(|solve| Some(solve.scramble.shape) == shape && solve.finished_at >= since)
+                .filter_map(Solve::result)
+                .collect()
+        });
+        times.sort_unstable();
+        times
+    });
+
+    let histogram = move || {
+        times.with(|times| {
+            let (&min, &max) = (times.first()?, times.last()?);
+            let span = (max - min).as_secs_f64().max(0.001);
+            let bin_of = |time: Duration| {
+                (((time - min).as_secs_f64() / span * BINS as f64) as usize).min(BINS - 1)
+            };
+
+            let mut counts = [0usize; BINS];
+            for &time in times {
+                counts[bin_of(time)] += 1;
+            }
+            let highest = counts.iter().copied().max().unwrap_or(1);
+            let position = |time: Duration| (time - min).as_secs_f64() / span * 100.0;
+            let median = percentile(times, 50.0)?;
+            let p90 = percentile(times, 90.0)?;
+
+            Some(view! {
+                <div class="relative h-32 flex items-end gap-px">
+                    {counts.map(|count| view! {
+                        <div
+                            class="flex-1 bg-violet-500/70 rounded-t-sm"
+                            style=("height", format!("{}%", count * 100 / highest))
+                            title=count
+                        />
+                    }).collect_view()}
+                    <div class="absolute inset-y-0 w-px bg-emerald-500" style=("left", format!("{}%", position(median))) />
+                    <div class="absolute inset-y-0 w-px bg-rose-500" style=("left", format!("{}%", position(p90))) />
+                </div>
+                <div class="flex justify-between font-mono text-xs">
+                    <span>{format_time(min)}</span>
+                    <span class="text-emerald-500">"median "{format_time(median)}</span>
+                    <span class="text-rose-500">"90th "{format_time(p90)}</span>
+                    <span>{format_time(max)}</span>
+                </div>
+            })
+        })
+    };
+
+    view! {
+        <div class="flex flex-col gap-2">
+            <div class="flex items-center gap-3">
+                <h2 class="font-bold">"Times"</h2>
+                <select
+                    class="bg-transparent"
+                    on:change=move |event| chosen.set(parse_shape(&event_target_value(&event)))
+                >
+                    <For
+                        each=shapes
+                        key=|&shape| shape
+                        children=move |shape| {
+                            let key = shape_key(shape);
+                            view! {
+                                <option value=key.clone() selected=move || shape() == Some(shape)>{key}</option>
+                            }
+                        }
+                    />
+                </select>
+                <label class="flex items-center gap-1">
+                    <input
+                        type="checkbox"
+                        prop:checked=session_only
+                        on:change=move |event| session_only.set(event_target_checked(&event))
+                    />
+                    "this session only"
+                </label>
+            </div>
+            {histogram}
+        </div>
+    }
+}
+
+fn parse_shape(key: &str) -> Option<(usize, usize)> {
+    let (width, height) = key.split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+use std::time::Duration;
+
+use leptos::*;
+
+use crate::goals::shape_key;
+use crate::stats::{format_time, percentile, session_start, use_solves, Solve};
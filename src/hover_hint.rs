@@ -0,0 +1,52 @@
+/// Marks where the piece under the mouse belongs, and with
+/// [`HoverHint::Path`] the way there: along its row, then down the column.
+pub fn show_hover_hint(
+    overlays: Overlays,
+    puzzle: RwSignal<AnyPuzzle>,
+    hovered: RwSignal<Option<(usize, usize)>>,
+) {
+    let settings = use_settings();
+    let hint = memo!(|settings| settings.hover_hint);
+
+    // (from, to) for the hovered piece, unless it's the blank or already home.
+    let route = create_memo(move |_| {
+        let from = hovered().filter(|_| hint() != HoverHint::Off)?;
+        with!(|puzzle| {
+            let piece = puzzle.get(from).filter(|&piece| piece != 0)?;
+            Some((from, puzzle.home_of(piece))).filter(|(from, to)| from != to)
+        })
+    });
+
+    overlays.register("hover_hint", move || {
+        let Some(((x, y), to)) = route() else {
+            return Vec::new();
+        };
+        let mut annotations = vec![Annotation::Outline {
+            from: to,
+            to,
+            tone: Tone::Accent,
+        }];
+        if hint() == HoverHint::Path {
+            let corner = (to.0, y);
+            annotations.extend(
+                [((x, y), corner), (corner, to)]
+                    .into_iter()
+                    .filter(|(from, to)| from != to)
+                    .map(|(from, to)| Annotation::Arrow {
+                        from,
+                        to,
+                        tone: Tone::Accent,
+                    }),
+            );
+        }
+        annotations
+    });
+}
+
+use leptos::*;
+
+use macros::memo;
+
+use crate::overlay::{Annotation, Overlays, Tone};
+use crate::puzzle::AnyPuzzle;
+use crate::settings::{use_settings, HoverHint};
@@ -0,0 +1,280 @@
+//! Importing solve histories from other timers.
+//!
+//! slidysim exports its solves as CSV (or TSV) with a header row, quoting
+//! fields as CSV does. Columns are matched by name, case-insensitively, so
+//! reordered or extra columns are fine:
+//!
+//! - `size` as `4x4`, or separate `width` and `height`
+//! - `time`: `12.345` seconds, `1:02.345`, or whole milliseconds
+//! - `moves`
+//! - `timestamp`/`date`: Unix seconds or milliseconds, or anything `Date.parse` reads
+//! - `scramble`/`state` (optional): the starting arrangement
+//!
+//! Rows without a timestamp are rejected, since the history is ordered by it.
+//!
+//! slidysim scrambles are arrangements rather than seeds, so imported solves
+//! keep theirs as text and get a placeholder [`Scramble`] derived from it, or
+//! from the whole row when there's none.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportError {
+    Empty,
+    MissingColumn(&'static str),
+    Row { line: usize, message: String },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Empty => write!(f, "the file has no solves"),
+            ImportError::MissingColumn(column) => write!(f, "no {column:?} column"),
+            ImportError::Row { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+pub fn parse_slidysim(text: &str) -> Result<Vec<Solve>, ImportError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (_, header) = lines.next().ok_or(ImportError::Empty)?;
+    let separator = if header.contains('\t') { '\t' } else { ',' };
+
+    let columns: Vec<String> = split_fields(header, separator)
+        .into_iter()
+        .map(|column| column.to_ascii_lowercase())
+        .collect();
+    let find = |names: &[&str]| {
+        columns
+            .iter()
+            .position(|column| names.contains(&column.as_str()))
+    };
+
+    let size = find(&["size", "puzzle"]);
+    let width = find(&["width"]);
+    let height = find(&["height"]);
+    if size.is_none() && (width.is_none() || height.is_none()) {
+        return Err(ImportError::MissingColumn("size"));
+    }
+    let time = find(&["time", "result"]).ok_or(ImportError::MissingColumn("time"))?;
+    let moves = find(&["moves", "movecount"]);
+    let timestamp =
+        find(&["timestamp", "date", "datetime"]).ok_or(ImportError::MissingColumn("timestamp"))?;
+    let scramble = find(&["scramble", "state"]);
+
+    let mut solves = Vec::new();
+    for (i, line) in lines {
+        let row_error = |message: String| ImportError::Row {
+            line: i + 1,
+            message,
+        };
+        let fields = split_fields(line, separator);
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| fields.get(column))
+                .map(String::as_str)
+                .filter(|field| !field.is_empty())
+        };
+
+        let shape = match (field(size), field(width), field(height)) {
+            (Some(size), _, _) => parse_size(size),
+            (_, Some(width), Some(height)) => width.parse().ok().zip(height.parse().ok()),
+            _ => None,
+        }
+        .ok_or_else(|| row_error("invalid size".to_string()))?;
+        let time = field(Some(time))
+            .and_then(parse_time)
+            .ok_or_else(|| row_error("invalid time".to_string()))?;
+        let moves = field(moves)
+            .and_then(|moves| moves.parse().ok())
+            .unwrap_or(0);
+        let finished_at = field(Some(timestamp))
+            .and_then(parse_timestamp)
+            .ok_or_else(|| row_error("missing or invalid timestamp".to_string()))?;
+        let state = field(scramble);
+
+        solves.push(Solve {
+            id: Solve::new_id(),
+            scramble: Scramble::from_phrase(state.unwrap_or(line), shape),
+            time,
+            moves,
+            finished_at,
+            hand_timed: false,
+            penalty: Penalty::None,
+            replay: Vec::new(),
+            imported_scramble: state.map(str::to_string),
+            daily: None,
+            session_index: None,
+            reaction: None,
+            flags: Vec::new(),
+        });
+    }
+
+    match solves.is_empty() {
+        true => Err(ImportError::Empty),
+        false => Ok(solves),
+    }
+}
+
+/// Splits a line into fields. Separators between double quotes don't split,
+/// and `""` there stands for a quote.
+fn split_fields(line: &str, separator: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => fields.push(mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+        .into_iter()
+        .map(|field| field.trim().to_string())
+        .collect()
+}
+
+fn parse_size(size: &str) -> Option<(usize, usize)> {
+    let (width, height) = size.split_once(['x', 'X'])?;
+    Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+}
+
+fn parse_time(time: &str) -> Option<Duration> {
+    let secs = match time.split_once(':') {
+        Some((minutes, secs)) => minutes.parse::<f64>().ok()? * 60.0 + secs.parse::<f64>().ok()?,
+        None if time.contains('.') => time.parse().ok()?,
+        None => time.parse::<f64>().ok()? / 1000.0,
+    };
+    (secs.is_finite() && secs >= 0.0).then(|| Duration::from_secs_f64(secs))
+}
+
+/// Milliseconds since the Unix epoch.
+fn parse_timestamp(timestamp: &str) -> Option<f64> {
+    match timestamp.parse::<f64>() {
+        Ok(n) if !n.is_finite() || n <= 0.0 => None,
+        // Anything before ~1973 in milliseconds is more likely to be seconds.
+        Ok(n) if n < 1e11 => Some(n * 1000.0),
+        Ok(n) => Some(n),
+        Err(_) => Some(js_sys::Date::parse(timestamp)).filter(|millis| !millis.is_nan()),
+    }
+}
+
+/// Adds `imported` to `solves`, skipping ones already there, and keeps the
+/// history in chronological order. Returns how many were added.
+pub fn merge(solves: &mut Vec<Solve>, imported: Vec<Solve>) -> usize {
+    let before = solves.len();
+    for solve in imported {
+        let duplicate = solves
+            .iter()
+            .any(|other| other.finished_at == solve.finished_at && other.time == solve.time);
+        if !duplicate {
+            solves.push(solve);
+        }
+    }
+    solves.sort_by(|a, b| a.finished_at.total_cmp(&b.finished_at));
+    solves.len() - before
+}
+
+use core::fmt;
+use std::mem;
+use std::time::Duration;
+
+use crate::seed::Scramble;
+use crate::stats::{Penalty, Solve};
+
+#[cfg(test)]
+mod tests {
+    /// Laid out as described above: a quoted header and fields, columns the
+    /// import doesn't use, and a comma inside one of them.
+    const EXPORT: &str = r#""Width","Height","Time","Moves","TPS","Timestamp","Mode","Scramble"
+"4","4","9.871","64","6.484","1697450000","Standard","15 2 1 12/8 5 6 11/4 9 10 7/3 14 13 0"
+"4","4","1:02.345","101","1.620","1697450100","Marathon, 1 of 10","1 2 3 4/5 6 7 8/9 10 11 12/13 15 14 0"
+"3","3","2.500","22","8.800","1697450200123","Standard",""
+"#;
+
+    #[test]
+    fn parses_an_export() {
+        let solves = parse_slidysim(EXPORT).unwrap();
+        assert_eq!(solves.len(), 3);
+
+        assert_eq!(solves[0].scramble.shape, (4, 4));
+        assert_eq!(solves[0].time, Duration::from_millis(9_871));
+        assert_eq!(solves[0].moves, 64);
+        assert_eq!(solves[0].finished_at, 1_697_450_000_000.0);
+        assert_eq!(
+            solves[0].imported_scramble.as_deref(),
+            Some("15 2 1 12/8 5 6 11/4 9 10 7/3 14 13 0")
+        );
+
+        assert_eq!(solves[1].time, Duration::from_millis(62_345));
+        assert_eq!(solves[2].scramble.shape, (3, 3));
+        assert_eq!(solves[2].finished_at, 1_697_450_200_123.0);
+        assert_eq!(solves[2].imported_scramble, None);
+    }
+
+    #[test]
+    fn scrambles_without_a_state_stay_apart() {
+        let text = "size\ttime\ttimestamp\n4x4\t10.000\t1697450000\n4x4\t11.000\t1697450001\n";
+        let solves = parse_slidysim(text).unwrap();
+        assert_ne!(solves[0].scramble, solves[1].scramble);
+        assert_ne!(solves[0].id, solves[1].id);
+    }
+
+    #[test]
+    fn rows_without_a_timestamp_are_rejected() {
+        let text = "size,time,timestamp\n4x4,10.000,1697450000\n4x4,11.000,\n";
+        assert_eq!(
+            parse_slidysim(text),
+            Err(ImportError::Row {
+                line: 3,
+                message: "missing or invalid timestamp".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_slidysim("size,time\n4x4,10.000\n"),
+            Err(ImportError::MissingColumn("timestamp"))
+        );
+    }
+
+    #[test]
+    fn splits_quoted_fields() {
+        assert_eq!(
+            split_fields(r#"a, "b, c" ,"say ""hi""",,"#, ','),
+            ["a", "b, c", r#"say "hi""#, "", ""]
+        );
+        assert_eq!(split_fields("a\t\"b\tc\"", '\t'), ["a", "b\tc"]);
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(parse_time("12.345"), Some(Duration::from_millis(12_345)));
+        assert_eq!(parse_time("1:02.345"), Some(Duration::from_millis(62_345)));
+        assert_eq!(parse_time("12345"), Some(Duration::from_millis(12_345)));
+        assert_eq!(parse_time("-1.0"), None);
+        assert_eq!(parse_time("1:xx"), None);
+        assert_eq!(parse_time("DNF"), None);
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(parse_timestamp("1697450000"), Some(1_697_450_000_000.0));
+        assert_eq!(parse_timestamp("1697450000.5"), Some(1_697_450_000_500.0));
+        assert_eq!(parse_timestamp("1697450000123"), Some(1_697_450_000_123.0));
+        assert_eq!(parse_timestamp("0"), None);
+        assert_eq!(parse_timestamp("-5"), None);
+    }
+
+    use super::*;
+}
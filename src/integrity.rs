@@ -0,0 +1,367 @@
+//! Heuristics for results that don't look like they came from a person at a
+//! keyboard, judged from the recorded moves. They only flag a solve for a
+//! closer look; none of them is proof on its own.
+
+/// Sustained above this over a whole solve is beyond any recorded human.
+const MAX_TPS: f64 = 25.0;
+/// People hesitate; gaps this uniform suggest a script replaying moves.
+const MIN_GAP_VARIATION: f64 = 0.08;
+/// Fewer moves than this are over too quickly to time a rate from.
+const TPS_MIN_MOVES: usize = 10;
+/// Fewer moves than this don't say much about rhythm.
+const RHYTHM_MIN_MOVES: usize = 20;
+/// Moves closer together than this are practically simultaneous.
+const BURST_GAP: Duration = Duration::from_millis(8);
+/// This many such gaps in a row is a burst no keyboard produces.
+const BURST_LENGTH: usize = 5;
+/// The solver only double-checks boards this small, where it's quick.
+const SOLVER_MAX_CELLS: usize = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Flag {
+    SuperhumanTps,
+    UniformRhythm,
+    ZeroDelayBurst,
+    SolverIdentical,
+}
+
+impl Flag {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Flag::SuperhumanTps => "turns per second beyond any human",
+            Flag::UniformRhythm => "moves evenly spaced like a script",
+            Flag::ZeroDelayBurst => "a burst of moves with no time between them",
+            Flag::SolverIdentical => "a move-optimal solution, as from a solver",
+        }
+    }
+}
+
+/// What replaying a shared solve against its scramble showed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verification {
+    /// Every move is legal, the last one solves it, and nothing looks off.
+    Verified,
+    /// It's a real solve, but [`check_moves`] flagged it.
+    Suspicious(Vec<Flag>),
+    /// The moves don't solve the scramble, for this reason.
+    Invalid(String),
+}
+
+/// Everything suspicious about `solve`; empty for ordinary solves and for
+/// ones recorded without their moves. Kept in [`Solve::flags`] when the solve
+/// is recorded, since the solver can take a while.
+pub fn check(solve: &Solve) -> Vec<Flag> {
+    // An imported solve's scramble is only a placeholder.
+    let scramble = solve.imported_scramble.is_none().then_some(solve.scramble);
+    check_moves(scramble, &solve.replay)
+}
+
+/// Replays `file` from its seed, move by move, and checks what it finds.
+pub fn verify(file: &ReplayFile) -> Verification {
+    let mut puzzle = file.scramble.puzzle();
+    let mut previous = Duration::ZERO;
+    for (index, timed) in file.moves.iter().enumerate() {
+        if timed.at < previous {
+            return Verification::Invalid(format!("move {} is out of order", index + 1));
+        }
+        if puzzle.is_solved() {
+            return Verification::Invalid(format!("it was solved before move {}", index + 1));
+        }
+        if !matches!(puzzle.slide_from(timed.cell), Some(1..)) {
+            return Verification::Invalid(format!("move {} isn't a legal slide", index + 1));
+        }
+        previous = timed.at;
+    }
+    if !puzzle.is_solved() {
+        return Verification::Invalid("the moves don't solve it".to_string());
+    }
+
+    match check_moves(Some(file.scramble), &file.moves) {
+        flags if flags.is_empty() => Verification::Verified,
+        flags => Verification::Suspicious(flags),
+    }
+}
+
+/// Everything suspicious about `replay`, a solve of `scramble` if that's
+/// known.
+pub fn check_moves(scramble: Option<Scramble>, replay: &[TimedMove]) -> Vec<Flag> {
+    let mut flags = Vec::new();
+    if replay.len() < 2 {
+        return flags;
+    }
+
+    let gaps: Vec<Duration> = replay
+        .windows(2)
+        .map(|pair| pair[1].at.saturating_sub(pair[0].at))
+        .collect();
+
+    let span = replay[replay.len() - 1].at.saturating_sub(replay[0].at);
+    if gaps.len() >= TPS_MIN_MOVES
+        && span.as_secs_f64() > 0.0
+        && gaps.len() as f64 / span.as_secs_f64() > MAX_TPS
+    {
+        flags.push(Flag::SuperhumanTps);
+    }
+
+    if gaps.len() >= RHYTHM_MIN_MOVES {
+        let millis: Vec<f64> = gaps.iter().map(|gap| gap.as_secs_f64() * 1000.0).collect();
+        let mean = millis.iter().sum::<f64>() / millis.len() as f64;
+        let variance =
+            millis.iter().map(|gap| (gap - mean).powi(2)).sum::<f64>() / millis.len() as f64;
+        if mean > 0.0 && variance.sqrt() / mean < MIN_GAP_VARIATION {
+            flags.push(Flag::UniformRhythm);
+        }
+    }
+
+    let longest_burst = gaps
+        .iter()
+        .scan(0, |run, &gap| {
+            *run = if gap < BURST_GAP { *run + 1 } else { 0 };
+            Some(*run)
+        })
+        .max()
+        .unwrap_or(0);
+    if longest_burst >= BURST_LENGTH {
+        flags.push(Flag::ZeroDelayBurst);
+    }
+
+    if scramble.is_some_and(|scramble| is_move_optimal(scramble, replay)) {
+        flags.push(Flag::SolverIdentical);
+    }
+
+    flags
+}
+
+/// Whether the solve took exactly as many single-tile moves as the shortest
+/// solution, which people practically never manage past the smallest boards.
+fn is_move_optimal(scramble: Scramble, replay: &[TimedMove]) -> bool {
+    let (width, height) = scramble.shape;
+    if width * height > SOLVER_MAX_CELLS {
+        return false;
+    }
+
+    let mut puzzle = scramble.puzzle();
+    let evaluation = solver::evaluate(
+        &puzzle.pieces(),
+        (width, height),
+        scramble.goal,
+        solver::DEFAULT_BUDGET,
+    );
+    let tiles_moved: usize = replay
+        .iter()
+        .map(|timed| puzzle.slide_from(timed.cell).unwrap_or(0))
+        .sum();
+
+    // Trivial scrambles are solved optimally by anyone.
+    evaluation.exact && evaluation.distance > width * height && tiles_moved == evaluation.distance
+}
+
+/// How far a shared replay can be trusted, from [`verify`].
+#[component]
+pub fn VerificationBadge(verification: Verification) -> impl IntoView {
+    let (class, label, detail) = match verification {
+        Verification::Verified => (
+            "bg-emerald-500/20 text-emerald-600",
+            "✓ Verified",
+            "Every move replays legally from the seed and solves it".to_string(),
+        ),
+        Verification::Suspicious(flags) => (
+            "bg-amber-400/20 text-amber-600",
+            "⚠ Suspicious",
+            flags
+                .into_iter()
+                .map(Flag::describe)
+                .collect::<Vec<_>>()
+                .join("; "),
+        ),
+        Verification::Invalid(reason) => ("bg-rose-500/20 text-rose-600", "✕ Invalid", reason),
+    };
+    view! {
+        <span class=classes!("px-2 py-0.5 rounded-full text-xs font-bold cursor-help", class) title=detail>
+            {label}
+        </span>
+    }
+}
+
+/// A warning sign listing what [`check`] found when `solve` was recorded, or
+/// nothing.
+pub fn flag_mark(solve: &Solve) -> impl IntoView {
+    (!solve.flags.is_empty()).then(|| {
+        let reasons = solve
+            .flags
+            .iter()
+            .copied()
+            .map(Flag::describe)
+            .collect::<Vec<_>>()
+            .join("; ");
+        view! {
+            <span class="mr-1 text-amber-500 cursor-help" title=format!("Suspicious: {reasons}")>
+                "⚠"
+            </span>
+        }
+    })
+}
+
+use core::time::Duration;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use macros::classes;
+
+use crate::replay_file::ReplayFile;
+use crate::seed::Scramble;
+use crate::solver;
+use crate::stats::{Solve, TimedMove};
+
+#[cfg(test)]
+mod tests {
+    /// Moves `gaps` milliseconds apart, on cells that don't matter without a
+    /// scramble.
+    fn replay(gaps: impl IntoIterator<Item = u64>) -> Vec<TimedMove> {
+        let mut at = 1_000;
+        let mut moves = vec![TimedMove {
+            at: Duration::from_millis(at),
+            cell: (0, 0),
+        }];
+        for gap in gaps {
+            at += gap;
+            moves.push(TimedMove {
+                at: Duration::from_millis(at),
+                cell: (0, 0),
+            });
+        }
+        moves
+    }
+
+    /// `count` gaps alternating between `a` and `b` milliseconds.
+    fn alternating(a: u64, b: u64, count: usize) -> Vec<TimedMove> {
+        replay((0..count).map(|i| if i % 2 == 0 { a } else { b }))
+    }
+
+    fn flagged(replay: &[TimedMove], flag: Flag) -> bool {
+        check_moves(None, replay).contains(&flag)
+    }
+
+    #[test]
+    fn ordinary_solves_pass() {
+        let gaps = [180, 95, 240, 130, 410, 88, 150, 260, 120, 330, 97, 205];
+        assert_eq!(check_moves(None, &replay(gaps.repeat(3))), []);
+        assert_eq!(check_moves(None, &replay([])), []);
+    }
+
+    #[test]
+    fn tps() {
+        // 1000 / 38 ≈ 26.3 and 1000 / 42 ≈ 23.8 moves a second
+        assert!(flagged(&alternating(35, 41, 40), Flag::SuperhumanTps));
+        assert!(!flagged(&alternating(40, 44, 40), Flag::SuperhumanTps));
+        // too few moves to tell
+        let few = alternating(10, 10, TPS_MIN_MOVES - 1);
+        assert!(!flagged(&few, Flag::SuperhumanTps));
+        let enough = alternating(10, 10, TPS_MIN_MOVES);
+        assert!(flagged(&enough, Flag::SuperhumanTps));
+    }
+
+    #[test]
+    fn rhythm() {
+        // a spread of 4% and 10% of the mean gap
+        assert!(flagged(&alternating(96, 104, 40), Flag::UniformRhythm));
+        assert!(!flagged(&alternating(90, 110, 40), Flag::UniformRhythm));
+        // too few moves to tell
+        let few = alternating(100, 100, RHYTHM_MIN_MOVES - 1);
+        assert!(!flagged(&few, Flag::UniformRhythm));
+        let enough = alternating(100, 100, RHYTHM_MIN_MOVES);
+        assert!(flagged(&enough, Flag::UniformRhythm));
+    }
+
+    #[test]
+    fn bursts() {
+        let burst = |gap: u64, length: usize| {
+            let mut gaps = vec![150, 220];
+            gaps.extend(std::iter::repeat(gap).take(length));
+            gaps.extend([310, 140]);
+            replay(gaps)
+        };
+        let just_under = BURST_GAP.as_millis() as u64 - 1;
+        assert!(flagged(&burst(0, BURST_LENGTH), Flag::ZeroDelayBurst));
+        assert!(flagged(
+            &burst(just_under, BURST_LENGTH),
+            Flag::ZeroDelayBurst
+        ));
+        assert!(!flagged(&burst(0, BURST_LENGTH - 1), Flag::ZeroDelayBurst));
+        assert!(!flagged(
+            &burst(just_under + 1, BURST_LENGTH),
+            Flag::ZeroDelayBurst
+        ));
+    }
+
+    /// A solve of a 4x4 scramble along the solver's line, at a human pace.
+    /// The board is too big for the solver check, so nothing's flagged.
+    fn real_solve() -> ReplayFile {
+        let scramble = Scramble {
+            mode: ScrambleMode::RandomMoves { length: 20 },
+            ..Scramble::new((4, 4), [3; 32])
+        };
+        let evaluation = solver::evaluate(
+            &scramble.puzzle().pieces(),
+            scramble.shape,
+            scramble.goal,
+            solver::DEFAULT_BUDGET,
+        );
+        assert!(evaluation.exact && evaluation.distance > 0);
+
+        let gaps = [180, 95, 240, 130, 410, 88, 150, 260, 120, 330, 97, 205];
+        let mut at = Duration::ZERO;
+        let moves = evaluation
+            .solution
+            .into_iter()
+            .zip(gaps.into_iter().cycle())
+            .map(|(cell, gap)| {
+                at += Duration::from_millis(gap);
+                TimedMove { at, cell }
+            })
+            .collect();
+        ReplayFile::new(scramble, moves)
+    }
+
+    fn invalid(reason: &str) -> Verification {
+        Verification::Invalid(reason.to_string())
+    }
+
+    #[test]
+    fn verify_real_solve() {
+        assert_eq!(verify(&real_solve()), Verification::Verified);
+    }
+
+    #[test]
+    fn verify_broken_solves() {
+        // Where the blank is, which isn't a slide.
+        let mut file = real_solve();
+        file.moves[3].cell = file.moves[2].cell;
+        assert_eq!(verify(&file), invalid("move 4 isn't a legal slide"));
+
+        let mut file = real_solve();
+        file.moves[3].at = file.moves[2].at - Duration::from_millis(1);
+        assert_eq!(verify(&file), invalid("move 4 is out of order"));
+
+        let mut file = real_solve();
+        let last = file.moves[file.moves.len() - 1];
+        file.moves.push(TimedMove {
+            at: last.at + Duration::from_millis(200),
+            ..file.moves[file.moves.len() - 2]
+        });
+        let extra = file.moves.len();
+        assert_eq!(
+            verify(&file),
+            invalid(&format!("it was solved before move {extra}"))
+        );
+
+        let mut file = real_solve();
+        file.moves.pop();
+        assert_eq!(verify(&file), invalid("the moves don't solve it"));
+    }
+
+    use crate::seed::ScrambleMode;
+
+    use super::*;
+}
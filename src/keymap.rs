@@ -0,0 +1,302 @@
+#[rustfmt::skip]
+static STANDARD: phf::Map<&'static str, (usize, usize)> = phf::phf_map! {
+    "4" => (0, 0), "5" => (1, 0), "6" => (2, 0), "7" => (3, 0),
+    "r" => (0, 1), "t" => (1, 1), "y" => (2, 1), "u" => (3, 1),
+    "f" => (0, 2), "g" => (1, 2), "h" => (2, 2), "j" => (3, 2),
+    "v" => (0, 3), "b" => (1, 3), "n" => (2, 3), "m" => (3, 3),
+
+    // "R" => (0, 1), "T" => (1, 1), "Y" => (2, 1), "U" => (3, 1),
+    // "F" => (0, 2), "G" => (1, 2), "H" => (2, 2), "J" => (3, 2),
+    // "V" => (0, 3), "B" => (1, 3), "N" => (2, 3), "M" => (3, 3),
+};
+
+#[rustfmt::skip]
+static LEFT_HAND: phf::Map<&'static str, (usize, usize)> = phf::phf_map! {
+    "1" => (0, 0), "2" => (1, 0), "3" => (2, 0), "4" => (3, 0),
+    "q" => (0, 1), "w" => (1, 1), "e" => (2, 1), "r" => (3, 1),
+    "a" => (0, 2), "s" => (1, 2), "d" => (2, 2), "f" => (3, 2),
+    "z" => (0, 3), "x" => (1, 3), "c" => (2, 3), "v" => (3, 3),
+};
+
+#[rustfmt::skip]
+static RIGHT_HAND: phf::Map<&'static str, (usize, usize)> = phf::phf_map! {
+    "7" => (0, 0), "8" => (1, 0), "9" => (2, 0), "0" => (3, 0),
+    "u" => (0, 1), "i" => (1, 1), "o" => (2, 1), "p" => (3, 1),
+    "j" => (0, 2), "k" => (1, 2), "l" => (2, 2), ";" => (3, 2),
+    "m" => (0, 3), "," => (1, 3), "." => (2, 3), "/" => (3, 3),
+};
+
+/// Which keys slide which cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Keymap {
+    #[default]
+    Standard,
+    /// The standard keys with the columns swapped left to right, for players
+    /// who'd rather lead with the other hand.
+    Mirrored,
+    /// Everything under the left hand, leaving the right one on the mouse.
+    LeftHand,
+    RightHand,
+}
+
+impl Keymap {
+    fn map(self) -> &'static phf::Map<&'static str, (usize, usize)> {
+        match self {
+            Keymap::Standard | Keymap::Mirrored => &STANDARD,
+            Keymap::LeftHand => &LEFT_HAND,
+            Keymap::RightHand => &RIGHT_HAND,
+        }
+    }
+
+    #[inline]
+    fn adjust(self, (x, y): (usize, usize)) -> (usize, usize) {
+        match self {
+            Keymap::Mirrored => (3 - x, y),
+            _ => (x, y),
+        }
+    }
+
+    pub fn lookup(self, key: &str) -> Option<(usize, usize)> {
+        self.map().get(key).map(|&idx| self.adjust(idx))
+    }
+
+    /// Every mapped key and the cell it slides from.
+    pub fn entries(self) -> impl Iterator<Item = (&'static str, (usize, usize))> {
+        self.map()
+            .entries()
+            .map(move |(&key, &idx)| (key, self.adjust(idx)))
+    }
+
+    /// The hand on the key that slides `cell`, if a key does.
+    pub fn hand_for(self, cell: (usize, usize)) -> Option<Hand> {
+        self.entries()
+            .find(|&(_, idx)| idx == cell)
+            .and_then(|(key, _)| Hand::of_key(key))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+impl Hand {
+    /// The hand that types `key` when touch typing on QWERTY.
+    pub fn of_key(key: &str) -> Option<Self> {
+        let mut chars = key.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+        match c.to_ascii_lowercase() {
+            '1'..='5'
+            | 'q'
+            | 'w'
+            | 'e'
+            | 'r'
+            | 't'
+            | 'a'
+            | 's'
+            | 'd'
+            | 'f'
+            | 'g'
+            | 'z'
+            | 'x'
+            | 'c'
+            | 'v'
+            | 'b' => Some(Hand::Left),
+            '6'..='9'
+            | '0'
+            | 'y'
+            | 'u'
+            | 'i'
+            | 'o'
+            | 'p'
+            | 'h'
+            | 'j'
+            | 'k'
+            | 'l'
+            | 'n'
+            | 'm'
+            | ';'
+            | ','
+            | '.'
+            | '/'
+            | '-'
+            | '='
+            | '['
+            | ']'
+            | '\'' => Some(Hand::Right),
+            _ => None,
+        }
+    }
+}
+
+#[inline]
+pub fn lookup(key: &str) -> Option<(usize, usize)> {
+    Keymap::default().lookup(key)
+}
+
+/// Something a key does besides sliding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    NewScramble,
+    RetryScramble,
+    NextBoard,
+    ToggleDevMode,
+    ForceReset,
+    ForceStart,
+    ForceFinish,
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::NewScramble,
+        Action::RetryScramble,
+        Action::NextBoard,
+        Action::ToggleDevMode,
+        Action::ForceReset,
+        Action::ForceStart,
+        Action::ForceFinish,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::NewScramble => "New scramble",
+            Action::RetryScramble => "Retry scramble",
+            Action::NextBoard => "Next board",
+            Action::ToggleDevMode => "Dev mode",
+            Action::ForceReset => "Force reset",
+            Action::ForceStart => "Force start",
+            Action::ForceFinish => "Force finish",
+        }
+    }
+
+    /// Only does anything once dev controls are unlocked.
+    pub fn is_dev(self) -> bool {
+        matches!(
+            self,
+            Action::ToggleDevMode | Action::ForceReset | Action::ForceStart | Action::ForceFinish
+        )
+    }
+
+    pub fn default_chord(self) -> Chord {
+        Chord(
+            match self {
+                Action::NewScramble => "Space",
+                Action::RetryScramble => "Ctrl+R",
+                Action::NextBoard => "Tab",
+                Action::ToggleDevMode => "Shift+D",
+                Action::ForceReset => "1",
+                Action::ForceStart => "2",
+                Action::ForceFinish => "3",
+            }
+            .to_owned(),
+        )
+    }
+}
+
+/// A key and the modifiers held with it, written like `Ctrl+R` or `Space`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Chord(String);
+
+impl Chord {
+    /// `None` while only modifiers are down.
+    pub fn from_event(event: &KeyboardEvent) -> Option<Self> {
+        let key = event.key();
+        if matches!(key.as_str(), "Control" | "Alt" | "Shift" | "Meta") {
+            return None;
+        }
+
+        let mut chord = String::new();
+        for (held, name) in [
+            (event.ctrl_key(), "Ctrl+"),
+            (event.alt_key(), "Alt+"),
+            (event.shift_key(), "Shift+"),
+            (event.meta_key(), "Meta+"),
+        ] {
+            if held {
+                chord.push_str(name);
+            }
+        }
+        match key.as_str() {
+            " " => chord.push_str("Space"),
+            key if key.chars().count() == 1 => chord.push_str(&key.to_uppercase()),
+            key => chord.push_str(key),
+        }
+
+        Some(Self(chord))
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The chords bound to each [`Action`]; anything not listed keeps its default.
+/// A chord bound here wins over a slide key that types the same thing.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Shortcuts(BTreeMap<Action, Chord>);
+
+impl Shortcuts {
+    pub fn chord(&self, action: Action) -> Chord {
+        self.0
+            .get(&action)
+            .cloned()
+            .unwrap_or_else(|| action.default_chord())
+    }
+
+    pub fn action_for(&self, chord: &Chord) -> Option<Action> {
+        Action::ALL
+            .into_iter()
+            .find(|&action| self.chord(action) == *chord)
+    }
+
+    pub fn bind(&mut self, action: Action, chord: Chord) {
+        self.0.insert(action, chord);
+    }
+}
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use web_sys::KeyboardEvent;
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn hand_of_key() {
+        assert_eq!(Hand::of_key("r"), Some(Hand::Left));
+        assert_eq!(Hand::of_key("R"), Some(Hand::Left));
+        assert_eq!(Hand::of_key("5"), Some(Hand::Left));
+        assert_eq!(Hand::of_key("6"), Some(Hand::Right));
+        assert_eq!(Hand::of_key(";"), Some(Hand::Right));
+        assert_eq!(Hand::of_key(""), None);
+        assert_eq!(Hand::of_key("rt"), None);
+        assert_eq!(Hand::of_key("ArrowUp"), None);
+        assert_eq!(Hand::of_key("é"), None);
+    }
+
+    #[test]
+    fn hand_for() {
+        assert_eq!(Keymap::Standard.hand_for((0, 0)), Some(Hand::Left));
+        assert_eq!(Keymap::Standard.hand_for((3, 3)), Some(Hand::Right));
+        // Mirrored, the right hand takes the left columns.
+        assert_eq!(Keymap::Mirrored.hand_for((0, 0)), Some(Hand::Right));
+        assert_eq!(Keymap::Mirrored.hand_for((3, 3)), Some(Hand::Left));
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(Keymap::LeftHand.hand_for((x, y)), Some(Hand::Left));
+                assert_eq!(Keymap::RightHand.hand_for((x, y)), Some(Hand::Right));
+            }
+        }
+        assert_eq!(Keymap::Standard.hand_for((4, 0)), None);
+    }
+
+    use super::*;
+}
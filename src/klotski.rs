@@ -0,0 +1,548 @@
+//! Klotski: blocks of different sizes sliding around a box, a cell at a time,
+//! until one of them reaches its goal. A game of its own beside the numbered
+//! board; nothing here is recorded as a solve.
+//!
+//! Layouts are written as a grid with a character per cell: `.` for an empty
+//! cell, and the same letter across every cell of a block, which has to be a
+//! rectangle. A last line `goal <letter> <x>,<y>` names the block to free and
+//! where its top-left corner has to end up.
+
+const CELL_REM: f64 = 4.0;
+const GAP_REM: f64 = 0.5;
+
+const PRESETS: &[(&str, &str)] = &[
+    ("Warm-up", "ABBC\nABBC\nD..E\nFGHI\ngoal B 1,2"),
+    ("Huarong Dao", "ABBC\nABBC\nDEEF\nDGHF\nI..J\ngoal B 1,3"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "ArrowUp" | "w" => Some(Direction::Up),
+            "ArrowDown" | "s" => Some(Direction::Down),
+            "ArrowLeft" | "a" => Some(Direction::Left),
+            "ArrowRight" | "d" => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Block {
+    pub name: char,
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Block {
+    fn contains(&self, (x, y): (usize, usize)) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+
+    /// This block one cell over, if that's still on a board of `shape`.
+    fn moved(&self, direction: Direction, (width, height): (usize, usize)) -> Option<Self> {
+        let (x, y) = match direction {
+            Direction::Up => (self.x, self.y.checked_sub(1)?),
+            Direction::Down => (self.x, self.y + 1),
+            Direction::Left => (self.x.checked_sub(1)?, self.y),
+            Direction::Right => (self.x + 1, self.y),
+        };
+        (x + self.width <= width && y + self.height <= height).then_some(Self { x, y, ..*self })
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.y..self.y + self.height)
+            .flat_map(move |y| (self.x..self.x + self.width).map(move |x| (x, y)))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutError {
+    Empty,
+    /// Row `n`, from zero, isn't as wide as the first.
+    Ragged(usize),
+    NotRectangle(char),
+    MissingGoal,
+    UnknownBlock(char),
+    GoalOutside,
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::Empty => write!(f, "the layout has no rows"),
+            LayoutError::Ragged(row) => {
+                write!(f, "row {} isn't as wide as the first", row + 1)
+            }
+            LayoutError::NotRectangle(name) => write!(f, "block {name} isn't a rectangle"),
+            LayoutError::MissingGoal => {
+                write!(f, "the last line should read `goal <letter> <x>,<y>`")
+            }
+            LayoutError::UnknownBlock(name) => write!(f, "there's no block {name}"),
+            LayoutError::GoalOutside => write!(f, "the goal puts the block off the board"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub shape: (usize, usize),
+    pub blocks: Vec<Block>,
+    /// Which of `blocks` has to reach `goal`.
+    pub target: usize,
+    pub goal: (usize, usize),
+}
+
+impl Layout {
+    pub fn parse(text: &str) -> Result<Self, LayoutError> {
+        let mut lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let goal_line = lines.pop().ok_or(LayoutError::Empty)?;
+        let rows: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+        let width = rows.first().ok_or(LayoutError::Empty)?.len();
+        if let Some(row) = rows.iter().position(|row| row.len() != width) {
+            return Err(LayoutError::Ragged(row));
+        }
+        let shape = (width, rows.len());
+
+        // In order of first appearance, reading row by row.
+        let mut blocks: Vec<Block> = Vec::new();
+        let mut counts: Vec<usize> = Vec::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &name) in row.iter().enumerate().filter(|&(_, &name)| name != '.') {
+                match blocks.iter().position(|block| block.name == name) {
+                    Some(i) => {
+                        let block = &mut blocks[i];
+                        let (right, bottom) = (
+                            (block.x + block.width).max(x + 1),
+                            (block.y + block.height).max(y + 1),
+                        );
+                        block.x = block.x.min(x);
+                        block.width = right - block.x;
+                        block.height = bottom - block.y;
+                        counts[i] += 1;
+                    }
+                    None => {
+                        blocks.push(Block {
+                            name,
+                            x,
+                            y,
+                            width: 1,
+                            height: 1,
+                        });
+                        counts.push(1);
+                    }
+                }
+            }
+        }
+        for (block, &count) in blocks.iter().zip(&counts) {
+            let filled = block.cells().all(|(x, y)| rows[y][x] == block.name);
+            if !filled || count != block.width * block.height {
+                return Err(LayoutError::NotRectangle(block.name));
+            }
+        }
+
+        let (target, goal) = parse_goal(goal_line).ok_or(LayoutError::MissingGoal)?;
+        let target = blocks
+            .iter()
+            .position(|block| block.name == target)
+            .ok_or(LayoutError::UnknownBlock(target))?;
+        let block = blocks[target];
+        if goal.0 + block.width > shape.0 || goal.1 + block.height > shape.1 {
+            return Err(LayoutError::GoalOutside);
+        }
+
+        Ok(Self {
+            shape,
+            blocks,
+            target,
+            goal,
+        })
+    }
+
+    pub fn block_at(&self, cell: (usize, usize)) -> Option<usize> {
+        self.blocks.iter().position(|block| block.contains(cell))
+    }
+
+    /// Where block `index` would go one cell in `direction`, if nothing's in
+    /// the way.
+    fn step(&self, index: usize, direction: Direction) -> Option<Block> {
+        let moved = self.blocks[index].moved(direction, self.shape)?;
+        let clear = moved
+            .cells()
+            .all(|cell| self.block_at(cell).map_or(true, |other| other == index));
+        clear.then_some(moved)
+    }
+
+    /// Moves block `index` one cell in `direction`, returning whether it could.
+    pub fn slide(&mut self, index: usize, direction: Direction) -> bool {
+        match self.step(index, direction) {
+            Some(moved) => {
+                self.blocks[index] = moved;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The way block `index` would go to cover `cell`, if one step does it.
+    fn direction_to(&self, index: usize, cell: (usize, usize)) -> Option<Direction> {
+        Direction::ALL.into_iter().find(|&direction| {
+            self.step(index, direction)
+                .is_some_and(|moved| moved.contains(cell))
+        })
+    }
+
+    pub fn is_solved(&self) -> bool {
+        let block = self.blocks[self.target];
+        (block.x, block.y) == self.goal
+    }
+}
+
+/// `goal B 1,3`
+fn parse_goal(line: &str) -> Option<(char, (usize, usize))> {
+    let mut words = line.split_whitespace();
+    if words.next()? != "goal" {
+        return None;
+    }
+    let mut name = words.next()?.chars();
+    let target = name.next().filter(|_| name.next().is_none())?;
+    let (x, y) = words.next()?.split_once(',')?;
+    let goal = (x.trim().parse().ok()?, y.trim().parse().ok()?);
+    words.next().is_none().then_some((target, goal))
+}
+
+#[component]
+pub fn Klotski() -> impl IntoView {
+    let source = create_rw_signal(PRESETS[0].1.to_owned());
+    let parsed = create_memo(move |_| source.with(|source| Layout::parse(source)));
+    let layout = create_rw_signal(None::<Layout>);
+    let selected = create_rw_signal(None::<usize>);
+    let moves = create_rw_signal(0usize);
+    let editing = create_rw_signal(false);
+
+    let restart = move || {
+        layout.set(parsed.get_untracked().ok());
+        selected.set(None);
+        moves.set(0);
+    };
+    create_effect(move |_| {
+        parsed.track();
+        restart();
+    });
+
+    let solved = move || layout.with(|layout| layout.as_ref().is_some_and(Layout::is_solved));
+    let slide = move |index: usize, direction: Direction| {
+        if solved() {
+            return;
+        }
+        let moved =
+            layout.update_if_some(|layout| layout.as_mut()?.slide(index, direction).then_some(()));
+        if moved.is_some() {
+            moves.update(|moves| *moves += 1);
+        }
+    };
+
+    // Clicking a block selects it; clicking a free cell next to the selected
+    // block moves it there.
+    let on_cell = move |cell: (usize, usize)| {
+        let (block, step) = layout
+            .with_untracked(|layout| {
+                let layout = layout.as_ref()?;
+                let step = selected
+                    .get_untracked()
+                    .and_then(|index| Some((index, layout.direction_to(index, cell)?)));
+                Some((layout.block_at(cell), step))
+            })
+            .unwrap_or_default();
+        match (block, step) {
+            (Some(block), _) => selected.set(Some(block)),
+            (None, Some((index, direction))) => slide(index, direction),
+            (None, None) => {}
+        }
+    };
+    let position = move |index: usize| {
+        layout.with(|layout| {
+            let block = layout.as_ref()?.blocks.get(index)?;
+            Some((block.x, block.y))
+        })
+    };
+    // Everything but where the blocks are, so moves don't redraw the board.
+    let frame = create_memo(move |_| {
+        layout.with(|layout| {
+            layout.as_ref().map(|layout| Layout {
+                blocks: layout
+                    .blocks
+                    .iter()
+                    .map(|block| Block {
+                        x: 0,
+                        y: 0,
+                        ..*block
+                    })
+                    .collect(),
+                ..layout.clone()
+            })
+        })
+    });
+
+    let keydown_handle = window_event_listener(keydown, move |event| {
+        let target = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlElement>().ok());
+        if target.is_some_and(|target| {
+            matches!(
+                target.tag_name().as_str(),
+                "INPUT" | "SELECT" | "TEXTAREA" | "BUTTON"
+            )
+        }) {
+            return;
+        }
+        if let (Some(index), Some(direction)) =
+            (selected.get_untracked(), Direction::from_key(&event.key()))
+        {
+            event.prevent_default();
+            slide(index, direction);
+        }
+    });
+    on_cleanup(move || keydown_handle.remove());
+
+    let board = move || {
+        let layout = frame.get()?;
+        let (width, height) = layout.shape;
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| view! {
+                <div
+                    class="absolute w-[var(--cell)] h-[var(--cell)] rounded-lg bg-neutral-200/40 dark:bg-neutral-700/40
+                           translate-x-[calc(var(--x)*var(--pitch))] translate-y-[calc(var(--y)*var(--pitch))]"
+                    style=("--x", x)
+                    style=("--y", y)
+                    on:click=move |_| on_cell((x, y))
+                />
+            })
+            .collect_view();
+        let goal = layout.blocks[layout.target];
+        let blocks = layout
+            .blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| view! {
+                <div
+                    class=move || classes!(
+                        "absolute flex items-center justify-center rounded-lg text-2xl font-bold pointer-events-none
+                        transition-transform ease-out-circ duration-100
+                        w-[calc(var(--w)*var(--pitch)_-_var(--gap))] h-[calc(var(--h)*var(--pitch)_-_var(--gap))]
+                        translate-x-[calc(var(--x)*var(--pitch))] translate-y-[calc(var(--y)*var(--pitch))]",
+                        index == layout.target => "bg-violet-500 text-white" else "bg-neutral-300 dark:bg-neutral-600",
+                        selected() == Some(index) => "ring-4 ring-amber-400",
+                    )
+                    style=("--x", move || position(index).map(|(x, _)| x))
+                    style=("--y", move || position(index).map(|(_, y)| y))
+                    style=("--w", block.width)
+                    style=("--h", block.height)
+                >
+                    {block.name}
+                </div>
+            })
+            .collect_view();
+
+        Some(view! {
+            <div
+                class="relative w-[calc(var(--cols)*var(--pitch)_-_var(--gap))] h-[calc(var(--rows)*var(--pitch)_-_var(--gap))]"
+                style=("--cell", format!("{CELL_REM}rem"))
+                style=("--gap", format!("{GAP_REM}rem"))
+                style=("--pitch", "calc(var(--cell) + var(--gap))")
+                style=("--cols", width)
+                style=("--rows", height)
+            >
+                {cells}
+                <div
+                    class="absolute rounded-lg outline-2 outline-dashed outline-violet-400 pointer-events-none
+                           w-[calc(var(--w)*var(--pitch)_-_var(--gap))] h-[calc(var(--h)*var(--pitch)_-_var(--gap))]
+                           translate-x-[calc(var(--x)*var(--pitch))] translate-y-[calc(var(--y)*var(--pitch))]"
+                    style=("--x", layout.goal.0)
+                    style=("--y", layout.goal.1)
+                    style=("--w", goal.width)
+                    style=("--h", goal.height)
+                />
+                {blocks}
+            </div>
+        })
+    };
+
+    view! {
+        <div class="flex flex-col items-center my-auto gap-4">
+            <div class="h-16 flex items-center text-3xl font-bold">
+                {move || match solved() {
+                    true => format!("Freed in {} moves!", moves()),
+                    false => format!("{} moves", moves()),
+                }}
+            </div>
+            <div class="flex gap-2 text-sm">
+                {PRESETS
+                    .iter()
+                    .map(|&(name, preset)| view! {
+                        <button
+                            class="px-3 py-1 rounded-md ring-1 ring-neutral-400 dark:ring-neutral-600"
+                            on:click=move |_| {
+                                source.set(preset.to_owned());
+                                editing.set(false);
+                            }
+                        >
+                            {name}
+                        </button>
+                    })
+                    .collect_view()}
+                <button
+                    class="px-3 py-1 rounded-md ring-1 ring-neutral-400 dark:ring-neutral-600"
+                    on:click=move |_| editing.update(|editing| *editing = !*editing)
+                >
+                    "Custom"
+                </button>
+                <button class="px-3 py-1 rounded-md bg-violet-500 text-white" on:click=move |_| restart()>
+                    "Restart"
+                </button>
+            </div>
+            <Show when=editing fallback=|| ()>
+                <textarea
+                    class="w-72 h-40 p-2 rounded-md font-mono text-sm bg-transparent
+                           ring-1 ring-neutral-400 dark:ring-neutral-600 outline-none"
+                    prop:value=source
+                    on:input=move |event| source.set(event_target_value(&event))
+                />
+            </Show>
+            {move || parsed.with(|parsed| parsed.as_ref().err().map(|err| view! {
+                <p class="text-sm text-red-500">{err.to_string()}</p>
+            }))}
+            {board}
+            <p class="text-sm opacity-60">
+                "Click a block, then move it with the arrow keys or by clicking where it should go."
+            </p>
+        </div>
+    }
+}
+
+use core::fmt;
+
+use leptos::{ev::keydown, *};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+use macros::classes;
+
+use crate::signal_ext::SignalUpdateConditional;
+
+#[cfg(test)]
+mod tests {
+    fn warm_up() -> Layout {
+        Layout::parse(PRESETS[0].1).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let layout = warm_up();
+        assert_eq!(layout.shape, (4, 4));
+        assert_eq!(layout.blocks.len(), 9);
+        assert_eq!(
+            layout.blocks[layout.target],
+            Block {
+                name: 'B',
+                x: 1,
+                y: 0,
+                width: 2,
+                height: 2,
+            }
+        );
+        assert_eq!(layout.goal, (1, 2));
+        assert_eq!(layout.block_at((0, 1)), Some(0));
+        assert_eq!(layout.block_at((1, 2)), None);
+
+        for (_, preset) in PRESETS {
+            assert!(Layout::parse(preset).is_ok(), "{preset}");
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parse = Layout::parse;
+        assert_eq!(parse(""), Err(LayoutError::Empty));
+        assert_eq!(parse("goal A 0,0"), Err(LayoutError::Empty));
+        assert_eq!(parse("AB\nC\ngoal A 0,0"), Err(LayoutError::Ragged(1)));
+        assert_eq!(
+            parse("AA\nA.\ngoal A 0,0"),
+            Err(LayoutError::NotRectangle('A'))
+        );
+        assert_eq!(
+            parse("A.A\ngoal A 0,0"),
+            Err(LayoutError::NotRectangle('A'))
+        );
+        assert_eq!(parse("A.\n.."), Err(LayoutError::MissingGoal));
+        assert_eq!(parse("A.\ngoal B 0,0"), Err(LayoutError::UnknownBlock('B')));
+        assert_eq!(parse("AA\n..\ngoal A 1,0"), Err(LayoutError::GoalOutside));
+    }
+
+    #[test]
+    fn goal_line() {
+        assert_eq!(parse_goal("goal B 1,3"), Some(('B', (1, 3))));
+        assert_eq!(parse_goal("goal  B  1, 3"), None);
+        assert_eq!(parse_goal("goal BB 1,3"), None);
+        assert_eq!(parse_goal("goal B 1"), None);
+        assert_eq!(parse_goal("goal B 1,3 more"), None);
+        assert_eq!(parse_goal("target B 1,3"), None);
+    }
+
+    #[test]
+    fn sliding() {
+        let mut layout = warm_up();
+        let (a, b, d) = (0, 1, 3);
+        // Into the gap, and no further than the blocks below it.
+        assert!(layout.slide(b, Direction::Down));
+        assert!(!layout.slide(b, Direction::Down));
+        assert!(layout.slide(b, Direction::Up));
+        // Not off the board, nor into another block.
+        assert!(!layout.slide(b, Direction::Up));
+        assert!(!layout.slide(a, Direction::Right));
+        assert!(layout.slide(d, Direction::Right));
+        assert!(layout.slide(d, Direction::Right));
+        assert!(!layout.slide(d, Direction::Right));
+        assert_eq!(layout.blocks[d].x, 2);
+
+        let layout = warm_up();
+        assert_eq!(layout.direction_to(b, (2, 2)), Some(Direction::Down));
+        assert_eq!(layout.direction_to(b, (0, 2)), None);
+    }
+
+    #[test]
+    fn solved() {
+        let mut layout = Layout::parse("A.\n..\ngoal A 1,1").unwrap();
+        assert!(!layout.is_solved());
+        assert!(layout.slide(0, Direction::Right));
+        assert!(!layout.is_solved());
+        assert!(layout.slide(0, Direction::Down));
+        assert!(layout.is_solved());
+    }
+
+    use super::*;
+}
@@ -0,0 +1,85 @@
+//! The user's keyboard layout, from `navigator.keyboard.getLayoutMap()`.
+//!
+//! Keymaps are written for US QWERTY. Where the browser can tell us what each
+//! physical key produces on the actual layout, key labels are shown that way
+//! instead; elsewhere (Firefox, Safari, insecure origins) they stay QWERTY.
+
+/// Physical key code (`KeyR`, `Digit4`, ...) to the character it types.
+pub type LayoutMap = BTreeMap<String, String>;
+
+/// Starts empty and fills in once the browser answers, if it ever does.
+pub fn use_layout_map() -> ReadSignal<Option<LayoutMap>> {
+    let map = create_rw_signal(None);
+    spawn_local(async move {
+        if let Some(layout) = fetch_layout_map().await {
+            map.set(Some(layout));
+        }
+    });
+    map.read_only()
+}
+
+async fn fetch_layout_map() -> Option<LayoutMap> {
+    let navigator = window().navigator();
+    // Still behind `web_sys_unstable_apis`, like the clipboard.
+    let keyboard = Reflect::get(&navigator, &"keyboard".into()).ok()?;
+    if keyboard.is_undefined() {
+        return None;
+    }
+    let get_layout_map: Function = Reflect::get(&keyboard, &"getLayoutMap".into())
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    let promise: Promise = get_layout_map.call0(&keyboard).ok()?.dyn_into().ok()?;
+    let layout = JsFuture::from(promise)
+        .await
+        .map_err(|err| log::warn!("keyboard layout unavailable: {err:?}"))
+        .ok()?;
+
+    let mut map = LayoutMap::new();
+    for entry in js_sys::try_iter(&layout).ok()?? {
+        let entry: Array = entry.ok()?.dyn_into().ok()?;
+        if let (Some(code), Some(key)) = (entry.get(0).as_string(), entry.get(1).as_string()) {
+            map.insert(code, key);
+        }
+    }
+    Some(map)
+}
+
+/// The physical key that types `key` on US QWERTY.
+pub fn qwerty_code(key: &str) -> Option<String> {
+    let mut chars = key.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+
+    let code = match c {
+        'a'..='z' => format!("Key{}", c.to_ascii_uppercase()),
+        '0'..='9' => format!("Digit{c}"),
+        ';' => "Semicolon".into(),
+        ',' => "Comma".into(),
+        '.' => "Period".into(),
+        '/' => "Slash".into(),
+        '\'' => "Quote".into(),
+        '[' => "BracketLeft".into(),
+        ']' => "BracketRight".into(),
+        '-' => "Minus".into(),
+        '=' => "Equal".into(),
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// How to label the key that types `key` on QWERTY, given the actual layout.
+pub fn display_key(key: &str, layout: Option<&LayoutMap>) -> String {
+    layout
+        .zip(qwerty_code(key))
+        .and_then(|(layout, code)| layout.get(&code).cloned())
+        .unwrap_or_else(|| key.to_owned())
+}
+
+use std::collections::BTreeMap;
+
+use js_sys::{Array, Function, Promise, Reflect};
+use leptos::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
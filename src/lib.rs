@@ -0,0 +1,75 @@
+mod app;
+pub use app::App;
+
+#[cfg(feature = "devtools")]
+mod devtools;
+
+pub mod analysis;
+pub mod animation;
+pub mod arrangement;
+pub mod audio;
+pub mod backdrop;
+pub mod board;
+pub mod board_view;
+pub mod bundle;
+pub mod canvas_board;
+pub mod clock;
+pub mod competition;
+pub mod controller;
+pub mod countdown;
+pub mod crowd;
+pub mod daily;
+pub mod deep_link;
+pub mod distance_heat;
+pub mod drag;
+pub mod embed;
+pub mod favorites;
+pub mod game_state;
+pub mod ghost;
+pub mod goals;
+pub mod grid_overlay;
+pub mod hands;
+pub mod heatmap;
+pub mod histogram;
+pub mod hover_hint;
+pub mod import;
+pub mod integrity;
+pub mod keymap;
+pub mod klotski;
+pub mod layout_map;
+pub mod location;
+pub mod migrations;
+pub mod move_log;
+pub mod overlay;
+pub mod pacer;
+pub mod plan_markers;
+pub mod plugin;
+pub mod profile;
+pub mod puzzle;
+pub mod qr;
+pub mod reaction;
+pub mod replay;
+pub mod replay_file;
+pub mod resume;
+pub mod scramble_diagram;
+pub mod scramble_panel;
+pub mod seed;
+pub mod session_log;
+pub mod session_seed;
+pub mod settings;
+pub mod signal_ext;
+pub mod sim;
+pub mod skin;
+pub mod solve_store;
+pub mod solver;
+pub mod speech;
+pub mod stackmat;
+pub mod startup;
+pub mod stats;
+pub mod stats_panel;
+pub mod summary;
+pub mod telemetry;
+pub mod toast;
+pub mod update;
+pub mod versus;
+pub mod view_model;
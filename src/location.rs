@@ -0,0 +1,112 @@
+/// Links longer than this don't survive every chat app and address bar.
+const MAX_LINK_LEN: usize = 2000;
+
+/// Reads `name` from the page's query string.
+pub fn query_param(name: &str) -> Option<String> {
+    let search = window().location().search().ok()?;
+    UrlSearchParams::new_with_str(&search).ok()?.get(name)
+}
+
+/// A link to this page that loads `scramble`.
+pub fn scramble_url(scramble: &Scramble) -> String {
+    let location = window().location();
+    let origin = location.origin().unwrap_or_default();
+    let path = location.pathname().unwrap_or_default();
+    format!("{origin}{path}?s={}", scramble.encode_base32())
+}
+
+/// Reads `name` from the page's fragment, which is written like a query
+/// string but never sent to the server.
+pub fn fragment_param(name: &str) -> Option<String> {
+    let hash = window().location().hash().ok()?;
+    UrlSearchParams::new_with_str(hash.strip_prefix('#')?)
+        .ok()?
+        .get(name)
+}
+
+/// A link to this page that opens `file` as a replay, unless there are too
+/// many moves for one.
+pub fn replay_url(file: &ReplayFile) -> Option<String> {
+    let location = window().location();
+    let origin = location.origin().unwrap_or_default();
+    let path = location.pathname().unwrap_or_default();
+    let url = format!("{origin}{path}#r={}", file.encode());
+    (url.len() <= MAX_LINK_LEN).then_some(url)
+}
+
+/// Takes the fragment off the page's address without reloading it.
+pub fn clear_fragment() {
+    return_with_try! {
+        let location = window().location();
+        let url = format!("{}{}", location.pathname()?, location.search()?);
+        window().history()?.replace_state_with_url(&JsValue::NULL, "", Some(&url))?;
+    }
+}
+
+/// Copies `text` to the clipboard, returning whether the write went through.
+/// Goes through `Reflect` since `navigator.clipboard` is still behind
+/// `web_sys_unstable_apis`.
+pub async fn copy_to_clipboard(text: &str) -> bool {
+    let written = return_with_try! { else false;
+        let navigator = window().navigator();
+        let clipboard = Reflect::get(&navigator, &"clipboard".into())?;
+        let write_text: Function = Reflect::get(&clipboard, &"writeText".into())?.dyn_into()?;
+        write_text.call1(&clipboard, &text.into())?.dyn_into::<Promise>()?
+    };
+    // Rejected when the page isn't focused or permission is denied.
+    JsFuture::from(written).await.is_ok()
+}
+
+/// Offers `text` as a file download named `filename`.
+pub fn download_text(filename: &str, text: &str, mime: &str) {
+    download(filename, &text.into(), mime);
+}
+
+/// Offers `bytes` as a file download named `filename`.
+pub fn download_bytes(filename: &str, bytes: &[u8], mime: &str) {
+    download(filename, &Uint8Array::from(bytes), mime);
+}
+
+fn download(filename: &str, part: &JsValue, mime: &str) {
+    return_with_try! {
+        let mut options = BlobPropertyBag::new();
+        options.type_(mime);
+        let blob = Blob::new_with_u8_array_sequence_and_options(&Array::of1(part), &options)?;
+        let url = Url::create_object_url_with_blob(&blob)?;
+
+        let link = document().create_element("a")?.dyn_into::<HtmlAnchorElement>()?;
+        link.set_href(&url);
+        link.set_download(filename);
+        link.click();
+        // Some browsers start the download asynchronously.
+        set_timeout(move || _ = Url::revoke_object_url(&url), Duration::from_secs(1));
+    }
+}
+
+/// The text of the file picked in `input`. `None` if nothing was picked, or
+/// if it couldn't be read, in which case a toast says so.
+pub async fn read_selected_file(input: NodeRef<html::Input>, toasts: Toasts) -> Option<String> {
+    let file = input.get_untracked()?.files()?.get(0)?;
+    let text = JsFuture::from(file.text())
+        .await
+        .ok()
+        .and_then(|text| text.as_string());
+    if text.is_none() {
+        toasts.show("Couldn't read the file");
+    }
+    text
+}
+
+use core::time::Duration;
+
+use js_sys::{Array, Function, Promise, Reflect, Uint8Array};
+use leptos::{document, html, set_timeout, window, NodeRef};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url, UrlSearchParams};
+
+use macros::return_with_try;
+
+use crate::replay_file::ReplayFile;
+use crate::seed::Scramble;
+use crate::toast::Toasts;
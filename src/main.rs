@@ -1,13 +1,13 @@
-mod app;
-use app::App;
-
-pub(crate) mod signal_ext;
+use jugo_leptos::startup::{self, Milestone};
+use jugo_leptos::App;
 
 use leptos::*;
 
 fn main() {
+    startup::mark(Milestone::Instantiated);
     _ = console_log::init_with_level(log::Level::Debug);
     console_error_panic_hook::set_once();
 
     mount_to_body(|| view! { <App /> });
+    startup::mark(Milestone::Mounted);
 }
@@ -0,0 +1,96 @@
+//! The layout of what's kept in localStorage, versioned so that changes to it
+//! are made on purpose. Each profile records the version it was last written
+//! with, and [`run`] brings it up to date at startup, before anything is read.
+//! Solves in IndexedDB carry a version of their own, and are brought up to
+//! [`SOLVES_VERSION`] by the solve store once the database is open.
+//!
+//! A profile written by a newer release is left alone: nothing is migrated and
+//! nothing is saved, so going back a version can't clobber it.
+
+pub const SCHEMA_VERSION: u32 = 2;
+const VERSION_KEY: &str = "schema_version";
+
+/// Upgrades from version `i + 1` to `i + 2`, applied in order to the stored
+/// JSON under the given key.
+const MIGRATIONS: &[(&str, fn(&mut Value))] = &[("in_progress", timed_history)];
+
+pub const SOLVES_VERSION: u32 = 2;
+
+/// Upgrades of each stored solve, as for [`MIGRATIONS`].
+pub const SOLVE_MIGRATIONS: &[fn(&mut Value)] = &[solve_id];
+
+/// 2: the history of a solve in progress went from a string of keys to a
+/// list of keys with times; the old ones get none.
+fn timed_history(in_progress: &mut Value) {
+    let Some(history) = in_progress.get_mut("history") else {
+        return;
+    };
+    if let Some(keys) = history.as_str() {
+        *history = keys
+            .chars()
+            .map(|key| json!({ "key": key.to_string(), "at": Duration::ZERO }))
+            .collect();
+    }
+}
+
+/// 2: solves got an id of their own, since imported ones could share a
+/// finish time.
+fn solve_id(solve: &mut Value) {
+    if let Some(solve) = solve.as_object_mut() {
+        solve.entry("id").or_insert_with(|| Solve::new_id().into());
+    }
+}
+
+thread_local! {
+    static READ_ONLY: Cell<bool> = Cell::new(false);
+}
+
+/// Whether storage belongs to a newer release and mustn't be written.
+pub fn is_read_only() -> bool {
+    READ_ONLY.with(Cell::get)
+}
+
+/// Brings the active profile's storage up to [`SCHEMA_VERSION`]. Data from
+/// before versioning counts as version 1.
+pub fn run() {
+    let version = load_persisted::<u32>(VERSION_KEY).unwrap_or(1);
+    if !check_version("storage", version, SCHEMA_VERSION) {
+        return;
+    }
+
+    upgrade(MIGRATIONS, version, |&(key, migrate)| {
+        if let Some(mut value) = load_persisted::<Value>(key) {
+            migrate(&mut value);
+            store_persisted(key, &value);
+        }
+    });
+    store_persisted(VERSION_KEY, &SCHEMA_VERSION);
+}
+
+/// Whether `what`, stored at `version`, can be brought up to `newest` by this
+/// release. If not, it's from a newer one and storage turns read-only.
+pub fn check_version(what: &str, version: u32, newest: u32) -> bool {
+    if version > newest {
+        log::warn!("{what} is at schema {version}, newer than {newest}; not saving");
+        READ_ONLY.with(|read_only| read_only.set(true));
+        return false;
+    }
+    true
+}
+
+/// Applies the steps of `migrations` that come after `version`, in order,
+/// where the one at index `i` upgrades from version `i + 1` to `i + 2`.
+pub fn upgrade<M>(migrations: &[M], version: u32, apply: impl FnMut(&M)) {
+    migrations
+        .iter()
+        .skip(version.max(1) as usize - 1)
+        .for_each(apply);
+}
+
+use core::cell::Cell;
+use core::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::signal_ext::{load_persisted, store_persisted};
+use crate::stats::Solve;
@@ -0,0 +1,137 @@
+//! The moves of the current attempt, as the keys typed or in slide notation.
+//! It also holds keyboard focus while solving, so it's what key presses land on.
+
+/// Gaps between moves at least this long are marked as pauses, and at least
+/// [`LONG_PAUSE`] as long ones.
+const PAUSE: Duration = Duration::from_millis(300);
+const LONG_PAUSE: Duration = Duration::from_secs(1);
+
+/// A key that moved a tile, and when in the solve it was pressed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoggedKey {
+    pub key: String,
+    pub at: Duration,
+}
+
+/// Slide notation for `moves` made from `scramble`: the way the tiles went,
+/// with a count when more than one went at once, e.g. `R2 U L`.
+pub fn notation(scramble: Scramble, moves: &[TimedMove]) -> Vec<LoggedKey> {
+    let mut puzzle = scramble.puzzle();
+    let mut notation = Vec::with_capacity(moves.len());
+
+    for &TimedMove { cell: (x, y), at } in moves {
+        let (bx, by) = puzzle.blank();
+        let direction = match () {
+            _ if x < bx => 'R',
+            _ if x > bx => 'L',
+            _ if y < by => 'D',
+            _ => 'U',
+        };
+        let key = match puzzle.slide_from((x, y)) {
+            None | Some(0) => continue,
+            Some(1) => direction.to_string(),
+            Some(moved) => format!("{direction}{moved}"),
+        };
+        notation.push(LoggedKey { key, at });
+    }
+
+    notation
+}
+
+/// Wraps rather than scrolling sideways, keeps the newest move in view, and can
+/// be selected and copied from like any text.
+#[component]
+pub fn MoveLog(
+    node_ref: NodeRef<Div>,
+    /// The keys pressed, as typed, and when.
+    #[prop(into)]
+    keys: Signal<Vec<LoggedKey>>,
+    puzzle: RwSignal<AnyPuzzle>,
+    replay: RwSignal<Vec<TimedMove>>,
+    #[prop(into)] on_keydown: Callback<KeyboardEvent>,
+    /// Kept focusable but visually hidden, e.g. in focus mode.
+    #[prop(into)]
+    hidden: Signal<bool>,
+    #[prop(into)] dev_mode: Signal<bool>,
+) -> impl IntoView {
+    let settings = use_settings();
+    let style = memo!(|settings| settings.move_log);
+    let show_pauses = memo!(|settings| settings.move_log_pauses);
+    let scramble = memo!(|puzzle| puzzle.scramble());
+
+    let entries = move || {
+        let (entries, separator) = match style() {
+            MoveLogStyle::Keys => (keys(), ""),
+            MoveLogStyle::Notation => (replay.with(|replay| notation(scramble(), replay)), " "),
+        };
+        let mut previous = None;
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, LoggedKey { key, at })| {
+                let gap = previous.map_or(Duration::ZERO, |previous| at.saturating_sub(previous));
+                previous = Some(at);
+                let text = match i {
+                    0 => key,
+                    _ => format!("{separator}{key}"),
+                };
+                view! {
+                    <span
+                        class=move || classes!(
+                            show_pauses() && gap >= LONG_PAUSE => "text-red-500",
+                            show_pauses() && gap >= PAUSE && gap < LONG_PAUSE => "text-amber-500",
+                        )
+                        title=move || show_pauses().then(|| format!("+{}ms", gap.as_millis()))
+                    >
+                        {text}
+                    </span>
+                }
+            })
+            .collect_view()
+    };
+
+    create_effect(move |_| {
+        style.track();
+        keys.track();
+        replay.track();
+        #[rustfmt::skip]
+        request_animation_frame(move || return_with_try! {
+            let log = node_ref.get_untracked()?;
+            log.set_scroll_top(log.scroll_height());
+        });
+    });
+
+    view! {
+        <div
+            _ref=node_ref
+            tabindex="0"
+            role="log"
+            aria-label="Moves"
+            class=move || classes!(
+                "mx-auto mb-auto w-[17.5rem] min-h-[2.5rem] max-h-24 overflow-y-auto p-2 shadow rounded-md outline-none
+                ring-inset ring-1 focus:ring-2 font-mono bg-neutral-100 dark:bg-neutral-800
+                whitespace-pre-wrap break-all select-text cursor-text
+                transition-all ease-out-circ duration-[40ms]",
+                hidden() => "sr-only",
+                dev_mode()
+                    => "ring-yellow-500 dark:ring-yellow-600 focus:ring-yellow-500 focus:dark:ring-yellow-500"
+                    else "ring-neutral-400 dark:ring-neutral-600 focus:ring-violet-400 focus:dark:ring-violet-500",
+            )
+            on:keydown=move |event| on_keydown.call(event)
+        >
+            {entries}
+        </div>
+    }
+}
+
+use core::time::Duration;
+
+use leptos::{ev::KeyboardEvent, html::Div, *};
+use serde::{Deserialize, Serialize};
+
+use macros::{classes, memo, return_with_try};
+
+use crate::puzzle::AnyPuzzle;
+use crate::seed::Scramble;
+use crate::settings::{use_settings, MoveLogStyle};
+use crate::stats::TimedMove;
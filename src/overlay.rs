@@ -0,0 +1,208 @@
+//! Annotations drawn above a board's pieces: highlighted cells, outlined
+//! regions, arrows and small text badges. Features don't draw them directly;
+//! each puts its own set into the board's [`Overlays`] under a name, and
+//! replaces or clears only that set.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tone {
+    Accent,
+    Good,
+    Warning,
+}
+
+impl Tone {
+    fn fill(self) -> &'static str {
+        match self {
+            Tone::Accent => "bg-violet-400/40",
+            Tone::Good => "bg-emerald-400/40",
+            Tone::Warning => "bg-amber-400/50",
+        }
+    }
+    fn ring(self) -> &'static str {
+        match self {
+            Tone::Accent => "ring-violet-400",
+            Tone::Good => "ring-emerald-500",
+            Tone::Warning => "ring-amber-500",
+        }
+    }
+    fn text(self) -> &'static str {
+        match self {
+            Tone::Accent => "text-violet-400",
+            Tone::Good => "text-emerald-500",
+            Tone::Warning => "text-amber-500",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Annotation {
+    /// Fills a cell.
+    Highlight { cell: (usize, usize), tone: Tone },
+    /// Fills a cell on a scale from green at `0.0` to red at `1.0`.
+    Heat { cell: (usize, usize), heat: f64 },
+    /// Outlines the rectangle with these two corners, both included.
+    Outline {
+        from: (usize, usize),
+        to: (usize, usize),
+        tone: Tone,
+    },
+    /// From the middle of one cell to the middle of another.
+    Arrow {
+        from: (usize, usize),
+        to: (usize, usize),
+        tone: Tone,
+    },
+    /// A few characters in a cell's corner.
+    Badge {
+        cell: (usize, usize),
+        text: String,
+        tone: Tone,
+    },
+}
+
+/// Everything on one board's overlay, by the name of what put it there.
+#[derive(Clone, Copy)]
+pub struct Overlays(RwSignal<BTreeMap<&'static str, Vec<Annotation>>>);
+
+impl Default for Overlays {
+    fn default() -> Self {
+        Self(create_rw_signal(BTreeMap::new()))
+    }
+}
+
+impl Overlays {
+    /// Replaces what `source` has on the board.
+    pub fn set(&self, source: &'static str, annotations: Vec<Annotation>) {
+        self.0.update(|overlays| match annotations.is_empty() {
+            true => _ = overlays.remove(source),
+            false => _ = overlays.insert(source, annotations),
+        });
+    }
+
+    /// Keeps what `source` has on the board in step with `annotations`, and
+    /// takes it off again when the calling scope is cleaned up.
+    pub fn register(
+        &self,
+        source: &'static str,
+        annotations: impl Fn() -> Vec<Annotation> + 'static,
+    ) {
+        let this = *self;
+        create_effect(move |_| this.set(source, annotations()));
+        on_cleanup(move || this.set(source, Vec::new()));
+    }
+
+    fn all(&self) -> Vec<Annotation> {
+        self.0
+            .with(|overlays| overlays.values().flatten().cloned().collect())
+    }
+}
+
+/// The overlays of the main board, for features outside it to draw into.
+pub fn provide_overlays() -> Overlays {
+    let overlays = Overlays::default();
+    provide_context(overlays);
+    overlays
+}
+
+pub fn use_overlays() -> Option<Overlays> {
+    use_context()
+}
+
+/// Draws `overlays` in the board's `--tile`/`--pitch` coordinates. Goes inside
+/// the board element, after the pieces.
+#[component]
+pub fn OverlayLayer(overlays: Overlays, #[prop(into)] mirrored: Signal<bool>) -> impl IntoView {
+    let render = move |annotation: Annotation| match annotation {
+        Annotation::Highlight { cell: (x, y), tone } => view! {
+            <div
+                class=classes!(
+                    "absolute w-[var(--tile)] h-[var(--tile)] rounded-lg
+                    translate-x-[calc(var(--x)*var(--pitch))] translate-y-[calc(var(--y)*var(--pitch))]",
+                    tone.fill(),
+                )
+                style=("--x", x)
+                style=("--y", y)
+            />
+        },
+        Annotation::Heat { cell: (x, y), heat } => {
+            let hue = 140.0 * (1.0 - heat.clamp(0.0, 1.0));
+            view! {
+                <div
+                    class="absolute w-[var(--tile)] h-[var(--tile)] rounded-lg
+                        translate-x-[calc(var(--x)*var(--pitch))] translate-y-[calc(var(--y)*var(--pitch))]"
+                    style=("--x", x)
+                    style=("--y", y)
+                    style=("background", format!("hsl({hue:.0} 85% 50% / 0.45)"))
+                />
+            }
+        }
+        Annotation::Outline { from, to, tone } => view! {
+            <div
+                class=classes!(
+                    "absolute rounded-lg ring-2
+                    translate-x-[calc(var(--x)*var(--pitch))] translate-y-[calc(var(--y)*var(--pitch))]
+                    w-[calc(var(--w)*var(--pitch)_-_var(--gap))] h-[calc(var(--h)*var(--pitch)_-_var(--gap))]",
+                    tone.ring(),
+                )
+                style=("--x", from.0.min(to.0))
+                style=("--y", from.1.min(to.1))
+                style=("--w", from.0.abs_diff(to.0) + 1)
+                style=("--h", from.1.abs_diff(to.1) + 1)
+            />
+        },
+        Annotation::Arrow { from, to, tone } => {
+            let (dx, dy) = (to.0 as f64 - from.0 as f64, to.1 as f64 - from.1 as f64);
+            view! {
+                // A bar from the middle of `from`, turned towards `to`, with a
+                // head at the far end.
+                <div
+                    class=classes!(
+                        "absolute h-1 flex justify-end items-center origin-left
+                        left-[calc(var(--tile)/2)] top-[calc(var(--tile)/2_-_0.125rem)]
+                        translate-x-[calc(var(--x)*var(--pitch))] translate-y-[calc(var(--y)*var(--pitch))]
+                        w-[calc(var(--len)*var(--pitch))] rotate-[var(--angle)] bg-current rounded-full",
+                        tone.text(),
+                    )
+                    style=("--x", from.0)
+                    style=("--y", from.1)
+                    style=("--len", dx.hypot(dy))
+                    style=("--angle", format!("{}rad", dy.atan2(dx)))
+                >
+                    <div class="border-l-[0.6rem] border-l-current border-y-[0.4rem] border-y-transparent" />
+                </div>
+            }
+        }
+        Annotation::Badge {
+            cell: (x, y),
+            text,
+            tone,
+        } => view! {
+            <div
+                class=move || classes!(
+                    "absolute w-[var(--tile)] h-[var(--tile)] p-1 flex justify-end items-start
+                    text-xs font-bold font-mono
+                    translate-x-[calc(var(--x)*var(--pitch))] translate-y-[calc(var(--y)*var(--pitch))]",
+                    tone.text(),
+                    // Undoes the board's flip so it still reads normally.
+                    mirrored() => "-scale-x-100",
+                )
+                style=("--x", x)
+                style=("--y", y)
+            >
+                {text}
+            </div>
+        },
+    };
+
+    view! {
+        <div class="absolute inset-0 pointer-events-none">
+            {move || overlays.all().into_iter().map(render).collect_view()}
+        </div>
+    }
+}
+
+use std::collections::BTreeMap;
+
+use leptos::*;
+
+use macros::classes;
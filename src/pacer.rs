@@ -0,0 +1,182 @@
+/// A metronome at the target TPS plus a bar showing how far ahead of (or
+/// behind) that pace the current solve is.
+#[component]
+pub fn Pacer(
+    #[prop(into)] game_state: Signal<GameState>,
+    #[prop(into)] moves: Signal<usize>,
+) -> impl IntoView {
+    let settings = use_settings();
+    let pacer = memo!(|settings| settings.pacer);
+    // A pace of zero, e.g. from an edited backup, has nothing to show.
+    let shown = move || pacer().enabled && pacer().tps > 0.0;
+    let solving = move || shown() && game_state().is_solving();
+    let elapsed = solve_time(game_state, solving);
+
+    let beat = memo!(|elapsed, pacer| (elapsed.as_secs_f32() * pacer.tps) as u64);
+    create_effect(move |previous: Option<u64>| {
+        let beat = beat();
+        // The count drops back to zero when the solve ends, which isn't a beat.
+        if previous.is_some_and(|previous| previous != beat)
+            && untrack(solving)
+            && pacer.with_untracked(|pacer| pacer.sound)
+        {
+            audio::beep(1000.0, Duration::from_millis(40));
+        }
+        beat
+    });
+
+    // Moves ahead of the target pace; negative when behind.
+    let lead = move || moves() as f32 - elapsed().as_secs_f32() * pacer().tps;
+    let bar = move || {
+        // A full half of the bar is one second's worth of moves.
+        let extent = (lead() / pacer().tps).clamp(-1.0, 1.0) * 50.0;
+        format!(
+            "left: {}%; width: {}%",
+            50.0 + extent.min(0.0),
+            extent.abs()
+        )
+    };
+
+    view! {
+        <Show when=shown fallback=|| ()>
+            <div class="mx-auto mt-2 w-[17.5rem] flex items-center gap-2 font-mono text-xs">
+                <div class=move || classes!(
+                    "w-2 h-2 rounded-full transition-colors duration-75",
+                    solving() && beat() % 2 == 0 => "bg-violet-500" else "bg-neutral-400 dark:bg-neutral-600",
+                ) />
+                <div class="relative flex-1 h-1.5 rounded-full bg-neutral-300 dark:bg-neutral-700 overflow-hidden">
+                    <div class="absolute left-1/2 w-px h-full bg-neutral-500" />
+                    <div
+                        class=move || classes!(
+                            "absolute h-full",
+                            lead() >= 0.0 => "bg-emerald-500" else "bg-rose-500",
+                        )
+                        style=bar
+                    />
+                </div>
+                <span class="w-10 text-right">{move || format!("{:+.1}", lead())}</span>
+            </div>
+        </Show>
+    }
+}
+
+/// A bar showing how far ahead of (or behind) the personal best on this
+/// shape the current solve is: the time now against when the PB made as many
+/// moves.
+#[component]
+pub fn PbPace(
+    #[prop(into)] game_state: Signal<GameState>,
+    #[prop(into)] moves: Signal<usize>,
+    #[prop(into)] shape: Signal<(usize, usize)>,
+) -> impl IntoView {
+    let settings = use_settings();
+    let solves = use_solves();
+    let enabled = memo!(|settings| settings.pb_pace);
+
+    // The moves of the fastest solve on this shape that kept them.
+    let pb = create_memo(move |_| {
+        if !enabled() {
+            return None;
+        }
+        let shape = shape();
+        solves.with(|solves| {
+            solves
+                .iter()
+                .filter(|solve| solve.scramble.shape == shape && !solve.replay.is_empty())
+                .filter_map(|solve| Some((solve.result()?, solve)))
+                .min_by_key(|&(result, _)| result)
+                .map(|(_, solve)| {
+                    solve
+                        .replay
+                        .iter()
+                        .map(|timed| timed.at)
+                        .collect::<Vec<_>>()
+                })
+        })
+    });
+    let shown = move || enabled() && pb.with(Option::is_some);
+    let elapsed = solve_time(game_state, move || shown() && game_state().is_solving());
+
+    // Seconds ahead of the PB; negative when behind.
+    let lead = move || {
+        let pb_at = pb.with(|pb| match (moves(), pb) {
+            (0, _) | (_, None) => Duration::ZERO,
+            (moves, Some(pb)) => pb.get(moves - 1).or(pb.last()).copied().unwrap_or_default(),
+        });
+        pb_at.as_secs_f32() - elapsed().as_secs_f32()
+    };
+    let bar = move || {
+        // A full half of the bar is two seconds.
+        let extent = (lead() / 2.0).clamp(-1.0, 1.0) * 50.0;
+        format!(
+            "left: {}%; width: {}%",
+            50.0 + extent.min(0.0),
+            extent.abs()
+        )
+    };
+
+    view! {
+        <Show when=shown fallback=|| ()>
+            <div class="mx-auto mt-2 w-[17.5rem] flex items-center gap-2 font-mono text-xs" title="Against your PB">
+                <span class="w-4 opacity-60">"PB"</span>
+                <div class="relative flex-1 h-1.5 rounded-full bg-neutral-300 dark:bg-neutral-700 overflow-hidden">
+                    <div class="absolute left-1/2 w-px h-full bg-neutral-500" />
+                    <div
+                        class=move || classes!(
+                            "absolute h-full",
+                            lead() >= 0.0 => "bg-emerald-500" else "bg-rose-500",
+                        )
+                        style=bar
+                    />
+                </div>
+                <span class="w-10 text-right">{move || format!("{:+.1}", lead())}</span>
+            </div>
+        </Show>
+    }
+}
+
+/// The current solve's time, ticking while `active` holds and zero otherwise.
+fn solve_time(
+    game_state: Signal<GameState>,
+    active: impl Fn() -> bool + 'static,
+) -> RwSignal<Duration> {
+    let clock = use_clock();
+    let elapsed = create_rw_signal(Duration::ZERO);
+    let interval = store_value(None::<IntervalHandle>);
+    create_effect(move |_| {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+        if !active() {
+            interval.set_value(None);
+            elapsed.set(Duration::ZERO);
+            return;
+        }
+
+        let tick = move || {
+            let now = game_state.with_untracked(|state| state.solve_time(clock.now()));
+            elapsed.set(now.unwrap_or(Duration::ZERO));
+        };
+        interval.set_value(set_interval_with_handle(tick, TICK).ok());
+    });
+    on_cleanup(move || {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+    });
+    elapsed
+}
+
+const TICK: Duration = Duration::from_millis(20);
+
+use std::time::Duration;
+
+use leptos::*;
+
+use macros::{classes, memo};
+
+use crate::audio;
+use crate::clock::use_clock;
+use crate::game_state::GameState;
+use crate::settings::use_settings;
+use crate::stats::use_solves;
@@ -0,0 +1,77 @@
+//! A planning aid for inspection: clicking tiles numbers them 1, 2, 3… in the
+//! order they're meant to go, and clicking a numbered one takes it off. The
+//! numbers go as soon as the solve starts.
+
+#[derive(Clone, Copy)]
+pub struct PlanMarkers {
+    settings: RwSignal<Settings>,
+    game_state: RwSignal<GameState>,
+    cells: RwSignal<Vec<(usize, usize)>>,
+}
+
+impl PlanMarkers {
+    pub fn new(game_state: RwSignal<GameState>, overlays: Overlays) -> Self {
+        let settings = use_settings();
+        let cells = create_rw_signal(Vec::new());
+
+        create_effect(move |_| {
+            if !matches!(game_state(), GameState::Inspecting { .. }) {
+                cells.update_if_changed(Vec::clear);
+            }
+        });
+
+        overlays.register("plan_markers", move || {
+            cells.with(|cells| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, &cell)| {
+                        [
+                            Annotation::Highlight {
+                                cell,
+                                tone: Tone::Accent,
+                            },
+                            Annotation::Badge {
+                                cell,
+                                text: (i + 1).to_string(),
+                                tone: Tone::Accent,
+                            },
+                        ]
+                    })
+                    .collect()
+            })
+        });
+
+        Self {
+            settings,
+            game_state,
+            cells,
+        }
+    }
+
+    /// Whether clicks should mark tiles rather than move them.
+    pub fn is_planning(&self) -> bool {
+        self.settings
+            .with_untracked(|settings| settings.plan_markers)
+            && self
+                .game_state
+                .with_untracked(|state| matches!(state, GameState::Inspecting { .. }))
+    }
+
+    /// Numbers `cell` next, or takes its number off, renumbering the rest.
+    pub fn toggle(&self, cell: (usize, usize)) {
+        self.cells.update(
+            |cells| match cells.iter().position(|&marked| marked == cell) {
+                Some(index) => _ = cells.remove(index),
+                None => cells.push(cell),
+            },
+        );
+    }
+}
+
+use leptos::*;
+
+use crate::game_state::GameState;
+use crate::overlay::{Annotation, Overlays, Tone};
+use crate::settings::{use_settings, Settings};
+use crate::signal_ext::SignalUpdateConditional;
@@ -0,0 +1,120 @@
+//! Hooks for experiments that shouldn't need `App` edited to try out: a
+//! plugin can bind keys, put something on the HUD and draw on the main
+//! board's overlays, and reads the game through its [`Host`].
+//!
+//! A plugin is a [`Plugin`] in [`REGISTERED`], each behind a cargo feature of
+//! its own so a build only carries the experiments it asks for.
+
+/// A plugin's name and what it does when the app starts.
+#[derive(Clone, Copy)]
+pub struct Plugin {
+    pub name: &'static str,
+    pub install: fn(Host),
+}
+
+/// The plugins built into this build, installed in order.
+pub const REGISTERED: &[Plugin] = &[
+    // #[cfg(feature = "my-trainer")]
+    // crate::my_trainer::PLUGIN,
+];
+
+type Action = Rc<dyn Fn()>;
+type Widget = Rc<dyn Fn() -> View>;
+
+/// What plugins have added, for `App` to wire in.
+#[derive(Clone, Copy)]
+pub struct Hooks {
+    keys: StoredValue<Vec<(&'static str, Action)>>,
+    hud: StoredValue<Vec<Widget>>,
+}
+
+impl Hooks {
+    /// Whether a plugin has bound `key`.
+    pub fn binds(&self, key: &str) -> bool {
+        self.keys
+            .with_value(|keys| keys.iter().any(|(bound, _)| *bound == key))
+    }
+
+    /// Runs what's bound to `key`, returning whether anything was.
+    pub fn press(&self, key: &str) -> bool {
+        let action = self.keys.with_value(|keys| {
+            keys.iter()
+                .find(|(bound, _)| *bound == key)
+                .map(|(_, action)| action.clone())
+        });
+        action.map(|action| action()).is_some()
+    }
+}
+
+/// What a plugin gets to work with.
+#[derive(Clone, Copy)]
+pub struct Host {
+    pub puzzle: Signal<AnyPuzzle>,
+    pub game_state: Signal<GameState>,
+    /// The main board's; put annotations in under the plugin's name.
+    pub overlays: Overlays,
+    hooks: Hooks,
+}
+
+impl Host {
+    /// Calls `action` when `key` (as in `KeyboardEvent.key`) is pressed with no
+    /// modifiers. The keymap and shortcuts come first, and the first plugin to
+    /// bind a key keeps it.
+    pub fn bind_key(&self, key: &'static str, action: impl Fn() + 'static) {
+        self.hooks
+            .keys
+            .update_value(|keys| keys.push((key, Rc::new(action))));
+    }
+
+    /// Adds `widget` to the HUD along the bottom left of the screen.
+    pub fn add_hud<V: IntoView>(&self, widget: impl Fn() -> V + 'static) {
+        self.hooks
+            .hud
+            .update_value(|hud| hud.push(Rc::new(move || widget().into_view())));
+    }
+}
+
+/// Installs every plugin in `plugins` against the given game.
+pub fn install(
+    plugins: &[Plugin],
+    puzzle: Signal<AnyPuzzle>,
+    game_state: Signal<GameState>,
+    overlays: Overlays,
+) -> Hooks {
+    let hooks = Hooks {
+        keys: store_value(Vec::new()),
+        hud: store_value(Vec::new()),
+    };
+    let host = Host {
+        puzzle,
+        game_state,
+        overlays,
+        hooks,
+    };
+    for plugin in plugins {
+        log::info!("installing plugin {}", plugin.name);
+        (plugin.install)(host);
+    }
+    hooks
+}
+
+/// What plugins put on the HUD.
+#[component]
+pub fn PluginHud(hooks: Hooks) -> impl IntoView {
+    let widgets = hooks.hud.get_value();
+    (!widgets.is_empty()).then(|| {
+        view! {
+            <div class="fixed bottom-3 left-3 flex flex-col gap-1 text-sm">
+                {widgets.into_iter().map(|widget| widget()).collect_view()}
+            </div>
+        }
+    })
+}
+
+use std::rc::Rc;
+
+use leptos::*;
+
+use crate::game_state::GameState;
+use crate::overlay::Overlays;
+use crate::puzzle::AnyPuzzle;
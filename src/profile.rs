@@ -0,0 +1,163 @@
+//! Named profiles, each with its own copy of everything persisted: settings,
+//! keymaps, solves, goals and so on.
+//!
+//! The active profile is fixed for the lifetime of the page; switching saves
+//! the choice and reloads, so every store starts cleanly from the new one.
+
+const PROFILES_KEY: &str = "profiles";
+pub const DEFAULT_PROFILE: &str = "Default";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    pub names: Vec<String>,
+    pub active: String,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            names: vec![DEFAULT_PROFILE.to_owned()],
+            active: DEFAULT_PROFILE.to_owned(),
+        }
+    }
+}
+
+thread_local! {
+    static ACTIVE: String = load_profiles().active;
+}
+
+/// `key` in the active profile's namespace. The default profile keeps the
+/// bare keys it had before profiles existed.
+pub fn scoped_key(key: &str) -> String {
+    ACTIVE.with(|active| match active.as_str() {
+        DEFAULT_PROFILE => key.to_owned(),
+        name => format!("profile:{name}:{key}"),
+    })
+}
+
+pub fn active_profile() -> String {
+    ACTIVE.with(Clone::clone)
+}
+
+pub fn profile_names() -> Vec<String> {
+    load_profiles().names
+}
+
+// Read and written directly, since the persistence helpers scope every key.
+fn load_profiles() -> Profiles {
+    return_with_try! { else Profiles::default();
+        let raw = window().local_storage()??.get_item(PROFILES_KEY)??;
+        serde_json::from_str(&raw)?
+    }
+}
+
+fn store_profiles(profiles: &Profiles) {
+    return_with_try! {
+        let raw = serde_json::to_string(profiles)?;
+        window().local_storage()??.set_item(PROFILES_KEY, &raw)?;
+    }
+}
+
+fn switch_to(name: &str) {
+    let mut profiles = load_profiles();
+    if !profiles.names.iter().any(|existing| existing == name) {
+        profiles.names.push(name.to_owned());
+    }
+    profiles.active = name.to_owned();
+    store_profiles(&profiles);
+    _ = window().location().reload();
+}
+
+/// Removes `name` and everything stored under it, then falls back to the
+/// default profile if it was the active one.
+async fn delete(name: &str) {
+    if let Err(err) = solve_store::delete_profile(name).await {
+        log::error!("couldn't delete the solves of {name:?}: {err:?}");
+    }
+
+    return_with_try! {
+        let storage = window().local_storage()??;
+        let prefix = format!("profile:{name}:");
+        let keys: Vec<String> = (0..storage.length()?)
+            .filter_map(|i| storage.key(i).ok().flatten())
+            .filter(|key| key.starts_with(&prefix))
+            .collect();
+        for key in keys {
+            storage.remove_item(&key)?;
+        }
+    }
+
+    let mut profiles = load_profiles();
+    profiles.names.retain(|existing| existing != name);
+    if profiles.active == name {
+        switch_to(DEFAULT_PROFILE);
+    } else {
+        store_profiles(&profiles);
+    }
+}
+
+#[component]
+pub fn ProfileSwitcher() -> impl IntoView {
+    let profiles = create_rw_signal(load_profiles());
+
+    let on_change = move |event| match event_target_value(&event).as_str() {
+        "+new" => {
+            let name = window()
+                .prompt_with_message("Name for the new profile")
+                .ok()
+                .flatten()
+                .map(|name| name.trim().to_owned())
+                .filter(|name| !name.is_empty() && !name.contains(':') && !name.starts_with('+'));
+            match name {
+                Some(name) => switch_to(&name),
+                // Put the select back on the active profile.
+                None => profiles.update(|_| ()),
+            }
+        }
+        "+delete" => {
+            let active = active_profile();
+            let confirmed = window()
+                .confirm_with_message(&format!("Delete profile \"{active}\" and all its data?"))
+                .unwrap_or(false);
+            if confirmed {
+                spawn_local(async move { delete(&active).await });
+            } else {
+                profiles.update(|_| ());
+            }
+        }
+        name => switch_to(name),
+    };
+
+    view! {
+        <select
+            class="fixed top-3 left-3 h-8 px-1 rounded-md bg-transparent text-sm opacity-60 hover:opacity-100"
+            title="Profile"
+            on:change=on_change
+        >
+            {move || {
+                let active = active_profile();
+                profiles.with(|profiles| {
+                    profiles
+                        .names
+                        .iter()
+                        .map(|name| {
+                            view! { <option value=name.clone() selected=*name == active>{name.clone()}</option> }
+                        })
+                        .collect_view()
+                })
+            }}
+            <option value="+new">"New profile…"</option>
+            <Show when=move || active_profile() != DEFAULT_PROFILE fallback=|| ()>
+                <option value="+delete">"Delete this profile"</option>
+            </Show>
+        </select>
+    }
+}
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use macros::return_with_try;
+
+use crate::solve_store;
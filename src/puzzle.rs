@@ -0,0 +1,161 @@
+#[derive(Deref, DerefMut)]
+pub struct SeedablePuzzle<T: Tile> {
+    #[deref]
+    #[deref_mut]
+    board: Board<T>,
+    scramble: Scramble,
+}
+
+impl<T: Tile> SeedablePuzzle<T> {
+    pub fn from_scramble(scramble: Scramble) -> Self {
+        let rng = &mut match scramble.rng {
+            RngId::Xoshiro256StarStar => Xoshiro256StarStar::from_seed(scramble.seed),
+        };
+        let board = match scramble.mode {
+            ScrambleMode::RandomState => {
+                let random = BoxPuzzle::<T>::random_with_rng(rng, scramble.shape);
+                let mut board = Board::from(&random).with_goal(scramble.goal);
+                board.make_solvable();
+                board
+            }
+            ScrambleMode::RandomMoves { length } => {
+                Board::random_moves(rng, scramble.shape, scramble.goal, length as usize)
+            }
+        };
+
+        Self { board, scramble }
+    }
+    #[inline]
+    pub fn new_from_seed(seed: [u8; 32], shape: (usize, usize)) -> Self {
+        Self::from_scramble(Scramble::new(shape, seed))
+    }
+    #[inline]
+    pub fn new(shape: (usize, usize)) -> Self {
+        Self::new_from_seed(rand::thread_rng().gen(), shape)
+    }
+
+    #[inline]
+    pub fn seed(&self) -> &[u8; 32] {
+        &self.scramble.seed
+    }
+    #[inline]
+    pub fn scramble(&self) -> &Scramble {
+        &self.scramble
+    }
+}
+
+/// The narrowest piece type that can number every tile of a board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PieceWidth {
+    U8,
+    U16,
+    Usize,
+}
+
+impl PieceWidth {
+    pub fn for_shape((width, height): (usize, usize)) -> Self {
+        match width * height {
+            ..=0x100 => PieceWidth::U8,
+            ..=0x10000 => PieceWidth::U16,
+            _ => PieceWidth::Usize,
+        }
+    }
+}
+
+/// A [`SeedablePuzzle`] whose piece type is picked from its shape, so small
+/// boards don't pay for `usize` pieces.
+pub enum AnyPuzzle {
+    U8(SeedablePuzzle<u8>),
+    U16(SeedablePuzzle<u16>),
+    Usize(SeedablePuzzle<usize>),
+}
+
+macro_rules! with_puzzle {
+    ($any:expr, |$puzzle:ident| $body:expr) => {
+        match $any {
+            AnyPuzzle::U8($puzzle) => $body,
+            AnyPuzzle::U16($puzzle) => $body,
+            AnyPuzzle::Usize($puzzle) => $body,
+        }
+    };
+}
+
+impl AnyPuzzle {
+    pub fn from_scramble(scramble: Scramble) -> Self {
+        match PieceWidth::for_shape(scramble.shape) {
+            PieceWidth::U8 => AnyPuzzle::U8(SeedablePuzzle::from_scramble(scramble)),
+            PieceWidth::U16 => AnyPuzzle::U16(SeedablePuzzle::from_scramble(scramble)),
+            PieceWidth::Usize => AnyPuzzle::Usize(SeedablePuzzle::from_scramble(scramble)),
+        }
+    }
+    pub fn new_from_seed(seed: [u8; 32], shape: (usize, usize)) -> Self {
+        Self::from_scramble(Scramble::new(shape, seed))
+    }
+    pub fn new(shape: (usize, usize)) -> Self {
+        Self::new_from_seed(rand::thread_rng().gen(), shape)
+    }
+
+    #[inline]
+    pub fn seed(&self) -> &[u8; 32] {
+        with_puzzle!(self, |puzzle| puzzle.seed())
+    }
+    #[inline]
+    pub fn scramble(&self) -> Scramble {
+        with_puzzle!(self, |puzzle| *puzzle.scramble())
+    }
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) {
+        with_puzzle!(self, |puzzle| puzzle.shape())
+    }
+    #[inline]
+    pub fn goal(&self) -> Arrangement {
+        with_puzzle!(self, |puzzle| puzzle.goal())
+    }
+    #[inline]
+    pub fn is_solved(&self) -> bool {
+        with_puzzle!(self, |puzzle| puzzle.is_solved())
+    }
+    #[inline]
+    pub fn get(&self, idx: (usize, usize)) -> Option<usize> {
+        with_puzzle!(self, |puzzle| puzzle.get(idx).map(Tile::index))
+    }
+    #[inline]
+    pub fn blank(&self) -> (usize, usize) {
+        with_puzzle!(self, |puzzle| puzzle.blank())
+    }
+    /// Where `piece` sits once the board is solved.
+    #[inline]
+    pub fn home_of(&self, piece: usize) -> (usize, usize) {
+        with_puzzle!(self, |puzzle| puzzle.home_of(Tile::from_index(piece)))
+    }
+    #[inline]
+    pub fn solved_prefix(&self) -> (usize, usize) {
+        with_puzzle!(self, |puzzle| puzzle.solved_prefix())
+    }
+    #[inline]
+    pub fn slide_from(&mut self, idx: (usize, usize)) -> Option<usize> {
+        with_puzzle!(self, |puzzle| puzzle.slide_from(idx))
+    }
+
+    /// Every piece, row by row.
+    pub fn pieces(&self) -> Vec<usize> {
+        self.iter_indexed().map(|(_, piece)| piece).collect()
+    }
+
+    pub fn iter_indexed(&self) -> Box<dyn Iterator<Item = ((usize, usize), usize)> + '_> {
+        with_puzzle!(self, |puzzle| Box::new(
+            puzzle
+                .iter_indexed()
+                .map(|(idx, &piece)| (idx, piece.into()))
+        ))
+    }
+}
+
+use derive_more::*;
+use jugo::BoxPuzzle;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256StarStar;
+
+use crate::arrangement::Arrangement;
+use crate::board::{Board, Tile};
+use crate::seed::{RngId, Scramble, ScrambleMode};
@@ -0,0 +1,51 @@
+//! QR codes for links, so a phone can pick up what's on the screen.
+
+/// Blank modules around the code, which scanners need to find its edges.
+const QUIET_ZONE: usize = 4;
+
+/// `text` as a QR code drawn in SVG, scaled to fill `class`'s size; nothing if
+/// it's too long to encode.
+#[component]
+pub fn QrCode(
+    #[prop(into)] text: MaybeSignal<String>,
+    #[prop(optional)] class: &'static str,
+) -> impl IntoView {
+    move || {
+        let code = text.with(|text| qrcode::QrCode::new(text.as_bytes()));
+        let code = match code {
+            Ok(code) => code,
+            Err(err) => {
+                log::warn!("couldn't make a QR code: {err}");
+                return None;
+            }
+        };
+
+        let width = code.width();
+        let size = width + 2 * QUIET_ZONE;
+        // One path for all dark modules; thousands of `<rect>`s are slow to diff.
+        let mut path = String::new();
+        for y in 0..width {
+            for x in 0..width {
+                if code[(x, y)] == Color::Dark {
+                    _ = write!(path, "M{},{}h1v1h-1z", x + QUIET_ZONE, y + QUIET_ZONE);
+                }
+            }
+        }
+
+        Some(view! {
+            <svg
+                class=class
+                viewBox=format!("0 0 {size} {size}")
+                shape-rendering="crispEdges"
+            >
+                <rect width=size height=size fill="white" />
+                <path d=path fill="black" />
+            </svg>
+        })
+    }
+}
+
+use core::fmt::Write;
+
+use leptos::*;
+use qrcode::Color;
@@ -0,0 +1,62 @@
+//! How long solves take to get going: the time from the board being playable
+//! to the first move, which is recognition rather than execution.
+
+/// How many of the latest solves the chart shows.
+const SHOWN: usize = 50;
+
+/// The first-move reaction time of the latest solves, oldest first, with
+/// their mean.
+#[component]
+pub fn ReactionChart() -> impl IntoView {
+    let solves = use_solves();
+    let reactions = create_memo(move |_| {
+        solves.with(|solves| {
+            let reactions: Vec<Duration> =
+                solves.iter().filter_map(|solve| solve.reaction).collect();
+            reactions[reactions.len().saturating_sub(SHOWN)..].to_vec()
+        })
+    });
+
+    let chart = move || {
+        reactions.with(|reactions| {
+            let &slowest = reactions.iter().max()?;
+            let scale = slowest.as_secs_f64().max(0.001);
+            let mean = reactions.iter().sum::<Duration>() / reactions.len() as u32;
+            let height = |time: Duration| time.as_secs_f64() / scale * 100.0;
+
+            Some(view! {
+                <div class="relative h-24 flex items-end gap-px">
+                    {reactions.iter().map(|&reaction| view! {
+                        <div
+                            class="flex-1 bg-violet-500/70 rounded-t-sm"
+                            style=("height", format!("{}%", height(reaction)))
+                            title=format_time(reaction)
+                        />
+                    }).collect_view()}
+                    <div class="absolute inset-x-0 h-px bg-emerald-500" style=("bottom", format!("{}%", height(mean))) />
+                </div>
+                <div class="flex justify-between font-mono text-xs">
+                    <span>{format!("last {}", reactions.len())}</span>
+                    <span class="text-emerald-500">"mean "{format_time(mean)}</span>
+                    <span>"slowest "{format_time(slowest)}</span>
+                </div>
+            })
+        })
+    };
+
+    view! {
+        <div class="flex flex-col gap-2">
+            <h2 class="font-bold">"First-move reaction"</h2>
+            {move || match chart() {
+                Some(chart) => chart.into_view(),
+                None => view! { <p class="opacity-60">"No solves with a recorded reaction yet."</p> }.into_view(),
+            }}
+        </div>
+    }
+}
+
+use std::time::Duration;
+
+use leptos::*;
+
+use crate::stats::{format_time, use_solves};
@@ -0,0 +1,366 @@
+/// The board of `scramble` after every move in `moves` made by `at`.
+pub fn pieces_at(scramble: Scramble, moves: &[TimedMove], at: Duration) -> Vec<usize> {
+    let mut puzzle = scramble.puzzle();
+    for timed in moves.iter().take_while(|timed| timed.at <= at) {
+        puzzle.slide_from(timed.cell);
+    }
+    puzzle.pieces()
+}
+
+/// A small, static rendering of a board, for lists and replays.
+#[component]
+pub fn MiniBoard(#[prop(into)] pieces: Signal<Vec<usize>>, shape: (usize, usize)) -> impl IntoView {
+    let (width, _) = shape;
+
+    view! {
+        <div
+            class="grid gap-0.5 font-mono text-xs"
+            style=("grid-template-columns", format!("repeat({width}, 1.5rem)"))
+        >
+            {move || pieces.with(|pieces| {
+                pieces
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &piece)| view! {
+                        <div class=classes!(
+                            "w-6 h-6 rounded-sm flex items-center justify-center",
+                            piece == 0 => "opacity-0",
+                            piece == i + 1
+                                => "bg-neutral-100 dark:bg-neutral-200 text-neutral-800"
+                                else "bg-neutral-900 dark:bg-neutral-800 text-neutral-200",
+                        )>
+                            {piece}
+                        </div>
+                    })
+                    .collect_view()
+            })}
+        </div>
+    }
+}
+
+const SPEEDS: [f64; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+/// How many moves Shift+arrow skips.
+const BIG_STEP: usize = 10;
+
+/// A position on a timeline ending at `end`, whether it's advancing, and how
+/// fast.
+#[derive(Clone, Copy)]
+struct Playback {
+    at: RwSignal<Duration>,
+    playing: RwSignal<bool>,
+    speed: RwSignal<f64>,
+    end: Duration,
+    /// When each move was made, in order, for stepping between them.
+    moves: StoredValue<Vec<Duration>>,
+}
+
+impl Playback {
+    fn new(end: Duration, moves: Vec<Duration>) -> Self {
+        let clock = use_clock();
+        let playback = Self {
+            at: create_rw_signal(Duration::ZERO),
+            playing: create_rw_signal(false),
+            speed: create_rw_signal(1.0),
+            end,
+            moves: store_value(moves),
+        };
+        let Self {
+            at, playing, speed, ..
+        } = playback;
+
+        let interval = store_value(None::<IntervalHandle>);
+        create_effect(move |_| {
+            if let Some(handle) = interval.get_value() {
+                handle.clear();
+            }
+            if !playing() {
+                interval.set_value(None);
+                return;
+            }
+
+            // Restarted from wherever it is whenever the speed changes.
+            let (started, from, speed) = (clock.now(), at.get_untracked(), speed());
+            let tick = move || {
+                let now = (from + clock.elapsed(started).mul_f64(speed)).min(end);
+                at.set(now);
+                if now == end {
+                    playing.set(false);
+                }
+            };
+            interval.set_value(set_interval_with_handle(tick, Duration::from_millis(16)).ok());
+        });
+        on_cleanup(move || {
+            if let Some(handle) = interval.get_value() {
+                handle.clear();
+            }
+        });
+
+        playback
+    }
+
+    fn toggle(&self) {
+        if self.at.get_untracked() >= self.end {
+            self.at.set(Duration::ZERO);
+        }
+        self.playing.update(|playing| *playing = !*playing);
+    }
+
+    fn seek(&self, to: Duration) {
+        self.playing.set(false);
+        self.at.set(to.min(self.end));
+    }
+
+    /// Pauses on the `count`th move after the current position, or before it
+    /// with `backwards`; the start comes before the first move.
+    fn step(&self, count: usize, backwards: bool) {
+        let at = self.at.get_untracked();
+        let to = self.moves.with_value(|moves| match backwards {
+            false => moves
+                .iter()
+                .filter(|&&time| time > at)
+                .nth(count - 1)
+                .copied(),
+            true => moves
+                .iter()
+                .rev()
+                .filter(|&&time| time < at)
+                .chain([&Duration::ZERO])
+                .nth(count - 1)
+                .copied(),
+        });
+        self.seek(to.unwrap_or(if backwards { Duration::ZERO } else { self.end }));
+    }
+
+    /// Space plays and pauses, arrows step a move (ten with Shift), and
+    /// Home/End jump to either end.
+    fn on_keydown(&self, event: KeyboardEvent) {
+        let count = if event.shift_key() { BIG_STEP } else { 1 };
+        match event.key().as_str() {
+            " " => self.toggle(),
+            "ArrowRight" => self.step(count, false),
+            "ArrowLeft" => self.step(count, true),
+            "Home" => self.seek(Duration::ZERO),
+            "End" => self.seek(self.end),
+            _ => return,
+        }
+        // Keeps the keys away from the board behind.
+        event.prevent_default();
+        event.stop_propagation();
+    }
+
+    /// Play/pause, a scrubber and the speed.
+    fn controls(self) -> impl IntoView {
+        let Self {
+            at,
+            playing,
+            speed,
+            end,
+            ..
+        } = self;
+        view! {
+            <div class="flex items-center gap-2">
+                <button
+                    class="w-8 h-8 rounded-md bg-violet-500 text-white"
+                    on:click=move |_| self.toggle()
+                >
+                    {move || if playing() { "❚❚" } else { "▶" }}
+                </button>
+                <input
+                    type="range"
+                    class="flex-1"
+                    min="0"
+                    max=end.as_millis().to_string()
+                    prop:value=move || at().as_millis().to_string()
+                    on:input=move |event| {
+                        if let Ok(millis) = event_target_value(&event).parse() {
+                            self.seek(Duration::from_millis(millis));
+                        }
+                    }
+                />
+                <span class="w-16 text-right font-mono text-xs">{move || format_time(at())}</span>
+                <select
+                    class="bg-transparent font-mono text-xs"
+                    title="Playback speed"
+                    on:change=move |event| {
+                        if let Ok(value) = event_target_value(&event).parse() {
+                            speed.set(value);
+                        }
+                    }
+                >
+                    {SPEEDS
+                        .into_iter()
+                        .map(|value| view! {
+                            <option value=value.to_string() selected=move || speed() == value>
+                                {format!("{value}×")}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </div>
+        }
+    }
+}
+
+/// One solve, replayed from its moves.
+#[component]
+pub fn SolveReplay(scramble: Scramble, replay: Vec<TimedMove>) -> impl IntoView {
+    let end = replay.last().map_or(Duration::ZERO, |timed| timed.at);
+    let playback = Playback::new(end, replay.iter().map(|timed| timed.at).collect());
+    let at = playback.at;
+    let pieces = Signal::derive(move || pieces_at(scramble, &replay, at()));
+
+    view! {
+        <div
+            class="flex flex-col items-center gap-2 outline-none"
+            tabindex="0"
+            on:keydown=move |event| playback.on_keydown(event)
+        >
+            <MiniBoard pieces shape=scramble.shape />
+            {playback.controls()}
+        </div>
+    }
+}
+
+/// A replay opened from a link, over everything else until it's closed.
+#[component]
+pub fn LinkedReplay(file: RwSignal<Option<ReplayFile>>) -> impl IntoView {
+    let close = move |_| {
+        file.set(None);
+        clear_fragment();
+    };
+
+    move || {
+        file().map(|file| {
+            let verification = verify(&file);
+            let ReplayFile { scramble, moves } = file;
+            let (width, height) = scramble.shape;
+            view! {
+                <div class="fixed inset-0 z-10 flex items-center justify-center bg-black/40" on:click=close>
+                    <div
+                        class="w-96 p-4 rounded-lg shadow-lg bg-neutral-100 dark:bg-neutral-800 flex flex-col gap-3 text-sm"
+                        on:click=|event| event.stop_propagation()
+                    >
+                        <div class="flex justify-between items-center">
+                            <h2 class="font-bold">
+                                {format!("Shared {width}x{height} replay, {} moves", moves.len())}
+                            </h2>
+                            <VerificationBadge verification />
+                            <button class="px-1 opacity-60 hover:opacity-100" title="Close" on:click=close>
+                                "✕"
+                            </button>
+                        </div>
+                        <SolveReplay scramble replay=moves />
+                    </div>
+                </div>
+            }
+        })
+    }
+}
+
+/// Two solves replayed on a shared timeline, with a per-phase comparison.
+#[component]
+pub fn SolveComparison(a: Solve, b: Solve) -> impl IntoView {
+    let shape = a.scramble.shape;
+    let end = a.time.max(b.time);
+    let mut moves: Vec<_> = a
+        .replay
+        .iter()
+        .chain(&b.replay)
+        .map(|timed| timed.at)
+        .collect();
+    moves.sort_unstable();
+    let playback = Playback::new(end, moves);
+    let at = playback.at;
+
+    let side = move |solve: Solve| {
+        let moves_at = {
+            let replay = solve.replay.clone();
+            move || replay.iter().take_while(|timed| timed.at <= at()).count()
+        };
+        let pieces = {
+            let replay = solve.replay.clone();
+            Signal::derive(move || pieces_at(solve.scramble, &replay, at()))
+        };
+        view! {
+            <div class="flex flex-col items-center gap-1">
+                <MiniBoard pieces shape />
+                <p class="font-mono text-xs">
+                    {format_time(solve.time)}" / "{moves_at}" of "{solve.moves}" moves"
+                </p>
+            </div>
+        }
+    };
+
+    // Where the faster solve gained (negative) or lost time against the other.
+    let breakpoints = row_breakpoints(shape);
+    let (faster, slower) = match a.time <= b.time {
+        true => (&a, &b),
+        false => (&b, &a),
+    };
+    let phase_rows = phases(faster, &breakpoints)
+        .zip(phases(slower, &breakpoints))
+        .map(|(faster, slower)| {
+            faster
+                .into_iter()
+                .zip(slower)
+                .map(|(faster, slower)| {
+                    let delta = faster.time.as_secs_f64() - slower.time.as_secs_f64();
+                    view! {
+                        <tr>
+                            <td class="pr-3">{faster.name.clone()}</td>
+                            <td class="text-right">{format!("{:.3}", faster.time.as_secs_f64())}</td>
+                            <td class="text-right">{format!("{:.3}", slower.time.as_secs_f64())}</td>
+                            <td class=classes!(
+                                "text-right",
+                                delta < 0.0 => "text-emerald-500 font-bold",
+                                delta > 0.0 => "text-rose-500",
+                            )>
+                                {format!("{delta:+.3}")}
+                            </td>
+                        </tr>
+                    }
+                })
+                .collect_view()
+        });
+
+    view! {
+        <div
+            class="flex flex-col gap-3 outline-none"
+            tabindex="0"
+            on:keydown=move |event| playback.on_keydown(event)
+        >
+            <div class="flex justify-evenly">
+                {side(a.clone())}
+                {side(b.clone())}
+            </div>
+            {playback.controls()}
+            {phase_rows.map(|rows| view! {
+                <table class="font-mono text-xs child:child:child:px-1">
+                    <thead class="opacity-60">
+                        <tr>
+                            <th class="text-left">"phase"</th>
+                            <th>"faster"</th>
+                            <th>"slower"</th>
+                            <th>"gained"</th>
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+            })}
+        </div>
+    }
+}
+
+use std::time::Duration;
+
+use leptos::{ev::KeyboardEvent, *};
+
+use macros::classes;
+
+use crate::analysis::{phases, row_breakpoints};
+use crate::clock::use_clock;
+use crate::integrity::{verify, VerificationBadge};
+use crate::location::clear_fragment;
+use crate::replay_file::ReplayFile;
+use crate::seed::Scramble;
+use crate::stats::{format_time, Solve, TimedMove};
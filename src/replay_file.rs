@@ -0,0 +1,298 @@
+//! A compact binary form of a solve's moves, for `.jugo` files and anywhere
+//! else a replay has to travel as bytes or text.
+//!
+//! After a version byte comes the scramble's own bytes (length first), then
+//! the move count and the moves. Each move is a varint for where it is from
+//! the previous one, which is where the blank went, then a varint of the
+//! milliseconds since the previous one; most moves take two or three bytes.
+
+/// Bump whenever a replay would read back differently than before.
+pub const REPLAY_VERSION: u8 = 1;
+pub const EXTENSION: &str = "jugo";
+
+/// Marks a move that isn't in line with the previous one, whose offset
+/// follows as two varints. It would otherwise mean a vertical move of zero.
+const OFF_LINE: u64 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayFile {
+    pub scramble: Scramble,
+    pub moves: Vec<TimedMove>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    Base64(base64::DecodeError),
+    UnsupportedVersion(u8),
+    Truncated,
+    Scramble(DecodeError),
+    OutOfBounds(usize),
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Base64(err) => write!(f, "not a replay code: {err}"),
+            ReplayError::UnsupportedVersion(version) => write!(
+                f,
+                "replay version {version} isn't supported by this version of the app"
+            ),
+            ReplayError::Truncated => write!(f, "replay is cut short"),
+            ReplayError::Scramble(err) => write!(f, "replay has a bad scramble: {err}"),
+            ReplayError::OutOfBounds(index) => write!(f, "move {} is off the board", index + 1),
+            ReplayError::TrailingBytes(len) => write!(f, "replay has {len} bytes too many"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl ReplayFile {
+    pub fn new(scramble: Scramble, moves: Vec<TimedMove>) -> Self {
+        Self { scramble, moves }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let scramble = self.scramble.to_bytes();
+        let mut bytes = Vec::with_capacity(scramble.len() + 4 + 3 * self.moves.len());
+        bytes.push(REPLAY_VERSION);
+        write_varint(&mut bytes, scramble.len() as u64);
+        bytes.extend(scramble);
+        write_varint(&mut bytes, self.moves.len() as u64);
+
+        let (mut previous_cell, mut previous_at) = ((0, 0), 0);
+        for timed in &self.moves {
+            let dx = timed.cell.0 as i64 - previous_cell.0 as i64;
+            let dy = timed.cell.1 as i64 - previous_cell.1 as i64;
+            match (dx, dy) {
+                (dx, 0) => write_varint(&mut bytes, zigzag(dx) << 1),
+                (0, dy) => write_varint(&mut bytes, (zigzag(dy) << 1) | 1),
+                (dx, dy) => {
+                    write_varint(&mut bytes, OFF_LINE);
+                    write_varint(&mut bytes, zigzag(dx));
+                    write_varint(&mut bytes, zigzag(dy));
+                }
+            }
+
+            // Times only go forward; a move stamped earlier reads back as
+            // simultaneous with the one before.
+            let at = timed.at.as_millis() as u64;
+            write_varint(&mut bytes, at.saturating_sub(previous_at));
+            (previous_cell, previous_at) = (timed.cell, at.max(previous_at));
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let mut reader = Reader(bytes);
+        match reader.byte()? {
+            REPLAY_VERSION => {}
+            version => return Err(ReplayError::UnsupportedVersion(version)),
+        }
+        let scramble_len = reader.varint()? as usize;
+        let scramble =
+            Scramble::from_bytes(reader.take(scramble_len)?).map_err(ReplayError::Scramble)?;
+        let (width, height) = scramble.shape;
+
+        let count = reader.varint()? as usize;
+        // Every move takes at least two bytes, which bounds the allocation.
+        let mut moves = Vec::with_capacity(count.min(reader.0.len() / 2));
+        let (mut cell, mut at) = ((0, 0), 0u64);
+        for index in 0..count {
+            let (dx, dy) = match reader.varint()? {
+                OFF_LINE => (unzigzag(reader.varint()?), unzigzag(reader.varint()?)),
+                token if token & 1 == 0 => (unzigzag(token >> 1), 0),
+                token => (0, unzigzag(token >> 1)),
+            };
+            let x = usize::try_from(cell.0 as i64 + dx)
+                .ok()
+                .filter(|&x| x < width);
+            let y = usize::try_from(cell.1 as i64 + dy)
+                .ok()
+                .filter(|&y| y < height);
+            let (Some(x), Some(y)) = (x, y) else {
+                return Err(ReplayError::OutOfBounds(index));
+            };
+            cell = (x, y);
+            at = at.saturating_add(reader.varint()?);
+            moves.push(TimedMove {
+                at: Duration::from_millis(at),
+                cell,
+            });
+        }
+
+        match reader.0.len() {
+            0 => Ok(Self { scramble, moves }),
+            len => Err(ReplayError::TrailingBytes(len)),
+        }
+    }
+
+    /// The bytes as URL-safe base64.
+    pub fn encode(&self) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(self.to_bytes())
+    }
+
+    pub fn decode(code: &str) -> Result<Self, ReplayError> {
+        let bytes = BASE64_URL_SAFE_NO_PAD
+            .decode(code.trim())
+            .map_err(ReplayError::Base64)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+#[inline]
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+#[inline]
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, ReplayError> {
+        let (&byte, rest) = self.0.split_first().ok_or(ReplayError::Truncated)?;
+        self.0 = rest;
+        Ok(byte)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ReplayError> {
+        if len > self.0.len() {
+            return Err(ReplayError::Truncated);
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn varint(&mut self) -> Result<u64, ReplayError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ReplayError::Truncated)
+    }
+}
+
+use core::fmt;
+use std::time::Duration;
+
+use base64::{prelude::*, Engine};
+
+use crate::seed::{DecodeError, Scramble};
+use crate::stats::TimedMove;
+
+#[cfg(test)]
+mod tests {
+    fn timed(at: u64, cell: (usize, usize)) -> TimedMove {
+        TimedMove {
+            at: Duration::from_millis(at),
+            cell,
+        }
+    }
+
+    /// Moves of several tiles at once, backwards and forwards, with a pause
+    /// long enough to take more than one byte.
+    fn file() -> ReplayFile {
+        ReplayFile::new(
+            Scramble::new((4, 4), [7; 32]),
+            vec![
+                timed(0, (3, 2)),
+                timed(180, (0, 2)),
+                timed(410, (0, 3)),
+                timed(520, (3, 3)),
+                timed(1_730, (3, 0)),
+                timed(1_730, (1, 0)),
+            ],
+        )
+    }
+
+    /// `file`'s bytes up to its move count, followed by `count`.
+    fn with_count(file: &ReplayFile, count: u64) -> Vec<u8> {
+        let empty = ReplayFile::new(file.scramble, Vec::new()).to_bytes();
+        let mut bytes = empty[..empty.len() - 1].to_vec();
+        write_varint(&mut bytes, count);
+        bytes
+    }
+
+    #[test]
+    fn round_trip() {
+        let file = file();
+        assert_eq!(ReplayFile::from_bytes(&file.to_bytes()), Ok(file.clone()));
+        assert_eq!(ReplayFile::decode(&file.encode()), Ok(file.clone()));
+
+        let empty = ReplayFile::new(file.scramble, Vec::new());
+        assert_eq!(ReplayFile::from_bytes(&empty.to_bytes()), Ok(empty));
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = file().to_bytes();
+        for len in 0..bytes.len() {
+            assert_eq!(
+                ReplayFile::from_bytes(&bytes[..len]),
+                Err(ReplayError::Truncated),
+                "cut to {len} bytes"
+            );
+        }
+
+        // Far more moves than there are bytes left.
+        let bytes = with_count(&file(), u32::MAX.into());
+        assert_eq!(ReplayFile::from_bytes(&bytes), Err(ReplayError::Truncated));
+    }
+
+    #[test]
+    fn bad_input() {
+        let mut bytes = file().to_bytes();
+        bytes[0] = REPLAY_VERSION + 1;
+        assert_eq!(
+            ReplayFile::from_bytes(&bytes),
+            Err(ReplayError::UnsupportedVersion(REPLAY_VERSION + 1))
+        );
+
+        let mut bytes = file().to_bytes();
+        bytes[2] = 0; // the scramble's own version
+        assert_eq!(
+            ReplayFile::from_bytes(&bytes),
+            Err(ReplayError::Scramble(DecodeError::UnsupportedVersion(0)))
+        );
+
+        let mut bytes = file().to_bytes();
+        bytes.push(0);
+        assert_eq!(
+            ReplayFile::from_bytes(&bytes),
+            Err(ReplayError::TrailingBytes(1))
+        );
+
+        let mut off_board = file();
+        off_board.moves[3].cell = (4, 3);
+        assert_eq!(
+            ReplayFile::from_bytes(&off_board.to_bytes()),
+            Err(ReplayError::OutOfBounds(3))
+        );
+
+        assert!(matches!(
+            ReplayFile::decode("not a replay!"),
+            Err(ReplayError::Base64(_))
+        ));
+    }
+
+    use super::*;
+}
@@ -0,0 +1,58 @@
+//! The solve in progress, saved so an accidental reload can pick it back up.
+
+const KEY: &str = "in_progress";
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InProgress {
+    /// As in `?s=` links.
+    pub scramble: String,
+    pub replay: Vec<TimedMove>,
+    pub history: Vec<LoggedKey>,
+    pub moves: usize,
+    pub elapsed: Duration,
+}
+
+impl InProgress {
+    pub fn scramble(&self) -> Option<Scramble> {
+        Scramble::parse(&self.scramble)
+            .map_err(|err| log::warn!("discarding saved solve: {err}"))
+            .ok()
+    }
+}
+
+pub fn load() -> Option<InProgress> {
+    load_persisted::<Option<InProgress>>(KEY).flatten()
+}
+
+pub fn store(in_progress: Option<&InProgress>) {
+    store_persisted(KEY, &in_progress);
+}
+
+/// Keeps the saved solve in step with `snapshot`, which should be `None`
+/// whenever there's nothing worth restoring. Writes are debounced while
+/// moves come in, and the exact elapsed time is written when the page goes
+/// away.
+pub fn autosave(snapshot: impl Fn() -> Option<InProgress> + Copy + 'static) {
+    let settled = debounced(Signal::derive(snapshot), SAVE_DEBOUNCE);
+    create_effect(move |initialized: Option<()>| {
+        settled.with(|in_progress| {
+            if initialized.is_some() {
+                store(in_progress.as_ref());
+            }
+        });
+    });
+
+    let handle = window_event_listener(pagehide, move |_| store(untrack(snapshot).as_ref()));
+    on_cleanup(move || handle.remove());
+}
+
+use core::time::Duration;
+
+use leptos::{ev::pagehide, *};
+use serde::{Deserialize, Serialize};
+
+use crate::move_log::LoggedKey;
+use crate::seed::Scramble;
+use crate::signal_ext::{debounced, load_persisted, store_persisted};
+use crate::stats::TimedMove;
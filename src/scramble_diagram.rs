@@ -0,0 +1,48 @@
+/// The starting position of the current scramble, as a small diagram and a
+/// row-by-row text layout, kept on screen for the whole attempt the way
+/// scrambles are shown at competitions.
+#[component]
+pub fn ScrambleDiagram(#[prop(into)] puzzle: Signal<AnyPuzzle>) -> impl IntoView {
+    let settings = use_settings();
+    let shown = memo!(|settings| settings.scramble_diagram);
+
+    let scramble = memo!(|puzzle| puzzle.scramble());
+    let pieces = memo!(|scramble| scramble.puzzle().pieces());
+    let shape = memo!(|scramble| scramble.shape);
+
+    view! {
+        <Show when=shown fallback=|| ()>
+            <div class="flex flex-col items-center gap-2 my-auto mr-8 text-sm">
+                <span class="opacity-60">"Scramble"</span>
+                {move || view! { <MiniBoard pieces shape=shape() /> }}
+                <pre class="font-mono text-xs opacity-80">{move || pieces.with(|pieces| text_layout(pieces, shape().0))}</pre>
+            </div>
+        </Show>
+    }
+}
+
+/// One line per row, pieces right-aligned and the blank as `.`.
+pub fn text_layout(pieces: &[usize], width: usize) -> String {
+    let digits = pieces.len().to_string().len();
+    pieces
+        .chunks(width)
+        .map(|row| {
+            row.iter()
+                .map(|&piece| match piece {
+                    0 => format!("{:>digits$}", "."),
+                    piece => format!("{piece:>digits$}"),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+use leptos::*;
+
+use macros::memo;
+
+use crate::puzzle::AnyPuzzle;
+use crate::replay::MiniBoard;
+use crate::settings::use_settings;
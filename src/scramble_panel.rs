@@ -0,0 +1,209 @@
+/// Lets players load a scramble from a code or derive one from any phrase;
+/// share, star or blacklist the current one; pick a favorite; and follow a
+/// seeded session. Starting one calls `on_session_start` to load its first
+/// scramble.
+#[component]
+pub fn ScramblePanel(
+    #[prop(into)] shape: Signal<(usize, usize)>,
+    #[prop(into)] current: Signal<Scramble>,
+    #[prop(into)] on_load: Callback<Scramble>,
+    session: SessionSeed,
+    #[prop(into)] on_session_start: Callback<()>,
+) -> impl IntoView {
+    let settings = use_settings();
+    let marks = use_scramble_marks();
+    let on_load = store_value(on_load);
+    let on_session_start = store_value(on_session_start);
+    let open = create_rw_signal(false);
+    let input = create_rw_signal(String::new());
+    let resolved = create_memo(move |_| {
+        with!(|input| match input.trim() {
+            "" => None,
+            code => Some(resolve(code, shape())),
+        })
+    });
+
+    let link = Signal::derive(move || current.with(scramble_url));
+    let copied = create_rw_signal(false);
+    create_effect(move |_| {
+        link.track();
+        copied.set(false);
+    });
+
+    let load = move || {
+        if let Some((scramble, _)) = resolved.get_untracked() {
+            on_load.with_value(|on_load| on_load.call(scramble));
+            open.set(false);
+        }
+    };
+
+    // From the code typed above if there is one, otherwise a random master.
+    let start_session = move || {
+        let master = match resolved.get_untracked() {
+            Some((scramble, _)) => scramble,
+            None => {
+                settings.with_untracked(|settings| settings.scramble.random(shape.get_untracked()))
+            }
+        };
+        session.start(master);
+        on_session_start.with_value(|on_session_start| on_session_start.call(()));
+        open.set(false);
+    };
+
+    view! {
+        <button
+            class="fixed top-3 right-12 w-8 h-8 rounded-md text-xl opacity-60 hover:opacity-100"
+            title="Load scramble"
+            on:click=move |_| open.update(|open| *open = !*open)
+        >
+            "#"
+        </button>
+        <Show when=open fallback=|| ()>
+            <div class="fixed top-12 right-3 w-72 p-4 rounded-lg shadow-lg bg-neutral-100 dark:bg-neutral-800
+                        flex flex-col gap-2 text-sm">
+                <input
+                    type="text"
+                    class="p-2 rounded-md bg-transparent ring-1 ring-neutral-400 dark:ring-neutral-600 outline-none"
+                    placeholder="Scramble code or any phrase"
+                    prop:value=input
+                    on:input=move |event| input.set(event_target_value(&event))
+                    on:keydown=move |event| if event.key() == "Enter" { load() }
+                />
+                {move || resolved().map(|(scramble, source)| view! {
+                    <p class="opacity-70">
+                        {match source {
+                            Source::Code => "Scramble code for ",
+                            Source::Phrase => "Phrase hashed to ",
+                        }}
+                        {format!("{}x{}:", scramble.shape.0, scramble.shape.1)}
+                    </p>
+                    <p class="font-mono break-all">{scramble.encode_base32()}</p>
+                })}
+                <button
+                    class="self-end px-3 py-1 rounded-md bg-violet-500 text-white disabled:opacity-40"
+                    disabled=move || resolved.with(Option::is_none)
+                    on:click=move |_| load()
+                >
+                    "Load"
+                </button>
+                <hr class="border-neutral-300 dark:border-neutral-700" />
+                <p class="opacity-70">"This scramble:"</p>
+                <QrCode text=link class="self-center w-40 h-40 rounded-md" />
+                <p class="font-mono text-xs break-all opacity-70">{link}</p>
+                <div class="flex justify-end gap-2">
+                    <button
+                        class="px-3 py-1 rounded-md ring-1 ring-neutral-400 dark:ring-neutral-600"
+                        title="Keep it in the favorites below"
+                        on:click=move |_| marks.toggle_star(current.get_untracked())
+                    >
+                        {move || if current.with(|current| marks.is_starred(current)) { "★ Starred" } else { "☆ Star" }}
+                    </button>
+                    <button
+                        class="px-3 py-1 rounded-md ring-1 ring-neutral-400 dark:ring-neutral-600"
+                        title="Never hand this one out again"
+                        on:click=move |_| marks.toggle_blacklist(current.get_untracked())
+                    >
+                        {move || match current.with(|current| marks.is_blacklisted(current)) {
+                            true => "Blacklisted",
+                            false => "Blacklist",
+                        }}
+                    </button>
+                    <button
+                        class="px-3 py-1 rounded-md ring-1 ring-neutral-400 dark:ring-neutral-600"
+                        on:click=move |_| spawn_local(async move {
+                            copied.set(copy_to_clipboard(&link.get_untracked()).await)
+                        })
+                    >
+                        {move || if copied() { "Copied" } else { "Copy link" }}
+                    </button>
+                </div>
+                <Show when=move || !marks.starred().is_empty() fallback=|| ()>
+                    <hr class="border-neutral-300 dark:border-neutral-700" />
+                    <p class="opacity-70">"Favorites:"</p>
+                    <ul class="max-h-40 overflow-y-auto flex flex-col gap-1">
+                        {move || {
+                            marks
+                                .starred()
+                                .into_iter()
+                                .map(|scramble| view! {
+                                    <li class="flex items-center gap-2">
+                                        <button
+                                            class="flex-1 text-left font-mono text-xs truncate hover:underline"
+                                            title="Load"
+                                            on:click=move |_| {
+                                                on_load.with_value(|on_load| on_load.call(scramble));
+                                                open.set(false);
+                                            }
+                                        >
+                                            {format!("{}x{} ", scramble.shape.0, scramble.shape.1)}
+                                            {scramble.encode_base32()}
+                                        </button>
+                                        <button
+                                            class="opacity-60 hover:opacity-100"
+                                            title="Unstar"
+                                            on:click=move |_| marks.toggle_star(scramble)
+                                        >
+                                            "✕"
+                                        </button>
+                                    </li>
+                                })
+                                .collect_view()
+                        }}
+                    </ul>
+                </Show>
+                <hr class="border-neutral-300 dark:border-neutral-700" />
+                {move || match session.master() {
+                    None => view! {
+                        <p class="opacity-70">
+                            "A seeded session derives every scramble from one code, \
+                            so the whole session can be shared or played again."
+                        </p>
+                        <button
+                            class="self-end px-3 py-1 rounded-md ring-1 ring-neutral-400 dark:ring-neutral-600"
+                            on:click=move |_| start_session()
+                        >
+                            {move || match resolved.with(Option::is_some) {
+                                true => "Start session from code",
+                                false => "Start seeded session",
+                            }}
+                        </button>
+                    }
+                    .into_view(),
+                    Some(master) => view! {
+                        <p class="opacity-70">{move || format!("Seeded session, {} played:", session.played())}</p>
+                        <p class="font-mono break-all">{master.encode_base32()}</p>
+                        <button
+                            class="self-end px-3 py-1 rounded-md ring-1 ring-neutral-400 dark:ring-neutral-600"
+                            on:click=move |_| session.stop()
+                        >
+                            "Stop session"
+                        </button>
+                    }
+                    .into_view(),
+                }}
+            </div>
+        </Show>
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Code,
+    Phrase,
+}
+
+fn resolve(text: &str, shape: (usize, usize)) -> (Scramble, Source) {
+    match Scramble::parse(text) {
+        Ok(scramble) => (scramble, Source::Code),
+        Err(_) => (Scramble::from_phrase(text, shape), Source::Phrase),
+    }
+}
+
+use leptos::*;
+
+use crate::favorites::use_scramble_marks;
+use crate::location::{copy_to_clipboard, scramble_url};
+use crate::qr::QrCode;
+use crate::seed::Scramble;
+use crate::session_seed::SessionSeed;
+use crate::settings::use_settings;
@@ -0,0 +1,340 @@
+/// The current encoding version; bump it whenever a code would produce a
+/// different board than before.
+///
+/// 1. Xoshiro256** feeding `jugo::BoxPuzzle::random_with_rng`.
+/// 2. Adds the scramble mode; random-state scrambles are generated as in 1.
+/// 3. Adds the goal arrangement; standard goals are generated as in 2.
+pub const CURRENT_VERSION: u8 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RngId {
+    Xoshiro256StarStar = 1,
+}
+
+impl TryFrom<u8> for RngId {
+    type Error = DecodeError;
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        match id {
+            1 => Ok(RngId::Xoshiro256StarStar),
+            id => Err(DecodeError::UnknownRng(id)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScrambleMode {
+    /// A uniformly random solvable state.
+    #[default]
+    RandomState,
+    /// `length` random moves away from the solved state.
+    RandomMoves { length: u16 },
+}
+
+/// Everything needed to reproduce a scramble.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scramble {
+    pub rng: RngId,
+    pub shape: (usize, usize),
+    pub seed: [u8; 32],
+    pub mode: ScrambleMode,
+    pub goal: Arrangement,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    Base64(base64::DecodeError),
+    Length(usize),
+    UnsupportedVersion(u8),
+    UnknownRng(u8),
+    InvalidShape(usize, usize),
+    UnknownMode(u8),
+    UnknownGoal(u8),
+    InvalidCharacter(char),
+    Checksum,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Base64(err) => write!(f, "not a scramble code: {err}"),
+            DecodeError::Length(len) => {
+                write!(f, "scramble code has the wrong length ({len} bytes)")
+            }
+            DecodeError::UnsupportedVersion(version) => write!(
+                f,
+                "scramble code version {version} isn't supported by this version of the app"
+            ),
+            DecodeError::UnknownRng(id) => write!(f, "unknown scramble generator {id}"),
+            DecodeError::InvalidShape(width, height) => {
+                write!(f, "invalid board shape {width}x{height}")
+            }
+            DecodeError::UnknownMode(mode) => write!(f, "unknown scramble mode {mode}"),
+            DecodeError::UnknownGoal(goal) => write!(f, "unknown goal arrangement {goal}"),
+            DecodeError::InvalidCharacter(c) => write!(f, "unexpected {c:?} in scramble code"),
+            DecodeError::Checksum => write!(f, "scramble code has a typo (checksum mismatch)"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// version + rng + width (u16 LE) + height (u16 LE) + seed
+const V1_LEN: usize = 1 + 1 + 2 + 2 + 32;
+/// v1 + mode + mode parameter (u16 LE)
+const V2_LEN: usize = V1_LEN + 1 + 2;
+/// v2 + goal
+const V3_LEN: usize = V2_LEN + 1;
+
+impl Scramble {
+    pub fn new(shape: (usize, usize), seed: [u8; 32]) -> Self {
+        Self {
+            rng: RngId::Xoshiro256StarStar,
+            shape,
+            seed,
+            mode: ScrambleMode::RandomState,
+            goal: Arrangement::Standard,
+        }
+    }
+
+    pub fn random(shape: (usize, usize), mode: ScrambleMode) -> Self {
+        Self {
+            mode,
+            ..Self::new(shape, rand::thread_rng().gen())
+        }
+    }
+
+    /// Derives the seed from a phrase, so a group can agree on a scramble by
+    /// voice. Case and runs of whitespace don't matter.
+    pub fn from_phrase(phrase: &str, shape: (usize, usize)) -> Self {
+        let normalized = phrase
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self::new(shape, Sha256::digest(normalized).into())
+    }
+
+    /// The `index`th scramble of a session derived from this one: the same
+    /// shape and mode, seeded from a hash of this seed and the index.
+    pub fn nth(&self, index: u32) -> Self {
+        let seed = Sha256::new()
+            .chain_update(self.seed)
+            .chain_update(index.to_le_bytes())
+            .finalize();
+        Self {
+            seed: seed.into(),
+            ..*self
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (width, height) = self.shape;
+        let (mode, parameter) = match self.mode {
+            ScrambleMode::RandomState => (0, 0),
+            ScrambleMode::RandomMoves { length } => (1, length),
+        };
+
+        let goal = match self.goal {
+            Arrangement::Standard => 0,
+            Arrangement::BlankFirst => 1,
+            Arrangement::Spiral => 2,
+            Arrangement::Mirrored => 3,
+        };
+
+        let mut bytes = Vec::with_capacity(V3_LEN);
+        bytes.push(CURRENT_VERSION);
+        bytes.push(self.rng as u8);
+        bytes.extend((width as u16).to_le_bytes());
+        bytes.extend((height as u16).to_le_bytes());
+        bytes.extend(self.seed);
+        bytes.push(mode);
+        bytes.extend(parameter.to_le_bytes());
+        bytes.push(goal);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let expected_len = match bytes.first() {
+            Some(1) => V1_LEN,
+            Some(2) => V2_LEN,
+            Some(3) => V3_LEN,
+            Some(&version) => return Err(DecodeError::UnsupportedVersion(version)),
+            None => return Err(DecodeError::Length(0)),
+        };
+        if bytes.len() != expected_len {
+            return Err(DecodeError::Length(bytes.len()));
+        }
+
+        let rng = RngId::try_from(bytes[1])?;
+        let width = u16::from_le_bytes([bytes[2], bytes[3]]) as usize;
+        let height = u16::from_le_bytes([bytes[4], bytes[5]]) as usize;
+        if width < 2 || height < 2 {
+            return Err(DecodeError::InvalidShape(width, height));
+        }
+        let mode = match bytes.get(V1_LEN..bytes.len().min(V2_LEN)) {
+            None | Some([]) | Some([0, _, _]) => ScrambleMode::RandomState,
+            Some(&[1, lo, hi]) => ScrambleMode::RandomMoves {
+                length: u16::from_le_bytes([lo, hi]),
+            },
+            Some(&[mode, ..]) => return Err(DecodeError::UnknownMode(mode)),
+        };
+        let goal = match bytes.get(V2_LEN) {
+            None | Some(0) => Arrangement::Standard,
+            Some(1) => Arrangement::BlankFirst,
+            Some(2) => Arrangement::Spiral,
+            Some(3) => Arrangement::Mirrored,
+            Some(&goal) => return Err(DecodeError::UnknownGoal(goal)),
+        };
+
+        Ok(Self {
+            rng,
+            shape: (width, height),
+            seed: bytes[6..V1_LEN].try_into().unwrap(),
+            mode,
+            goal,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(self.to_bytes())
+    }
+
+    pub fn decode(code: &str) -> Result<Self, DecodeError> {
+        let bytes = BASE64_URL_SAFE_NO_PAD
+            .decode(code.trim())
+            .map_err(DecodeError::Base64)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Crockford base32 in dash-separated groups of five, ending in a check
+    /// symbol, e.g. `0H0G0-...-9`. Meant for reading aloud and typing.
+    pub fn encode_base32(&self) -> String {
+        let bytes = self.to_bytes();
+        let mut code = String::new();
+
+        for (i, symbol) in crockford::encode(&bytes).chars().enumerate() {
+            if i > 0 && i % 5 == 0 {
+                code.push('-');
+            }
+            code.push(symbol);
+        }
+        code.push('-');
+        code.push(crockford::check_symbol(&bytes));
+
+        code
+    }
+
+    pub fn decode_base32(code: &str) -> Result<Self, DecodeError> {
+        let mut symbols: Vec<char> = code.chars().filter(|c| !matches!(c, '-' | ' ')).collect();
+        let check = symbols.pop().ok_or(DecodeError::Length(0))?;
+        let bytes = crockford::decode(symbols.into_iter())?;
+
+        if !crockford::check_symbol(&bytes).eq_ignore_ascii_case(&check) {
+            return Err(DecodeError::Checksum);
+        }
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Accepts either the base64 or the base32 form.
+    pub fn parse(code: &str) -> Result<Self, DecodeError> {
+        Self::decode(code).or_else(|base64_err| match Self::decode_base32(code) {
+            Err(DecodeError::InvalidCharacter(_)) => Err(base64_err),
+            result => result,
+        })
+    }
+
+    pub fn puzzle(&self) -> AnyPuzzle {
+        AnyPuzzle::from_scramble(*self)
+    }
+}
+
+/// Scrambles are stored as their base64 code.
+impl Serialize for Scramble {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.encode())
+    }
+}
+
+impl<'de> Deserialize<'de> for Scramble {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Scramble::decode(&code).map_err(de::Error::custom)
+    }
+}
+
+mod crockford {
+    use super::DecodeError;
+
+    const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    const CHECK_ALPHABET: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+    pub fn encode(bytes: &[u8]) -> String {
+        let mut code = String::new();
+        let (mut buffer, mut bits) = (0u16, 0);
+
+        for &byte in bytes {
+            buffer = (buffer << 8) | byte as u16;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                code.push(ALPHABET[(buffer >> bits) as usize & 0x1f] as char);
+            }
+        }
+        if bits > 0 {
+            code.push(ALPHABET[(buffer << (5 - bits)) as usize & 0x1f] as char);
+        }
+
+        code
+    }
+
+    pub fn decode(symbols: impl Iterator<Item = char>) -> Result<Vec<u8>, DecodeError> {
+        let mut bytes = Vec::new();
+        let (mut buffer, mut bits) = (0u16, 0);
+
+        for symbol in symbols {
+            buffer = (buffer << 5) | value(symbol)? as u16;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((buffer >> bits) as u8);
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    /// Crockford's mod-37 check symbol over the whole byte string.
+    pub fn check_symbol(bytes: &[u8]) -> char {
+        let remainder = bytes
+            .iter()
+            .fold(0, |remainder, &byte| (remainder * 256 + byte as usize) % 37);
+        CHECK_ALPHABET[remainder] as char
+    }
+
+    fn value(symbol: char) -> Result<u8, DecodeError> {
+        let normalized = match symbol.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        };
+
+        ALPHABET
+            .iter()
+            .position(|&c| c as char == normalized)
+            .map(|value| value as u8)
+            .ok_or(DecodeError::InvalidCharacter(symbol))
+    }
+}
+
+use core::fmt;
+
+use base64::{prelude::*, Engine};
+use rand::Rng;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+use crate::arrangement::Arrangement;
+use crate::puzzle::AnyPuzzle;
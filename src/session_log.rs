@@ -0,0 +1,117 @@
+//! Everything that happened this session, in order: each scramble loaded, each
+//! attempt abandoned for another, each solve. Unlike the move log it survives
+//! resets, and unlike the solve list it keeps what never finished.
+
+/// Oldest entries go once there are this many.
+const MAX_ENTRIES: usize = 2000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Entry {
+    Scramble {
+        /// As in `?s=` links.
+        scramble: String,
+    },
+    /// An attempt left for a new scramble or a retry before it was solved.
+    Aborted {
+        scramble: String,
+        moves: usize,
+        /// `None` if the timer never started.
+        elapsed: Option<Duration>,
+        keys: String,
+    },
+    Solved {
+        scramble: String,
+        time: Duration,
+        moves: usize,
+    },
+}
+
+impl Entry {
+    fn label(&self) -> &'static str {
+        match self {
+            Entry::Scramble { .. } => "scramble",
+            Entry::Aborted { .. } => "aborted",
+            Entry::Solved { .. } => "solved",
+        }
+    }
+
+    fn details(&self) -> String {
+        match self {
+            Entry::Scramble { scramble } => scramble.clone(),
+            Entry::Aborted {
+                moves,
+                elapsed,
+                keys,
+                ..
+            } => {
+                let elapsed = elapsed.map_or_else(|| "-".to_string(), format_time);
+                format!("{elapsed}, {moves} moves: {keys}")
+            }
+            Entry::Solved { time, moves, .. } => format!("{}, {moves} moves", format_time(*time)),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct SessionLog(RwSignal<Vec<(f64, Entry)>>);
+
+impl SessionLog {
+    pub fn append(&self, entry: Entry) {
+        self.0.update(|entries| {
+            if entries.len() == MAX_ENTRIES {
+                entries.remove(0);
+            }
+            entries.push((js_sys::Date::now(), entry));
+        });
+    }
+}
+
+pub fn provide_session_log() -> SessionLog {
+    let log = SessionLog(create_rw_signal(Vec::new()));
+    provide_context(log);
+    log
+}
+
+pub fn use_session_log() -> SessionLog {
+    expect_context()
+}
+
+/// The newest `limit` entries (all of them by default), newest first.
+#[component]
+pub fn SessionLogList(#[prop(optional)] limit: Option<usize>) -> impl IntoView {
+    let SessionLog(entries) = use_session_log();
+
+    let rows = move || {
+        entries.with(|entries| {
+            entries
+                .iter()
+                .rev()
+                .take(limit.unwrap_or(usize::MAX))
+                .map(|(at, entry)| {
+                    let at = String::from(
+                        js_sys::Date::new(&(*at).into()).to_locale_time_string("default"),
+                    );
+                    view! {
+                        <tr>
+                            <td class="opacity-60">{at}</td>
+                            <td>{entry.label()}</td>
+                            <td class="break-all">{entry.details()}</td>
+                        </tr>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <table class="font-mono text-xs child:child:child:px-1 child:child:child:align-top">
+            <tbody>{rows}</tbody>
+        </table>
+    }
+}
+
+use core::time::Duration;
+
+use leptos::*;
+
+use crate::stats::format_time;
@@ -0,0 +1,64 @@
+//! Practice sessions that can be played again: every scramble is derived from
+//! one master scramble code and its place in the session, so sharing that
+//! code shares the whole session, in order.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Progress {
+    master: Scramble,
+    next: u32,
+}
+
+/// The session being followed, if any. Kept across reloads so a session can
+/// be continued.
+#[derive(Clone, Copy)]
+pub struct SessionSeed(RwSignal<Option<Progress>>);
+
+impl SessionSeed {
+    pub fn new() -> Self {
+        Self(create_persisted_signal("session_seed", None))
+    }
+
+    /// Follows the session derived from `master`, from its first scramble.
+    pub fn start(&self, master: Scramble) {
+        self.0.set(Some(Progress { master, next: 0 }));
+    }
+
+    pub fn stop(&self) {
+        self.0.set(None);
+    }
+
+    pub fn master(&self) -> Option<Scramble> {
+        self.0
+            .with(|progress| progress.map(|progress| progress.master))
+    }
+
+    /// How many scrambles of the session have been handed out.
+    pub fn played(&self) -> u32 {
+        self.0
+            .with(|progress| progress.map_or(0, |progress| progress.next))
+    }
+
+    /// The session's next scramble and its index, moving on past it.
+    pub fn next(&self) -> Option<(u32, Scramble)> {
+        let mut next = None;
+        self.0.update(|progress| {
+            if let Some(progress) = progress {
+                next = Some((progress.next, progress.master.nth(progress.next)));
+                progress.next += 1;
+            }
+        });
+        next
+    }
+}
+
+impl Default for SessionSeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::seed::Scramble;
+use crate::signal_ext::create_persisted_signal;
@@ -0,0 +1,1049 @@
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub timer_precision: TimerPrecision,
+    pub scramble: ScrambleSetting,
+    pub solved_region: SolvedRegionStyle,
+    pub completion_effect: CompletionEffect,
+    pub motion: Motion,
+    pub hover_hint: HoverHint,
+    /// Row/column labels and gridlines around the board.
+    pub coordinates: bool,
+    /// A screen-filling timer above the board, with the history and stats
+    /// hidden, for filming attempts.
+    pub focus_mode: bool,
+    /// Pause a running solve when the tab is hidden; the next move resumes it.
+    pub pause_when_hidden: bool,
+    /// Count down 3-2-1 after a new scramble or retry before it can be played.
+    pub countdown: bool,
+    /// The latest solve and ao5 in the page title and a footer.
+    pub solve_summary: bool,
+    /// During inspection, clicking tiles numbers them as a plan instead of
+    /// moving them.
+    pub plan_markers: bool,
+    /// Tint tiles by how far they are from home.
+    pub distance_heat: bool,
+    /// The scramble's starting position beside the board during the solve.
+    pub scramble_diagram: bool,
+    /// A ghost board following the solver's line, one move per move.
+    pub ghost: bool,
+    pub keymap: Keymap,
+    pub move_log: MoveLogStyle,
+    /// Colour moves in the log by how long they came after the one before.
+    pub move_log_pauses: bool,
+    pub shortcuts: Shortcuts,
+    /// Draw the board flipped left to right, to go with a mirrored keymap.
+    pub mirror_board: bool,
+    /// Drag tiles with the pointer and let go to slide them, rather than
+    /// sliding on press.
+    pub drag_tiles: bool,
+    pub background: BackgroundSetting,
+    pub skin: PieceSkin,
+    pub speech: SpeechSetting,
+    /// Take official times from a Stackmat timer on the audio input.
+    pub stackmat: bool,
+    pub pacer: PacerSetting,
+    /// A bar comparing the solve with the PB's time at the same move.
+    pub pb_pace: bool,
+    /// Where solves are split for phase analysis, as tile counts; empty to
+    /// split after each row.
+    pub phase_breakpoints: Vec<usize>,
+    /// How many boards to solve at once under one timer; `0` reads as one.
+    pub boards: u8,
+    pub telemetry: TelemetrySetting,
+}
+
+pub const MAX_BOARDS: u8 = 4;
+
+impl Settings {
+    pub fn board_count(&self) -> usize {
+        self.boards.clamp(1, MAX_BOARDS) as usize
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrambleSetting {
+    pub random_moves: bool,
+    pub length: u16,
+    /// The arrangement new random scrambles are solved into.
+    pub goal: Arrangement,
+}
+
+impl Default for ScrambleSetting {
+    fn default() -> Self {
+        Self {
+            random_moves: false,
+            length: 40,
+            goal: Arrangement::Standard,
+        }
+    }
+}
+
+impl ScrambleSetting {
+    pub fn mode(self) -> ScrambleMode {
+        match self.random_moves {
+            false => ScrambleMode::RandomState,
+            true => ScrambleMode::RandomMoves {
+                length: self.length,
+            },
+        }
+    }
+
+    /// A fresh random scramble of `shape` as set up here.
+    pub fn random(self, shape: (usize, usize)) -> Scramble {
+        Scramble {
+            goal: self.goal,
+            ..Scramble::random(shape, self.mode())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimerPrecision {
+    Centiseconds,
+    #[default]
+    Milliseconds,
+}
+
+impl TimerPrecision {
+    /// Formats the sub-second part of `time`, truncating rather than rounding so
+    /// the display never runs ahead of the actual time.
+    pub fn format_fraction(self, time: Duration) -> String {
+        match self {
+            TimerPrecision::Centiseconds => format!("{:02}", time.subsec_millis() / 10),
+            TimerPrecision::Milliseconds => format!("{:03}", time.subsec_millis()),
+        }
+    }
+}
+
+/// How to mark the finished rows and columns (see [`Board::solved_prefix`]).
+///
+/// [`Board::solved_prefix`]: crate::board::Board::solved_prefix
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolvedRegionStyle {
+    #[default]
+    Off,
+    Dim,
+    Outline,
+}
+
+/// What the move log under the board shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoveLogStyle {
+    /// The keys as pressed.
+    #[default]
+    Keys,
+    /// Which way the tiles went, e.g. `R2 U L`.
+    Notation,
+}
+
+/// How pieces get from one cell to the next.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Motion {
+    /// A short eased transition.
+    #[default]
+    Transition,
+    /// A spring, which carries some momentum and settles with a slight bounce.
+    Spring,
+}
+
+/// What the board does when a solve finishes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompletionEffect {
+    Off,
+    /// A highlight sweeping from the top-left corner to the bottom-right.
+    #[default]
+    Wave,
+    /// Every piece swelling briefly at once.
+    Pulse,
+}
+
+/// What to show for the piece under the mouse.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HoverHint {
+    #[default]
+    Off,
+    Target,
+    /// The target plus a Manhattan path to it.
+    Path,
+}
+
+/// What's drawn behind the board. Every kind's values are kept so switching
+/// back and forth doesn't lose them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundSetting {
+    pub kind: BackgroundKind,
+    pub color: String,
+    pub gradient_to: String,
+    pub image_url: String,
+    /// Opacity of the black overlay on top, in percent.
+    pub dim: u8,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackgroundKind {
+    #[default]
+    Default,
+    Color,
+    Gradient,
+    Image,
+}
+
+impl Default for BackgroundSetting {
+    fn default() -> Self {
+        Self {
+            kind: BackgroundKind::Default,
+            color: "#1e1b4b".to_string(),
+            gradient_to: "#4c1d95".to_string(),
+            image_url: String::new(),
+            dim: 30,
+        }
+    }
+}
+
+impl BackgroundSetting {
+    /// The CSS `background` value, or `None` for the theme's own background.
+    pub fn css(&self) -> Option<String> {
+        match self.kind {
+            BackgroundKind::Default => None,
+            BackgroundKind::Color => Some(self.color.clone()),
+            BackgroundKind::Gradient => Some(format!(
+                "linear-gradient(135deg, {}, {})",
+                self.color, self.gradient_to
+            )),
+            BackgroundKind::Image => Some(format!(
+                "center / cover no-repeat url(\"{}\")",
+                self.image_url.replace(['"', '\\', '\n'], "")
+            )),
+        }
+    }
+}
+
+/// Spoken announcements when a solve finishes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeechSetting {
+    pub enabled: bool,
+    /// A `voiceURI`; empty for the browser's default voice.
+    pub voice: String,
+    pub rate: f32,
+}
+
+impl Default for SpeechSetting {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            voice: String::new(),
+            rate: 1.0,
+        }
+    }
+}
+
+/// Anonymous usage counts; off unless turned on.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySetting {
+    pub enabled: bool,
+    /// Where to send them; empty for the one this build was made with.
+    pub endpoint: String,
+}
+
+impl TelemetrySetting {
+    pub fn endpoint(&self) -> &str {
+        match self.endpoint.as_str() {
+            "" => telemetry::DEFAULT_ENDPOINT,
+            endpoint => endpoint,
+        }
+    }
+}
+
+/// A metronome and pacing bar for turn-speed practice.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PacerSetting {
+    pub enabled: bool,
+    /// Target turns (moves) per second.
+    pub tps: f32,
+    pub sound: bool,
+}
+
+impl Default for PacerSetting {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            tps: 4.0,
+            sound: true,
+        }
+    }
+}
+
+pub fn provide_settings() -> RwSignal<Settings> {
+    let settings = create_persisted_signal("settings", Settings::default());
+    provide_context(settings);
+    settings
+}
+
+pub fn use_settings() -> RwSignal<Settings> {
+    expect_context()
+}
+
+#[component]
+pub fn SettingsPanel() -> impl IntoView {
+    let settings = use_settings();
+    let open = create_rw_signal(false);
+    let timer_precision = create_lens(
+        settings,
+        |settings| &settings.timer_precision,
+        |settings| &mut settings.timer_precision,
+    );
+    let scramble = create_lens(
+        settings,
+        |settings| &settings.scramble,
+        |settings| &mut settings.scramble,
+    );
+    let solved_region = create_lens(
+        settings,
+        |settings| &settings.solved_region,
+        |settings| &mut settings.solved_region,
+    );
+    let move_log = create_lens(
+        settings,
+        |settings| &settings.move_log,
+        |settings| &mut settings.move_log,
+    );
+    let move_log_pauses = create_lens(
+        settings,
+        |settings| &settings.move_log_pauses,
+        |settings| &mut settings.move_log_pauses,
+    );
+    let motion = create_lens(
+        settings,
+        |settings| &settings.motion,
+        |settings| &mut settings.motion,
+    );
+    let completion_effect = create_lens(
+        settings,
+        |settings| &settings.completion_effect,
+        |settings| &mut settings.completion_effect,
+    );
+    let hover_hint = create_lens(
+        settings,
+        |settings| &settings.hover_hint,
+        |settings| &mut settings.hover_hint,
+    );
+    let coordinates = create_lens(
+        settings,
+        |settings| &settings.coordinates,
+        |settings| &mut settings.coordinates,
+    );
+    let focus_mode = create_lens(
+        settings,
+        |settings| &settings.focus_mode,
+        |settings| &mut settings.focus_mode,
+    );
+    let pause_when_hidden = create_lens(
+        settings,
+        |settings| &settings.pause_when_hidden,
+        |settings| &mut settings.pause_when_hidden,
+    );
+    let countdown = create_lens(
+        settings,
+        |settings| &settings.countdown,
+        |settings| &mut settings.countdown,
+    );
+    let solve_summary = create_lens(
+        settings,
+        |settings| &settings.solve_summary,
+        |settings| &mut settings.solve_summary,
+    );
+    let plan_markers = create_lens(
+        settings,
+        |settings| &settings.plan_markers,
+        |settings| &mut settings.plan_markers,
+    );
+    let pb_pace = create_lens(
+        settings,
+        |settings| &settings.pb_pace,
+        |settings| &mut settings.pb_pace,
+    );
+    let distance_heat = create_lens(
+        settings,
+        |settings| &settings.distance_heat,
+        |settings| &mut settings.distance_heat,
+    );
+    let scramble_diagram = create_lens(
+        settings,
+        |settings| &settings.scramble_diagram,
+        |settings| &mut settings.scramble_diagram,
+    );
+    let ghost = create_lens(
+        settings,
+        |settings| &settings.ghost,
+        |settings| &mut settings.ghost,
+    );
+    let keymap = create_lens(
+        settings,
+        |settings| &settings.keymap,
+        |settings| &mut settings.keymap,
+    );
+    let shortcuts = create_lens(
+        settings,
+        |settings| &settings.shortcuts,
+        |settings| &mut settings.shortcuts,
+    );
+    // The action waiting for its new key, if any.
+    let rebinding = create_rw_signal(None::<Action>);
+    let shortcut_rows = move || {
+        Action::ALL
+            .into_iter()
+            .filter(|action| cfg!(feature = "devtools") || !action.is_dev())
+            .map(|action| {
+                view! {
+                    <label>
+                        {action.label()}
+                        <button
+                            class="px-2 rounded-md font-mono ring-1 ring-neutral-400/40"
+                            on:click=move |_| rebinding.set(Some(action))
+                            on:blur=move |_| rebinding.set(None)
+                            on:keydown=move |event| {
+                                if rebinding() != Some(action) {
+                                    return;
+                                }
+                                event.prevent_default();
+                                if event.key() == "Escape" {
+                                    rebinding.set(None);
+                                } else if let Some(chord) = Chord::from_event(&event) {
+                                    shortcuts.update(|shortcuts| shortcuts.bind(action, chord));
+                                    rebinding.set(None);
+                                }
+                            }
+                        >
+                            {move || if rebinding() == Some(action) {
+                                "Press a key…".to_owned()
+                            } else {
+                                shortcuts.with(|shortcuts| shortcuts.chord(action).to_string())
+                            }}
+                        </button>
+                    </label>
+                }
+            })
+            .collect_view()
+    };
+    let drag_tiles = create_lens(
+        settings,
+        |settings| &settings.drag_tiles,
+        |settings| &mut settings.drag_tiles,
+    );
+    let mirror_board = create_lens(
+        settings,
+        |settings| &settings.mirror_board,
+        |settings| &mut settings.mirror_board,
+    );
+    let background = create_lens(
+        settings,
+        |settings| &settings.background,
+        |settings| &mut settings.background,
+    );
+    let background_kind = move || background.with(|background| background.kind);
+    let speech = create_lens(
+        settings,
+        |settings| &settings.speech,
+        |settings| &mut settings.speech,
+    );
+    let voices = use_voices();
+    let phase_breakpoints = create_lens(
+        settings,
+        |settings| &settings.phase_breakpoints,
+        |settings| &mut settings.phase_breakpoints,
+    );
+    let pacer = create_lens(
+        settings,
+        |settings| &settings.pacer,
+        |settings| &mut settings.pacer,
+    );
+    let stackmat = create_lens(
+        settings,
+        |settings| &settings.stackmat,
+        |settings| &mut settings.stackmat,
+    );
+    let boards = create_lens(
+        settings,
+        |settings| &settings.boards,
+        |settings| &mut settings.boards,
+    );
+    let telemetry = create_lens(
+        settings,
+        |settings| &settings.telemetry,
+        |settings| &mut settings.telemetry,
+    );
+    let skin = create_lens(
+        settings,
+        |settings| &settings.skin,
+        |settings| &mut settings.skin,
+    );
+    // Remembered across skin changes so switching away and back keeps the image.
+    let skin_image = store_value(settings.with_untracked(|settings| match &settings.skin {
+        PieceSkin::ImageSlice { url } => url.clone(),
+        _ => String::new(),
+    }));
+    let skin_name = move || {
+        skin.with(|skin| match skin {
+            PieceSkin::Flat => "flat",
+            PieceSkin::Beveled => "beveled",
+            PieceSkin::Glassy => "glassy",
+            PieceSkin::Emoji => "emoji",
+            PieceSkin::ImageSlice { .. } => "image",
+        })
+    };
+
+    view! {
+        <button
+            class="fixed top-3 right-3 w-8 h-8 rounded-md text-xl opacity-60 hover:opacity-100"
+            title="Settings"
+            on:click=move |_| open.update(|open| *open = !*open)
+        >
+            "⚙"
+        </button>
+        <Show when=open fallback=|| ()>
+            <div class="fixed top-12 right-3 w-72 max-h-[calc(100dvh_-_4rem)] overflow-y-auto p-4 rounded-lg shadow-lg bg-neutral-100 dark:bg-neutral-800
+                        flex flex-col gap-3 text-sm child:flex child:justify-between child:items-center">
+                <label>
+                    "Timer precision"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| timer_precision.set(match event_target_value(&event).as_str() {
+                            "cs" => TimerPrecision::Centiseconds,
+                            _ => TimerPrecision::Milliseconds,
+                        })
+                    >
+                        <option value="ms" selected=move || timer_precision.get() == TimerPrecision::Milliseconds>"1/1000 s"</option>
+                        <option value="cs" selected=move || timer_precision.get() == TimerPrecision::Centiseconds>"1/100 s"</option>
+                    </select>
+                </label>
+                <label>
+                    "Scramble"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| {
+                            let random_moves = event_target_value(&event) == "moves";
+                            scramble.update(|scramble| scramble.random_moves = random_moves);
+                        }
+                    >
+                        <option value="state" selected=move || !scramble.get().random_moves>"Random state"</option>
+                        <option value="moves" selected=move || scramble.get().random_moves>"Random moves"</option>
+                    </select>
+                </label>
+                <Show when=move || scramble.get().random_moves fallback=|| ()>
+                    <label>
+                        "Scramble length"
+                        <input
+                            type="number"
+                            min="1"
+                            max="10000"
+                            class="w-20 bg-transparent text-right"
+                            prop:value=move || scramble.get().length
+                            on:change=move |event| {
+                                if let Ok(length @ 1..) = event_target_value(&event).parse::<u16>() {
+                                    scramble.update(|scramble| scramble.length = length);
+                                }
+                            }
+                        />
+                    </label>
+                </Show>
+                <label>
+                    "Goal"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| {
+                            if let Some(&goal) = event_target_value(&event)
+                                .parse::<usize>()
+                                .ok()
+                                .and_then(|i| Arrangement::ALL.get(i))
+                            {
+                                scramble.update(|scramble| scramble.goal = goal);
+                            }
+                        }
+                    >
+                        {Arrangement::ALL
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, goal)| view! {
+                                <option value=i selected=move || scramble.get().goal == goal>{goal.label()}</option>
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label>
+                    "Finished rows/columns"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| solved_region.set(match event_target_value(&event).as_str() {
+                            "dim" => SolvedRegionStyle::Dim,
+                            "outline" => SolvedRegionStyle::Outline,
+                            _ => SolvedRegionStyle::Off,
+                        })
+                    >
+                        <option value="off" selected=move || solved_region.get() == SolvedRegionStyle::Off>"Don't mark"</option>
+                        <option value="dim" selected=move || solved_region.get() == SolvedRegionStyle::Dim>"Dim"</option>
+                        <option value="outline" selected=move || solved_region.get() == SolvedRegionStyle::Outline>"Outline"</option>
+                    </select>
+                </label>
+                <label>
+                    "Move log"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| move_log.set(match event_target_value(&event).as_str() {
+                            "notation" => MoveLogStyle::Notation,
+                            _ => MoveLogStyle::Keys,
+                        })
+                    >
+                        <option value="keys" selected=move || move_log.get() == MoveLogStyle::Keys>"Keys"</option>
+                        <option value="notation" selected=move || move_log.get() == MoveLogStyle::Notation>"Notation"</option>
+                    </select>
+                </label>
+                <label title="Amber after 0.3 s, red after 1 s">
+                    "Mark pauses in the log"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || move_log_pauses.get()
+                        on:change=move |event| move_log_pauses.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Piece motion"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| motion.set(match event_target_value(&event).as_str() {
+                            "spring" => Motion::Spring,
+                            _ => Motion::Transition,
+                        })
+                    >
+                        <option value="transition" selected=move || motion.get() == Motion::Transition>"Ease"</option>
+                        <option value="spring" selected=move || motion.get() == Motion::Spring>"Spring"</option>
+                    </select>
+                </label>
+                <label>
+                    "When solved"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| completion_effect.set(match event_target_value(&event).as_str() {
+                            "wave" => CompletionEffect::Wave,
+                            "pulse" => CompletionEffect::Pulse,
+                            _ => CompletionEffect::Off,
+                        })
+                    >
+                        <option value="off" selected=move || completion_effect.get() == CompletionEffect::Off>"Do nothing"</option>
+                        <option value="wave" selected=move || completion_effect.get() == CompletionEffect::Wave>"Wave"</option>
+                        <option value="pulse" selected=move || completion_effect.get() == CompletionEffect::Pulse>"Pulse"</option>
+                    </select>
+                </label>
+                <label>
+                    "On hover, show"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| hover_hint.set(match event_target_value(&event).as_str() {
+                            "target" => HoverHint::Target,
+                            "path" => HoverHint::Path,
+                            _ => HoverHint::Off,
+                        })
+                    >
+                        <option value="off" selected=move || hover_hint.get() == HoverHint::Off>"Nothing"</option>
+                        <option value="target" selected=move || hover_hint.get() == HoverHint::Target>"Target cell"</option>
+                        <option value="path" selected=move || hover_hint.get() == HoverHint::Path>"Target and path"</option>
+                    </select>
+                </label>
+                <label>
+                    "Distance heatmap"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || distance_heat.get()
+                        on:change=move |event| distance_heat.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Coordinates and gridlines"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || coordinates.get()
+                        on:change=move |event| coordinates.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Keys"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| {
+                            keymap.set(match event_target_value(&event).as_str() {
+                                "mirrored" => Keymap::Mirrored,
+                                "left" => Keymap::LeftHand,
+                                "right" => Keymap::RightHand,
+                                _ => Keymap::Standard,
+                            });
+                        }
+                    >
+                        <option value="standard" selected=move || keymap.get() == Keymap::Standard>"Standard"</option>
+                        <option value="mirrored" selected=move || keymap.get() == Keymap::Mirrored>"Mirrored"</option>
+                        <option value="left" selected=move || keymap.get() == Keymap::LeftHand>"Left hand only"</option>
+                        <option value="right" selected=move || keymap.get() == Keymap::RightHand>"Right hand only"</option>
+                    </select>
+                </label>
+                {shortcut_rows}
+                <button
+                    class="self-end text-xs opacity-60 hover:opacity-100"
+                    on:click=move |_| shortcuts.set(Shortcuts::default())
+                >
+                    "Reset shortcuts"
+                </button>
+                <label>
+                    "Mirror board"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || mirror_board.get()
+                        on:change=move |event| mirror_board.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Drag tiles"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || drag_tiles.get()
+                        on:change=move |event| drag_tiles.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Scramble diagram"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || scramble_diagram.get()
+                        on:change=move |event| scramble_diagram.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Ghost board (training)"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || ghost.get()
+                        on:change=move |event| ghost.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Focus mode"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || focus_mode.get()
+                        on:change=move |event| focus_mode.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Pause when the tab is hidden"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || pause_when_hidden.get()
+                        on:change=move |event| pause_when_hidden.set(event_target_checked(&event))
+                    />
+                </label>
+                <label title="Beeps on each count; the timer starts on the last">
+                    "Countdown before each scramble"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || countdown.get()
+                        on:change=move |event| countdown.set(event_target_checked(&event))
+                    />
+                </label>
+                <label title="For streaming overlays and screenshots">
+                    "Latest solve in the title"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || solve_summary.get()
+                        on:change=move |event| solve_summary.set(event_target_checked(&event))
+                    />
+                </label>
+                <label title="Click tiles to number the moves you plan; keys still move">
+                    "Plan markers during inspection"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || plan_markers.get()
+                        on:change=move |event| plan_markers.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Pieces"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| skin.set(match event_target_value(&event).as_str() {
+                            "beveled" => PieceSkin::Beveled,
+                            "glassy" => PieceSkin::Glassy,
+                            "emoji" => PieceSkin::Emoji,
+                            "image" => PieceSkin::ImageSlice { url: skin_image.get_value() },
+                            _ => PieceSkin::Flat,
+                        })
+                    >
+                        <option value="flat" selected=move || skin_name() == "flat">"Flat"</option>
+                        <option value="beveled" selected=move || skin_name() == "beveled">"Beveled"</option>
+                        <option value="glassy" selected=move || skin_name() == "glassy">"Glassy"</option>
+                        <option value="emoji" selected=move || skin_name() == "emoji">"Emoji"</option>
+                        <option value="image" selected=move || skin_name() == "image">"Picture"</option>
+                    </select>
+                </label>
+                <Show when=move || skin_name() == "image" fallback=|| ()>
+                    <label>
+                        "Picture URL"
+                        <input
+                            type="url"
+                            class="w-40 bg-transparent ring-1 ring-neutral-400 dark:ring-neutral-600 rounded px-1"
+                            prop:value=move || skin.with(|skin| match skin {
+                                PieceSkin::ImageSlice { url } => url.clone(),
+                                _ => String::new(),
+                            })
+                            on:change=move |event| {
+                                let url = event_target_value(&event);
+                                skin_image.set_value(url.clone());
+                                skin.set(PieceSkin::ImageSlice { url });
+                            }
+                        />
+                    </label>
+                </Show>
+                <label>
+                    "Phase breakpoints"
+                    <input
+                        type="text"
+                        class="w-24 bg-transparent ring-1 ring-neutral-400 dark:ring-neutral-600 rounded px-1 text-right"
+                        placeholder="by rows"
+                        title="Tile counts, e.g. 4, 8"
+                        prop:value=move || phase_breakpoints.with(|breakpoints| {
+                            breakpoints.iter().map(usize::to_string).collect::<Vec<_>>().join(", ")
+                        })
+                        on:change=move |event| {
+                            let breakpoints = event_target_value(&event)
+                                .split(',')
+                                .filter_map(|n| n.trim().parse().ok())
+                                .collect();
+                            phase_breakpoints.set(breakpoints);
+                        }
+                    />
+                </label>
+                <label>
+                    "Pacer"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || pacer.get().enabled
+                        on:change=move |event| pacer.update(|pacer| pacer.enabled = event_target_checked(&event))
+                    />
+                </label>
+                <Show when=move || pacer.get().enabled fallback=|| ()>
+                    <label>
+                        "Target TPS"
+                        <input
+                            type="number"
+                            min="0.5"
+                            max="30"
+                            step="0.5"
+                            class="w-20 bg-transparent text-right"
+                            prop:value=move || pacer.get().tps
+                            on:change=move |event| {
+                                match event_target_value(&event).parse::<f32>() {
+                                    Ok(tps) if tps > 0.0 => pacer.update(|pacer| pacer.tps = tps),
+                                    _ => {}
+                                }
+                            }
+                        />
+                    </label>
+                    <label>
+                        "Metronome sound"
+                        <input
+                            type="checkbox"
+                            prop:checked=move || pacer.get().sound
+                            on:change=move |event| pacer.update(|pacer| pacer.sound = event_target_checked(&event))
+                        />
+                    </label>
+                </Show>
+                <label title="Needs a PB on this shape with its moves recorded">
+                    "Pace against PB"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || pb_pace.get()
+                        on:change=move |event| pb_pace.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Announce results"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || speech.with(|speech| speech.enabled)
+                        on:change=move |event| speech.update(|speech| speech.enabled = event_target_checked(&event))
+                    />
+                </label>
+                <Show when=move || speech.with(|speech| speech.enabled) fallback=|| ()>
+                    <label>
+                        "Voice"
+                        <select
+                            class="w-40 bg-transparent"
+                            on:change=move |event| speech.update(|speech| speech.voice = event_target_value(&event))
+                        >
+                            <option value="" selected=move || speech.with(|speech| speech.voice.is_empty())>"Default"</option>
+                            <For
+                                each=voices
+                                key=|voice| voice.uri.clone()
+                                children=move |voice| {
+                                    let uri = voice.uri.clone();
+                                    view! {
+                                        <option
+                                            value=voice.uri
+                                            selected=move || speech.with(|speech| speech.voice == uri)
+                                        >
+                                            {voice.label}
+                                        </option>
+                                    }
+                                }
+                            />
+                        </select>
+                    </label>
+                    <label>
+                        "Speech rate"
+                        <input
+                            type="range"
+                            min="0.5"
+                            max="2"
+                            step="0.1"
+                            prop:value=move || speech.with(|speech| speech.rate)
+                            on:change=move |event| {
+                                if let Ok(rate) = event_target_value(&event).parse() {
+                                    speech.update(|speech| speech.rate = rate);
+                                }
+                            }
+                        />
+                    </label>
+                </Show>
+                <label>
+                    "Boards at once"
+                    <input
+                        type="number"
+                        min="1"
+                        max=MAX_BOARDS
+                        class="w-12 bg-transparent text-right"
+                        prop:value=move || boards.get().clamp(1, MAX_BOARDS)
+                        on:change=move |event| {
+                            if let Ok(count) = event_target_value(&event).parse::<u8>() {
+                                boards.set(count.clamp(1, MAX_BOARDS));
+                            }
+                        }
+                    />
+                </label>
+                <label>
+                    "Stackmat input (experimental)"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || stackmat.get()
+                        on:change=move |event| stackmat.set(event_target_checked(&event))
+                    />
+                </label>
+                <label>
+                    "Background"
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| {
+                            let kind = match event_target_value(&event).as_str() {
+                                "color" => BackgroundKind::Color,
+                                "gradient" => BackgroundKind::Gradient,
+                                "image" => BackgroundKind::Image,
+                                _ => BackgroundKind::Default,
+                            };
+                            background.update(|background| background.kind = kind);
+                        }
+                    >
+                        <option value="default" selected=move || background_kind() == BackgroundKind::Default>"Theme"</option>
+                        <option value="color" selected=move || background_kind() == BackgroundKind::Color>"Color"</option>
+                        <option value="gradient" selected=move || background_kind() == BackgroundKind::Gradient>"Gradient"</option>
+                        <option value="image" selected=move || background_kind() == BackgroundKind::Image>"Image"</option>
+                    </select>
+                </label>
+                <Show
+                    when=move || matches!(background_kind(), BackgroundKind::Color | BackgroundKind::Gradient)
+                    fallback=|| ()
+                >
+                    <label>
+                        "Colors"
+                        <span class="flex gap-1">
+                            <input
+                                type="color"
+                                prop:value=move || background.with(|background| background.color.clone())
+                                on:input=move |event| background.update(|background| background.color = event_target_value(&event))
+                            />
+                            <Show when=move || background_kind() == BackgroundKind::Gradient fallback=|| ()>
+                                <input
+                                    type="color"
+                                    prop:value=move || background.with(|background| background.gradient_to.clone())
+                                    on:input=move |event| background.update(|background| background.gradient_to = event_target_value(&event))
+                                />
+                            </Show>
+                        </span>
+                    </label>
+                </Show>
+                <Show when=move || background_kind() == BackgroundKind::Image fallback=|| ()>
+                    <label>
+                        "Image URL"
+                        <input
+                            type="url"
+                            class="w-40 bg-transparent ring-1 ring-neutral-400 dark:ring-neutral-600 rounded px-1"
+                            prop:value=move || background.with(|background| background.image_url.clone())
+                            on:change=move |event| background.update(|background| background.image_url = event_target_value(&event))
+                        />
+                    </label>
+                </Show>
+                <Show when=move || background_kind() != BackgroundKind::Default fallback=|| ()>
+                    <label>
+                        "Darken"
+                        <input
+                            type="range"
+                            min="0"
+                            max="80"
+                            prop:value=move || background.with(|background| background.dim)
+                            on:input=move |event| {
+                                if let Ok(dim) = event_target_value(&event).parse() {
+                                    background.update(|background| background.dim = dim);
+                                }
+                            }
+                        />
+                    </label>
+                </Show>
+                <label title="Counts of solved puzzle sizes and switched options, with no identifier, scrambles or times">
+                    "Send anonymous usage data"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || telemetry.with(|telemetry| telemetry.enabled)
+                        on:change=move |event| telemetry.update(|telemetry| telemetry.enabled = event_target_checked(&event))
+                    />
+                </label>
+                <Show when=move || telemetry.with(|telemetry| telemetry.enabled) fallback=|| ()>
+                    <label>
+                        "Endpoint"
+                        <input
+                            type="url"
+                            class="w-40 bg-transparent ring-1 ring-neutral-400 dark:ring-neutral-600 rounded px-1"
+                            placeholder=telemetry::DEFAULT_ENDPOINT
+                            prop:value=move || telemetry.with(|telemetry| telemetry.endpoint.clone())
+                            on:change=move |event| telemetry.update(|telemetry| telemetry.endpoint = event_target_value(&event))
+                        />
+                    </label>
+                </Show>
+                <BundleControls />
+            </div>
+        </Show>
+    }
+}
+
+use std::time::Duration;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::arrangement::Arrangement;
+use crate::bundle::BundleControls;
+use crate::keymap::{Action, Chord, Keymap, Shortcuts};
+use crate::seed::{Scramble, ScrambleMode};
+use crate::signal_ext::{create_lens, create_persisted_signal};
+use crate::skin::PieceSkin;
+use crate::speech::use_voices;
+use crate::telemetry;
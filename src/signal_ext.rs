@@ -1,8 +1,19 @@
 use core::cell::Cell;
 use core::ops::{Deref, DerefMut};
-use leptos::{SignalUpdate, SignalUpdateUntracked};
+use core::time::Duration;
+use leptos::{
+    create_effect, create_memo, create_rw_signal, set_timeout, set_timeout_with_handle,
+    store_value, window, Memo, ReadSignal, RwSignal, Signal, SignalGet, SignalGetUntracked,
+    SignalSet, SignalUpdate, SignalUpdateUntracked, SignalWith, TimeoutHandle,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::VecDeque;
 use std::rc::Rc;
 
+use crate::clock::{use_clock, Instant};
+use crate::migrations;
+use crate::profile::scoped_key;
+
 pub struct UpdateGuard<'a, T: ?Sized> {
     inner: &'a mut T,
     updated: Rc<Cell<bool>>,
@@ -59,5 +70,297 @@ pub trait SignalUpdateConditional<T>: SignalUpdate<Value = T> + SignalUpdateUntr
             _ => false,
         }
     }
+    fn update_if_changed(&self, f: impl FnOnce(&mut Self::Value)) -> bool
+    where
+        Self::Value: Clone + PartialEq,
+    {
+        self.update_if(move |value| {
+            let mut next = value.clone();
+            f(&mut next);
+
+            match next != *value {
+                true => {
+                    *value = next;
+                    true
+                }
+                false => false,
+            }
+        })
+    }
 }
 impl<T, S: SignalUpdate<Value = T> + SignalUpdateUntracked<T>> SignalUpdateConditional<T> for S {}
+
+struct UndoHistory<T> {
+    past: VecDeque<T>,
+    future: Vec<T>,
+}
+
+/// An `RwSignal` that remembers up to `limit` previous values.
+pub struct UndoableRwSignal<T: 'static> {
+    value: RwSignal<T>,
+    history: RwSignal<UndoHistory<T>>,
+    limit: usize,
+}
+impl<T> Clone for UndoableRwSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for UndoableRwSignal<T> {}
+impl<T: Clone> UndoableRwSignal<T> {
+    pub fn new(value: T, limit: usize) -> Self {
+        Self {
+            value: create_rw_signal(value),
+            history: create_rw_signal(UndoHistory {
+                past: VecDeque::new(),
+                future: Vec::new(),
+            }),
+            limit,
+        }
+    }
+
+    pub fn read_only(&self) -> ReadSignal<T> {
+        self.value.read_only()
+    }
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.value.with(f)
+    }
+
+    pub fn set(&self, value: T) {
+        self.update(move |v| *v = value);
+    }
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let previous = self.value.get_untracked();
+        self.history.update(|history| {
+            history.past.push_back(previous);
+            if history.past.len() > self.limit {
+                history.past.pop_front();
+            }
+            history.future.clear();
+        });
+        self.value.update(f);
+    }
+    /// Replaces the value without recording the previous one.
+    pub fn set_without_history(&self, value: T) {
+        self.value.set(value);
+    }
+    pub fn clear_history(&self) {
+        self.history.update(|history| {
+            history.past.clear();
+            history.future.clear();
+        });
+    }
+
+    pub fn undo(&self) -> bool {
+        let current = self.value.get_untracked();
+        let previous = self.history.update_if_some(move |history| {
+            let previous = history.past.pop_back()?;
+            history.future.push(current);
+            Some(previous)
+        });
+
+        match previous {
+            Some(previous) => {
+                self.value.set(previous);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn redo(&self) -> bool {
+        let current = self.value.get_untracked();
+        let next = self.history.update_if_some(move |history| {
+            let next = history.future.pop()?;
+            history.past.push_back(current);
+            Some(next)
+        });
+
+        match next {
+            Some(next) => {
+                self.value.set(next);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_undo(&self) -> Signal<bool> {
+        let history = self.history;
+        Signal::derive(move || history.with(|history| !history.past.is_empty()))
+    }
+    pub fn can_redo(&self) -> Signal<bool> {
+        let history = self.history;
+        Signal::derive(move || history.with(|history| !history.future.is_empty()))
+    }
+}
+
+const PERSIST_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Creates a signal backed by `localStorage[key]`, falling back to `default` when
+/// nothing (or nothing parseable) is stored. Writes are debounced.
+pub fn create_persisted_signal<T>(key: impl Into<Rc<str>>, default: T) -> RwSignal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let key: Rc<str> = key.into();
+    let signal = create_rw_signal(load_persisted(&key).unwrap_or(default));
+    let settled = debounced(signal, PERSIST_DEBOUNCE);
+
+    create_effect(move |initialized: Option<()>| {
+        settled.with(|value| {
+            if initialized.is_some() {
+                store_persisted(&key, value);
+            }
+        });
+    });
+
+    signal
+}
+
+/// Reads `key` from the active profile's storage.
+pub fn load_persisted<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let raw = window()
+        .local_storage()
+        .ok()??
+        .get_item(&scoped_key(key))
+        .ok()??;
+
+    match serde_json::from_str(&raw) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            // Kept aside so a bad migration or a bug can't lose it for good.
+            log::warn!("can't read persisted {key:?}, keeping it as {key:?}.unreadable: {err}");
+            if let Some(storage) = window().local_storage().ok().flatten() {
+                let _ = storage.set_item(&scoped_key(&format!("{key}.unreadable")), &raw);
+            }
+            None
+        }
+    }
+}
+
+pub fn store_persisted<T: Serialize>(key: &str, value: &T) {
+    if migrations::is_read_only() {
+        return;
+    }
+    let Some(storage) = window().local_storage().ok().flatten() else {
+        return;
+    };
+
+    match serde_json::to_string(value) {
+        Ok(raw) => {
+            if storage.set_item(&scoped_key(key), &raw).is_err() {
+                log::warn!("failed to persist {key:?}");
+            }
+        }
+        Err(err) => log::warn!("failed to serialize {key:?}: {err}"),
+    }
+}
+
+/// Follows `source`, but only after it has stopped changing for `delay`.
+pub fn debounced<T: Clone + 'static>(source: impl Into<Signal<T>>, delay: Duration) -> Signal<T> {
+    let source = source.into();
+    let output = create_rw_signal(source.get_untracked());
+    let pending = store_value(None::<TimeoutHandle>);
+
+    create_effect(move |initialized: Option<()>| {
+        source.with(|_| {});
+        if initialized.is_none() {
+            return;
+        }
+
+        if let Some(handle) = pending.get_value() {
+            handle.clear();
+        }
+        let handle = set_timeout_with_handle(move || output.set(source.get_untracked()), delay);
+        pending.set_value(handle.ok());
+    });
+
+    output.into()
+}
+
+/// Follows `source`, updating at most once per `interval` (including a trailing update).
+pub fn throttled<T: Clone + 'static>(
+    source: impl Into<Signal<T>>,
+    interval: Duration,
+) -> Signal<T> {
+    let source = source.into();
+    let output = create_rw_signal(source.get_untracked());
+    let clock = use_clock();
+    let last_emit = store_value(None::<Instant>);
+    let pending = store_value(false);
+
+    create_effect(move |initialized: Option<()>| {
+        source.with(|_| {});
+        if initialized.is_none() || pending.get_value() {
+            return;
+        }
+
+        let elapsed = last_emit
+            .get_value()
+            .map_or(interval, |last_emit| clock.elapsed(last_emit));
+        let emit = move || {
+            pending.set_value(false);
+            last_emit.set_value(Some(clock.now()));
+            output.set(source.get_untracked());
+        };
+
+        match interval.checked_sub(elapsed) {
+            None | Some(Duration::ZERO) => emit(),
+            Some(remaining) => {
+                pending.set_value(true);
+                set_timeout(emit, remaining);
+            }
+        }
+    });
+
+    output.into()
+}
+
+/// A read/write handle onto one field of a larger signal. Readers are only
+/// notified when the projected field itself changes.
+pub struct Lens<T: 'static, F: 'static> {
+    source: RwSignal<T>,
+    value: Memo<F>,
+    project_mut: fn(&mut T) -> &mut F,
+}
+impl<T, F> Clone for Lens<T, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T, F> Copy for Lens<T, F> {}
+impl<T, F: Clone + PartialEq> Lens<T, F> {
+    pub fn get(&self) -> F {
+        self.value.get()
+    }
+    pub fn with<R>(&self, f: impl FnOnce(&F) -> R) -> R {
+        self.value.with(f)
+    }
+    pub fn set(&self, value: F) {
+        let project_mut = self.project_mut;
+        self.source
+            .update(move |source| *project_mut(source) = value);
+    }
+    pub fn update(&self, f: impl FnOnce(&mut F)) {
+        let project_mut = self.project_mut;
+        self.source.update(move |source| f(project_mut(source)));
+    }
+    pub fn signal(&self) -> Signal<F> {
+        self.value.into()
+    }
+}
+
+pub fn create_lens<T, F: Clone + PartialEq>(
+    source: RwSignal<T>,
+    project: fn(&T) -> &F,
+    project_mut: fn(&mut T) -> &mut F,
+) -> Lens<T, F> {
+    Lens {
+        source,
+        value: create_memo(move |_| source.with(|source| project(source).clone())),
+        project_mut,
+    }
+}
@@ -0,0 +1,151 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimResult {
+    /// The final arrangement, row by row.
+    pub pieces: Vec<usize>,
+    pub solved: bool,
+    pub state: GameState,
+    /// The keys that moved at least one piece, as they'd appear in the history box.
+    pub history: String,
+    pub moves: usize,
+    pub pieces_moved: usize,
+    /// Keys that weren't mapped or didn't move anything.
+    pub ignored: usize,
+}
+
+/// Replays `keys` against `scramble` with the same keymap, sliding and state
+/// machine as the app, but without any DOM or reactive runtime.
+pub fn simulate<K: AsRef<str>>(
+    scramble: Scramble,
+    keys: impl IntoIterator<Item = (K, Instant)>,
+) -> SimResult {
+    let mut puzzle = scramble.puzzle();
+    let mut state = GameState::NotSolving;
+    let mut history = String::new();
+    let (mut moves, mut pieces_moved, mut ignored) = (0, 0, 0);
+
+    for (key, at) in keys {
+        let key = key.as_ref();
+        let moved = keymap::lookup(key).and_then(|idx| puzzle.slide_from(idx));
+
+        match moved {
+            Some(moved @ 1..) => {
+                state.apply(GameEvent::Move {
+                    at,
+                    solved: puzzle.is_solved(),
+                });
+                history.push_str(key);
+                moves += 1;
+                pieces_moved += moved;
+            }
+            _ => ignored += 1,
+        }
+    }
+
+    SimResult {
+        pieces: puzzle.iter_indexed().map(|(_, piece)| piece).collect(),
+        solved: puzzle.is_solved(),
+        state,
+        history,
+        moves,
+        pieces_moved,
+        ignored,
+    }
+}
+
+use crate::clock::Instant;
+use crate::game_state::{GameEvent, GameState};
+use crate::keymap;
+use crate::seed::Scramble;
+
+#[cfg(test)]
+mod tests {
+    const SHAPE: (usize, usize) = (3, 3);
+
+    fn timed<'a>(keys: impl IntoIterator<Item = &'a str>) -> Vec<(&'a str, Instant)> {
+        keys.into_iter()
+            .enumerate()
+            .map(|(i, key)| (key, Instant::from_millis_f64(i as f64 * 100.0)))
+            .collect()
+    }
+
+    /// The keys of an optimal solution to `scramble`.
+    fn solution(scramble: Scramble) -> Vec<&'static str> {
+        let evaluation = solver::evaluate(
+            &scramble.puzzle().pieces(),
+            scramble.shape,
+            scramble.goal,
+            usize::MAX,
+        );
+        assert!(evaluation.exact);
+        evaluation
+            .solution
+            .into_iter()
+            .map(|cell| {
+                let (key, _) = Keymap::default()
+                    .entries()
+                    .find(|&(_, at)| at == cell)
+                    .unwrap();
+                key
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pieces_are_never_lost_or_duplicated() {
+        let mut keys: Vec<&str> = Keymap::default().entries().map(|(key, _)| key).collect();
+        keys.extend(["q", "Enter", ""]);
+
+        for seed in 0..20 {
+            let mut rng = Xoshiro256StarStar::seed_from_u64(seed);
+            let sequence: Vec<&str> = (0..200).map(|_| *keys.choose(&mut rng).unwrap()).collect();
+            let result = simulate(Scramble::new(SHAPE, [seed as u8; 32]), timed(sequence));
+
+            let mut pieces = result.pieces.clone();
+            pieces.sort_unstable();
+            assert_eq!(pieces, (0..SHAPE.0 * SHAPE.1).collect::<Vec<_>>());
+            assert_eq!(result.moves + result.ignored, 200);
+        }
+    }
+
+    #[test]
+    fn a_solution_solves() {
+        let scramble = Scramble::new(SHAPE, [7; 32]);
+        let keys = solution(scramble);
+        let result = simulate(scramble, timed(keys.iter().copied()));
+
+        assert!(result.solved);
+        assert_eq!(result.moves, keys.len());
+        assert_eq!(result.history, keys.concat());
+        assert_eq!(
+            result.state,
+            GameState::Solved {
+                took: Duration::from_millis((keys.len() as u64 - 1) * 100),
+            }
+        );
+    }
+
+    #[test]
+    fn moves_after_the_solve_dont_count() {
+        let scramble = Scramble::new(SHAPE, [7; 32]);
+        let keys = solution(scramble);
+        let solved = simulate(scramble, timed(keys.iter().copied()));
+
+        // every key once more, some of which slide
+        let mut more = keys.clone();
+        more.extend(Keymap::default().entries().map(|(key, _)| key));
+        let result = simulate(scramble, timed(more.iter().copied()));
+
+        assert_eq!(result.state, solved.state);
+        assert!(result.moves > solved.moves);
+    }
+
+    use core::time::Duration;
+
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoshiro256StarStar;
+
+    use super::*;
+    use crate::keymap::Keymap;
+    use crate::solver;
+}
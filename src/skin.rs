@@ -0,0 +1,69 @@
+/// How a tile looks. `render_piece` only positions tiles; everything visual
+/// comes from here, so adding a skin doesn't touch the game logic.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PieceSkin {
+    #[default]
+    Flat,
+    Beveled,
+    Glassy,
+    Emoji,
+    /// Each tile shows its slice of the image at `url`, as in a picture puzzle.
+    ImageSlice {
+        url: String,
+    },
+}
+
+impl PieceSkin {
+    /// Classes for a (non-blank) tile; `in_place` is whether it's on its home cell.
+    pub fn classes(&self, in_place: bool) -> &'static str {
+        match (self, in_place) {
+            (PieceSkin::Flat, true) => "rounded-lg font-mono text-2xl shadow bg-neutral-100 dark:bg-neutral-200 text-neutral-800",
+            (PieceSkin::Flat, false) => "rounded-lg font-mono text-2xl shadow bg-neutral-900 dark:bg-neutral-800 text-neutral-200",
+            (PieceSkin::Beveled, true) => "rounded-md font-mono text-2xl font-bold shadow-md border-4 border-t-white border-l-white border-b-neutral-400 border-r-neutral-400 bg-neutral-200 text-neutral-800",
+            (PieceSkin::Beveled, false) => "rounded-md font-mono text-2xl font-bold shadow-md border-4 border-t-neutral-600 border-l-neutral-600 border-b-black border-r-black bg-neutral-800 text-neutral-200",
+            (PieceSkin::Glassy, true) => "rounded-2xl font-mono text-2xl shadow-lg backdrop-blur bg-white/60 ring-1 ring-white/70 text-neutral-800",
+            (PieceSkin::Glassy, false) => "rounded-2xl font-mono text-2xl shadow-lg backdrop-blur bg-neutral-900/40 ring-1 ring-white/20 text-white",
+            (PieceSkin::Emoji, true) => "rounded-lg text-4xl shadow bg-emerald-100 dark:bg-emerald-200",
+            (PieceSkin::Emoji, false) => "rounded-lg text-4xl shadow bg-neutral-100 dark:bg-neutral-700",
+            (PieceSkin::ImageSlice { .. }, true) => "rounded-sm font-mono text-xs text-white/70 items-end justify-end p-1",
+            (PieceSkin::ImageSlice { .. }, false) => "rounded-sm font-mono text-xs text-white/70 items-end justify-end p-1 brightness-75",
+        }
+    }
+
+    /// What's written on the tile.
+    pub fn label(&self, piece: usize) -> String {
+        match self {
+            PieceSkin::Emoji => EMOJI[(piece - 1) % EMOJI.len()].to_string(),
+            _ => piece.to_string(),
+        }
+    }
+
+    /// The CSS `background` for the tile whose home is `(x, y)`, if the skin has one.
+    pub fn background(
+        &self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+    ) -> Option<String> {
+        let PieceSkin::ImageSlice { url } = self else {
+            return None;
+        };
+
+        // Positions are percentages of the space left over, hence the `- 1`.
+        let percent = |i: usize, n: usize| i as f64 * 100.0 / (n.max(2) - 1) as f64;
+        Some(format!(
+            "url(\"{}\") {}% {}% / {}% {}%",
+            url.replace(['"', '\\', '\n'], ""),
+            percent(x, width),
+            percent(y, height),
+            width * 100,
+            height * 100,
+        ))
+    }
+}
+
+const EMOJI: &[&str] = &[
+    "🍎", "🍊", "🍋", "🍉", "🍇", "🍓", "🍒", "🍑", "🥝", "🍍", "🥥", "🍅", "🍆", "🥑", "🥕", "🌽",
+    "🥦", "🍄", "🥜", "🌰", "🍞", "🧀", "🥚", "🍤",
+];
+
+use serde::{Deserialize, Serialize};
@@ -0,0 +1,305 @@
+//! Solves live in IndexedDB, one record per solve, since a few thousand of
+//! them with their move logs would outgrow localStorage. Everything else is
+//! small and stays in localStorage.
+//!
+//! The solve list is still held in memory as one signal; this module loads it
+//! once at startup and writes back only the records that changed.
+
+const DB_NAME: &str = "jugo";
+const DB_VERSION: u32 = 2;
+const STORE: &str = "solves";
+/// Each profile's [`SOLVES_VERSION`], keyed by profile.
+const META: &str = "meta";
+/// Where solves were kept before, migrated on first load.
+const LEGACY_KEY: &str = "solves";
+
+/// Records are keyed `[profile, finished_at, id]`, so a profile's solves come
+/// back in the order they were finished, and solves finished at the same
+/// moment still get a record each.
+fn record_key(profile: &str, solve: &Solve) -> JsValue {
+    Array::of3(
+        &profile.into(),
+        &solve.finished_at.into(),
+        &(solve.id as f64).into(),
+    )
+    .into()
+}
+
+/// Every record of `profile`.
+fn profile_range(profile: &str) -> Result<IdbKeyRange, JsValue> {
+    IdbKeyRange::bound(
+        &Array::of2(&profile.into(), &f64::NEG_INFINITY.into()),
+        &Array::of2(&profile.into(), &f64::INFINITY.into()),
+    )
+}
+
+/// Resolves once `request` succeeds, with its result.
+async fn settle(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let on_success = Closure::once_into_js(move || _ = resolve.call0(&JsValue::NULL));
+        let on_error = Closure::once_into_js(move || _ = reject.call0(&JsValue::NULL));
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise).await?;
+    request.result()
+}
+
+async fn open() -> Result<IdbDatabase, JsValue> {
+    let factory = window()
+        .indexed_db()?
+        .ok_or_else(|| JsValue::from_str("IndexedDB unavailable"))?;
+    let request = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+
+    let on_upgrade =
+        Closure::<dyn Fn(IdbVersionChangeEvent)>::new(|event: IdbVersionChangeEvent| {
+            return_with_try! {
+                let request: IdbOpenDbRequest = event.target()?.dyn_into()?;
+                let db: IdbDatabase = request.result()?.dyn_into()?;
+                for name in [STORE, META] {
+                    if !db.object_store_names().contains(name) {
+                        db.create_object_store(name)?;
+                    }
+                }
+            }
+        });
+    request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+
+    let db = settle(&request).await?.dyn_into()?;
+    drop(on_upgrade);
+    Ok(db)
+}
+
+fn store(
+    db: &IdbDatabase,
+    name: &str,
+    mode: IdbTransactionMode,
+) -> Result<IdbObjectStore, JsValue> {
+    db.transaction_with_str_and_mode(name, mode)?
+        .object_store(name)
+}
+
+fn put(store: &IdbObjectStore, profile: &str, solve: &Solve) -> Result<IdbRequest, JsValue> {
+    let json = serde_json::to_string(solve).map_err(|err| JsValue::from_str(&err.to_string()))?;
+    store.put_with_key(&json.into(), &record_key(profile, solve))
+}
+
+/// Every record of `profile`, with its key.
+async fn records(db: &IdbDatabase, profile: &str) -> Result<Vec<(JsValue, String)>, JsValue> {
+    let range = profile_range(profile)?;
+    let store = store(db, STORE, IdbTransactionMode::Readonly)?;
+    let keys = store.get_all_keys_with_key(&range)?;
+    let records = store.get_all_with_key(&range)?;
+    let keys: Array = settle(&keys).await?.dyn_into()?;
+    let records: Array = settle(&records).await?.dyn_into()?;
+
+    Ok(keys
+        .iter()
+        .zip(records.iter())
+        .filter_map(|(key, record)| Some((key, record.as_string()?)))
+        .collect())
+}
+
+/// Every solve stored for `profile`, oldest first.
+async fn read(db: &IdbDatabase, profile: &str) -> Result<Vec<Solve>, JsValue> {
+    Ok(records(db, profile)
+        .await?
+        .into_iter()
+        .filter_map(|(_, json)| {
+            serde_json::from_str(&json)
+                .map_err(|err| log::warn!("skipping stored solve: {err}"))
+                .ok()
+        })
+        .collect())
+}
+
+/// Brings `profile`'s records up to [`SOLVES_VERSION`], each under the key it
+/// has now. Records from a newer release are left alone.
+async fn migrate(db: &IdbDatabase, profile: &str) -> Result<(), JsValue> {
+    let version = settle(&store(db, META, IdbTransactionMode::Readonly)?.get(&profile.into())?)
+        .await?
+        .as_f64()
+        .map_or(1, |version| version as u32);
+    if version == SOLVES_VERSION || !migrations::check_version("solves", version, SOLVES_VERSION) {
+        return Ok(());
+    }
+
+    let records = records(db, profile).await?;
+    let transaction = db.transaction_with_str_sequence_and_mode(
+        &Array::of2(&STORE.into(), &META.into()),
+        IdbTransactionMode::Readwrite,
+    )?;
+    let store = transaction.object_store(STORE)?;
+    for (key, json) in records {
+        let solve = serde_json::from_str(&json).and_then(|mut value: Value| {
+            migrations::upgrade(SOLVE_MIGRATIONS, version, |migrate| migrate(&mut value));
+            serde_json::from_value::<Solve>(value)
+        });
+        match solve {
+            Ok(solve) => {
+                store.delete(&key)?;
+                put(&store, profile, &solve)?;
+            }
+            Err(err) => log::warn!("couldn't migrate stored solve: {err}"),
+        }
+    }
+    let meta = transaction.object_store(META)?;
+    settle(&meta.put_with_key(&SOLVES_VERSION.into(), &profile.into())?).await?;
+    Ok(())
+}
+
+/// Every solve of the active `profile`, oldest first. Solves still in
+/// localStorage from before are moved over.
+async fn load(db: &IdbDatabase, profile: &str) -> Result<Vec<Solve>, JsValue> {
+    migrate(db, profile).await?;
+    let mut solves = read(db, profile).await?;
+
+    if let Some(legacy) = load_persisted::<Vec<Value>>(LEGACY_KEY) {
+        let (legacy, unreadable) = parse_each(legacy);
+        if !migrations::is_read_only() {
+            if !unreadable.is_empty() {
+                // Kept aside, as `load_persisted` does with whole values.
+                store_persisted(&format!("{LEGACY_KEY}.unreadable"), &unreadable);
+            }
+            write(db, profile, &[], &legacy).await?;
+            if let Some(storage) = window().local_storage()? {
+                storage.remove_item(&scoped_key(LEGACY_KEY))?;
+            }
+        }
+        solves = merge_sorted(solves, legacy);
+    }
+
+    Ok(solves)
+}
+
+fn merge_sorted(mut solves: Vec<Solve>, more: Vec<Solve>) -> Vec<Solve> {
+    solves.extend(more);
+    solves.sort_by(|a, b| a.finished_at.total_cmp(&b.finished_at));
+    let mut seen = HashSet::new();
+    solves.retain(|solve| seen.insert(solve.id));
+    solves
+}
+
+/// Puts the solves that differ between `before` and `after` and deletes
+/// the ones that are gone, all in one transaction. Nothing is written while
+/// storage is [read-only](migrations::is_read_only).
+async fn write(
+    db: &IdbDatabase,
+    profile: &str,
+    before: &[Solve],
+    after: &[Solve],
+) -> Result<(), JsValue> {
+    if migrations::is_read_only() {
+        return Ok(());
+    }
+
+    let previous: HashMap<u64, &Solve> = before.iter().map(|solve| (solve.id, solve)).collect();
+    let current: HashSet<u64> = after.iter().map(|solve| solve.id).collect();
+
+    let changed: Vec<&Solve> = after
+        .iter()
+        .filter(|solve| previous.get(&solve.id) != Some(solve))
+        .collect();
+    let removed: Vec<&Solve> = before
+        .iter()
+        .filter(|solve| !current.contains(&solve.id))
+        .collect();
+    if changed.is_empty() && removed.is_empty() {
+        return Ok(());
+    }
+
+    let store = store(db, STORE, IdbTransactionMode::Readwrite)?;
+    let mut last = None;
+    for solve in changed {
+        last = Some(put(&store, profile, solve)?);
+    }
+    for solve in removed {
+        last = Some(store.delete(&record_key(profile, solve))?);
+    }
+    if let Some(request) = last {
+        settle(&request).await?;
+    }
+    Ok(())
+}
+
+/// The solves of any profile, not just the active one, as last saved.
+pub async fn load_profile(profile: &str) -> Result<Vec<Solve>, JsValue> {
+    read(&open().await?, profile).await
+}
+
+/// Deletes every solve stored for `profile`.
+pub async fn delete_profile(profile: &str) -> Result<(), JsValue> {
+    let db = open().await?;
+    let range = profile_range(profile)?;
+    settle(&store(&db, STORE, IdbTransactionMode::Readwrite)?.delete(&range)?).await?;
+    settle(&store(&db, META, IdbTransactionMode::Readwrite)?.delete(&profile.into())?).await?;
+    Ok(())
+}
+
+/// Loads `solves` from IndexedDB, then keeps the database in step with it.
+/// `loaded` turns true once the stored solves are in.
+pub fn sync(solves: RwSignal<Vec<Solve>>, loaded: RwSignal<bool>) {
+    // The solves as the database last saw them.
+    let synced = store_value(Vec::<Solve>::new());
+    let db = store_value(None::<IdbDatabase>);
+    let profile = active_profile();
+
+    spawn_local(async move {
+        let result = async {
+            let database = open().await?;
+            let stored = load(&database, &profile).await?;
+            db.set_value(Some(database));
+            Ok::<_, JsValue>(stored)
+        }
+        .await;
+
+        match result {
+            Ok(stored) => {
+                synced.set_value(stored.clone());
+                // Anything solved while loading goes after the stored ones.
+                solves.update(|solves| *solves = merge_sorted(stored, mem::take(solves)));
+            }
+            Err(err) => log::error!("couldn't load solves: {err:?}"),
+        }
+        loaded.set(true);
+    });
+
+    create_effect(move |_| {
+        let after = solves.get();
+        if !loaded() {
+            return;
+        }
+        let Some(database) = db.get_value() else {
+            return;
+        };
+        let before = synced.get_value();
+        synced.set_value(after.clone());
+
+        let profile = active_profile();
+        spawn_local(async move {
+            if let Err(err) = write(&database, &profile, &before, &after).await {
+                log::error!("couldn't save solves: {err:?}");
+            }
+        });
+    });
+}
+
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use js_sys::{Array, Promise};
+use leptos::*;
+use serde_json::Value;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    IdbDatabase, IdbKeyRange, IdbObjectStore, IdbOpenDbRequest, IdbRequest, IdbTransactionMode,
+    IdbVersionChangeEvent,
+};
+
+use macros::return_with_try;
+
+use crate::migrations::{self, SOLVES_VERSION, SOLVE_MIGRATIONS};
+use crate::profile::{active_profile, scoped_key};
+use crate::signal_ext::{load_persisted, store_persisted};
+use crate::stats::{parse_each, Solve};
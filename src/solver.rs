@@ -0,0 +1,474 @@
+//! A small IDA* solver over the single-tile move metric, used for training aids.
+//! Searches are bounded by a node budget so they never stall the page; when the
+//! budget runs out the result is a lower bound rather than an exact distance.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Evaluation {
+    /// Moves to solved; a lower bound unless `exact`.
+    pub distance: usize,
+    pub exact: bool,
+    /// The cells to slide, in order, along an optimal solution (if `exact`).
+    pub solution: Vec<(usize, usize)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveQuality {
+    Optimal,
+    Neutral,
+    Regressive,
+}
+
+impl MoveQuality {
+    /// Classifies a move of `moved` tiles from a position `before` moves away
+    /// from solved to one `after` moves away.
+    pub fn classify(before: usize, after: usize, moved: usize) -> Self {
+        match () {
+            _ if after + moved == before => MoveQuality::Optimal,
+            _ if after > before => MoveQuality::Regressive,
+            _ => MoveQuality::Neutral,
+        }
+    }
+}
+
+pub const DEFAULT_BUDGET: usize = 300_000;
+
+/// Sum of each tile's Manhattan distance from its home, plus linear conflicts.
+/// Never overestimates. `homes` is each piece's home offset, as from
+/// [`Arrangement::homes`].
+pub fn heuristic(pieces: &[usize], (width, height): (usize, usize), homes: &[usize]) -> usize {
+    let mut distance = manhattan(pieces, width, homes);
+
+    for y in 0..height {
+        let row = (0..width)
+            .map(|x| pieces[y * width + x])
+            .filter(|&piece| piece != 0 && homes[piece] / width == y)
+            .map(|piece| homes[piece] % width);
+        distance += 2 * conflicts(row);
+    }
+    for x in 0..width {
+        let column = (0..height)
+            .map(|y| pieces[y * width + x])
+            .filter(|&piece| piece != 0 && homes[piece] % width == x)
+            .map(|piece| homes[piece] / width);
+        distance += 2 * conflicts(column);
+    }
+
+    distance
+}
+
+fn manhattan(pieces: &[usize], width: usize, homes: &[usize]) -> usize {
+    pieces
+        .iter()
+        .enumerate()
+        .filter(|&(_, &piece)| piece != 0)
+        .map(|(i, &piece)| tile_distance(homes[piece], i, width))
+        .sum()
+}
+
+#[inline]
+fn tile_distance(home: usize, at: usize, width: usize) -> usize {
+    (home % width).abs_diff(at % width) + (home / width).abs_diff(at / width)
+}
+
+/// The minimum number of tiles that must leave the line so the rest, whose
+/// goals are listed in line order, can pass each other: length minus LIS.
+fn conflicts(goals: impl Iterator<Item = usize>) -> usize {
+    let goals: Vec<usize> = goals.collect();
+    let mut tails: Vec<usize> = Vec::new();
+
+    for &goal in &goals {
+        match tails.binary_search(&goal) {
+            Ok(_) => {}
+            Err(i) if i == tails.len() => tails.push(goal),
+            Err(i) => tails[i] = goal,
+        }
+    }
+
+    goals.len() - tails.len()
+}
+
+/// Searches for an optimal solution to `goal`, visiting at most `budget` nodes.
+pub fn evaluate(
+    pieces: &[usize],
+    shape: (usize, usize),
+    goal: Arrangement,
+    budget: usize,
+) -> Evaluation {
+    let mut search = Search::new(pieces, shape, goal, budget);
+    loop {
+        if let Some(evaluation) = search.run(budget) {
+            return evaluation;
+        }
+    }
+}
+
+/// Positions visited before yielding to the page, in [`evaluate_in_slices`].
+const SLICE: usize = 10_000;
+
+/// As [`evaluate`], but a slice at a time, yielding to the page between
+/// slices so input never waits on more than one. `None` as soon as `stale`
+/// says the result is no longer wanted.
+pub async fn evaluate_in_slices(
+    pieces: &[usize],
+    shape: (usize, usize),
+    goal: Arrangement,
+    budget: usize,
+    stale: impl Fn() -> bool,
+) -> Option<Evaluation> {
+    let mut search = Search::new(pieces, shape, goal, budget);
+    loop {
+        next_task().await;
+        if stale() {
+            return None;
+        }
+        if let Some(evaluation) = search.run(SLICE) {
+            return Some(evaluation);
+        }
+    }
+}
+
+/// Resolves once the page has had a turn to handle input and paint.
+async fn next_task() {
+    let promise = Promise::new(&mut |resolve, _| {
+        set_timeout(move || _ = resolve.call0(&JsValue::NULL), Duration::ZERO);
+    });
+    _ = JsFuture::from(promise).await;
+}
+
+/// `length` moves that each lower the heuristic as much as possible, never
+/// undoing the previous one; for when [`evaluate`] runs out of budget.
+pub fn greedy_line(
+    pieces: &[usize],
+    (width, height): (usize, usize),
+    goal: Arrangement,
+    length: usize,
+) -> Vec<(usize, usize)> {
+    let homes = goal.homes((width, height));
+    let mut pieces = pieces.to_vec();
+    let mut blank = pieces.iter().position(|&piece| piece == 0).unwrap_or(0);
+    let mut previous_blank = None;
+    let mut line = Vec::with_capacity(length);
+
+    while line.len() < length && heuristic(&pieces, (width, height), &homes) > 0 {
+        let (bx, by) = (blank % width, blank / width);
+        let neighbors = [
+            (bx > 0).then(|| blank - 1),
+            (bx + 1 < width).then(|| blank + 1),
+            (by > 0).then(|| blank - width),
+            (by + 1 < height).then(|| blank + width),
+        ];
+        let Some(tile) = neighbors
+            .into_iter()
+            .flatten()
+            .filter(|&tile| Some(tile) != previous_blank)
+            .min_by_key(|&tile| {
+                pieces.swap(blank, tile);
+                let estimate = heuristic(&pieces, (width, height), &homes);
+                pieces.swap(blank, tile);
+                estimate
+            })
+        else {
+            break;
+        };
+
+        pieces.swap(blank, tile);
+        previous_blank = Some(blank);
+        blank = tile;
+        line.push((tile % width, tile / width));
+    }
+
+    line
+}
+
+/// An [`evaluate`] that can be run a slice at a time, so a caller on the
+/// main thread can yield to the page between slices.
+pub struct Search {
+    pieces: Vec<usize>,
+    shape: (usize, usize),
+    homes: Vec<usize>,
+    blank: usize,
+    path: Vec<(usize, usize)>,
+    /// One per position on `path`, plus the start.
+    frames: Vec<Frame>,
+    bound: usize,
+    next_bound: usize,
+    nodes: usize,
+    budget: usize,
+}
+
+struct Frame {
+    /// Where the blank was before the move into this position.
+    previous_blank: Option<usize>,
+    /// The next of the blank's four neighbors to try.
+    direction: usize,
+}
+
+enum Visit {
+    Found,
+    Pruned,
+    Expanded,
+}
+
+impl Search {
+    pub fn new(pieces: &[usize], shape: (usize, usize), goal: Arrangement, budget: usize) -> Self {
+        let homes = goal.homes(shape);
+        let bound = heuristic(pieces, shape, &homes);
+        Self {
+            pieces: pieces.to_vec(),
+            shape,
+            homes,
+            blank: pieces.iter().position(|&piece| piece == 0).unwrap_or(0),
+            path: Vec::new(),
+            frames: Vec::new(),
+            bound,
+            next_bound: usize::MAX,
+            nodes: 0,
+            budget,
+        }
+    }
+
+    /// Visits at most `nodes` more positions. Returns the evaluation once the
+    /// search is over, `None` while there's more to do; not to be run again
+    /// after that.
+    pub fn run(&mut self, nodes: usize) -> Option<Evaluation> {
+        let stop = self.nodes.saturating_add(nodes);
+
+        loop {
+            if self.nodes >= self.budget {
+                return Some(self.lower_bound());
+            }
+            if self.nodes >= stop {
+                return None;
+            }
+
+            let Some(frame) = self.frames.last_mut() else {
+                // Starting out, or every position under `bound` was tried.
+                if self.nodes > 0 {
+                    if self.next_bound == usize::MAX {
+                        return Some(self.lower_bound());
+                    }
+                    self.bound = mem::replace(&mut self.next_bound, usize::MAX);
+                }
+                match self.visit(None) {
+                    Visit::Found => return Some(self.solution()),
+                    Visit::Pruned | Visit::Expanded => continue,
+                }
+            };
+
+            let (width, height) = self.shape;
+            let (bx, by) = (self.blank % width, self.blank / width);
+            let direction = frame.direction;
+            frame.direction += 1;
+            let previous_blank = frame.previous_blank;
+
+            let tile = match direction {
+                0 => (bx > 0).then(|| self.blank - 1),
+                1 => (bx + 1 < width).then(|| self.blank + 1),
+                2 => (by > 0).then(|| self.blank - width),
+                3 => (by + 1 < height).then(|| self.blank + width),
+                _ => {
+                    self.frames.pop();
+                    if let Some(previous_blank) = previous_blank {
+                        self.undo(previous_blank);
+                    }
+                    continue;
+                }
+            };
+            let Some(tile) = tile.filter(|&tile| Some(tile) != previous_blank) else {
+                continue;
+            };
+
+            let blank = self.blank;
+            self.pieces.swap(blank, tile);
+            self.blank = tile;
+            self.path.push((tile % width, tile / width));
+            match self.visit(Some(blank)) {
+                Visit::Found => return Some(self.solution()),
+                Visit::Pruned => self.undo(blank),
+                Visit::Expanded => {}
+            }
+        }
+    }
+
+    /// Counts the current position and, unless it's solved or over the
+    /// bound, queues its neighbors.
+    fn visit(&mut self, previous_blank: Option<usize>) -> Visit {
+        self.nodes += 1;
+
+        let cost = self.path.len();
+        let estimate = cost + heuristic(&self.pieces, self.shape, &self.homes);
+        if estimate > self.bound {
+            self.next_bound = self.next_bound.min(estimate);
+            return Visit::Pruned;
+        }
+        if estimate == cost {
+            return Visit::Found;
+        }
+
+        self.frames.push(Frame {
+            previous_blank,
+            direction: 0,
+        });
+        Visit::Expanded
+    }
+
+    /// Takes back the last move, which took the blank from `previous_blank`.
+    fn undo(&mut self, previous_blank: usize) {
+        self.pieces.swap(self.blank, previous_blank);
+        self.blank = previous_blank;
+        self.path.pop();
+    }
+
+    fn solution(&self) -> Evaluation {
+        Evaluation {
+            distance: self.path.len(),
+            exact: true,
+            solution: self.path.clone(),
+        }
+    }
+
+    fn lower_bound(&self) -> Evaluation {
+        Evaluation {
+            distance: self.bound,
+            exact: false,
+            solution: Vec::new(),
+        }
+    }
+}
+
+use std::mem;
+use std::time::Duration;
+
+use js_sys::Promise;
+use leptos::set_timeout;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::arrangement::Arrangement;
+
+#[cfg(test)]
+mod tests {
+    /// The standard goal of `shape` after the blank takes `path`, a string of
+    /// `L`, `R`, `U` and `D`.
+    fn walk((width, height): (usize, usize), path: &str) -> Vec<usize> {
+        let mut pieces: Vec<usize> = (1..width * height).chain([0]).collect();
+        let mut blank = width * height - 1;
+        for step in path.chars() {
+            let to = match step {
+                'L' => blank - 1,
+                'R' => blank + 1,
+                'U' => blank - width,
+                'D' => blank + width,
+                _ => unreachable!(),
+            };
+            pieces.swap(blank, to);
+            blank = to;
+        }
+        pieces
+    }
+
+    /// Every position of `shape` with its distance from solved, found
+    /// breadth-first.
+    fn distances((width, height): (usize, usize)) -> HashMap<Vec<usize>, usize> {
+        let solved = walk((width, height), "");
+        let mut distances = HashMap::from([(solved.clone(), 0)]);
+        let mut queue = VecDeque::from([solved]);
+        while let Some(pieces) = queue.pop_front() {
+            let distance = distances[&pieces];
+            let blank = pieces.iter().position(|&piece| piece == 0).unwrap();
+            let (bx, by) = (blank % width, blank / width);
+            let neighbors = [
+                (bx > 0).then(|| blank - 1),
+                (bx + 1 < width).then(|| blank + 1),
+                (by > 0).then(|| blank - width),
+                (by + 1 < height).then(|| blank + width),
+            ];
+            for tile in neighbors.into_iter().flatten() {
+                let mut next = pieces.clone();
+                next.swap(blank, tile);
+                if !distances.contains_key(&next) {
+                    distances.insert(next.clone(), distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Slides the cells of `solution` on `pieces`, each next to the blank.
+    fn play(pieces: &[usize], width: usize, solution: &[(usize, usize)]) -> Vec<usize> {
+        let mut pieces = pieces.to_vec();
+        for &(x, y) in solution {
+            let blank = pieces.iter().position(|&piece| piece == 0).unwrap();
+            let tile = y * width + x;
+            assert_eq!(tile_distance(blank, tile, width), 1, "not a slide");
+            pieces.swap(blank, tile);
+        }
+        pieces
+    }
+
+    #[test]
+    fn solved() {
+        let evaluation = evaluate(&walk((3, 3), ""), (3, 3), Arrangement::Standard, 10);
+        assert_eq!(
+            evaluation,
+            Evaluation {
+                distance: 0,
+                exact: true,
+                solution: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn exact() {
+        let pieces = walk((3, 3), "LL");
+        let evaluation = evaluate(&pieces, (3, 3), Arrangement::Standard, DEFAULT_BUDGET);
+        assert_eq!(evaluation.distance, 2);
+        assert!(evaluation.exact);
+        assert_eq!(evaluation.solution, [(1, 2), (2, 2)]);
+
+        let shape = (3, 2);
+        for (pieces, distance) in distances(shape) {
+            let evaluation = evaluate(&pieces, shape, Arrangement::Standard, DEFAULT_BUDGET);
+            assert!(evaluation.exact);
+            assert_eq!(evaluation.distance, distance, "{pieces:?}");
+            assert_eq!(evaluation.solution.len(), distance);
+            assert_eq!(
+                play(&pieces, shape.0, &evaluation.solution),
+                walk(shape, "")
+            );
+        }
+    }
+
+    #[test]
+    fn slices_add_up() {
+        let pieces = walk((3, 3), "UULDDRULLDRRUULDLURDDR");
+        let whole = evaluate(&pieces, (3, 3), Arrangement::Standard, DEFAULT_BUDGET);
+        assert!(whole.exact);
+
+        let mut search = Search::new(&pieces, (3, 3), Arrangement::Standard, DEFAULT_BUDGET);
+        let sliced = iter::repeat_with(|| search.run(1)).find_map(|run| run);
+        assert_eq!(sliced, Some(whole));
+    }
+
+    #[test]
+    fn budget() {
+        let shape = (3, 2);
+        let (pieces, distance) = distances(shape)
+            .into_iter()
+            .max_by_key(|&(_, distance)| distance)
+            .unwrap();
+        let homes = Arrangement::Standard.homes(shape);
+        let evaluation = evaluate(&pieces, shape, Arrangement::Standard, 5);
+        assert!(!evaluation.exact);
+        assert!(evaluation.solution.is_empty());
+        assert!(evaluation.distance >= heuristic(&pieces, shape, &homes));
+        assert!(evaluation.distance <= distance);
+    }
+
+    use std::collections::{HashMap, VecDeque};
+    use std::iter;
+
+    use super::*;
+}
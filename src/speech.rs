@@ -0,0 +1,76 @@
+/// Reads `text` aloud with the configured voice, interrupting anything still
+/// being spoken. Does nothing if announcements are off or unsupported.
+pub fn announce(text: &str, setting: &SpeechSetting) {
+    if !setting.enabled {
+        return;
+    }
+
+    return_with_try! {
+        let synth = window().speech_synthesis()?;
+        let utterance = SpeechSynthesisUtterance::new_with_text(text)?;
+        utterance.set_rate(setting.rate);
+
+        let voice = find_voice(&synth, &setting.voice);
+        utterance.set_voice(voice.as_ref());
+
+        synth.cancel();
+        synth.speak(&utterance);
+    }
+}
+
+/// A voice as listed in the settings, identified by its `voiceURI`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Voice {
+    pub uri: String,
+    pub label: String,
+}
+
+/// The available voices. Browsers load them lazily, so the list may start out
+/// empty and fill in later.
+pub fn use_voices() -> ReadSignal<Vec<Voice>> {
+    let voices = create_rw_signal(Vec::new());
+
+    if let Ok(synth) = window().speech_synthesis() {
+        voices.set(list_voices(&synth));
+
+        let on_change = Closure::<dyn Fn()>::new(clone!(synth => move || {
+            voices.set(list_voices(&synth));
+        }));
+        synth.set_onvoiceschanged(Some(on_change.as_ref().unchecked_ref()));
+        on_change.forget();
+    }
+
+    voices.read_only()
+}
+
+fn list_voices(synth: &SpeechSynthesis) -> Vec<Voice> {
+    synth
+        .get_voices()
+        .iter()
+        .filter_map(|voice| voice.dyn_into::<SpeechSynthesisVoice>().ok())
+        .map(|voice| Voice {
+            uri: voice.voice_uri(),
+            label: format!("{} ({})", voice.name(), voice.lang()),
+        })
+        .collect()
+}
+
+fn find_voice(synth: &SpeechSynthesis, uri: &str) -> Option<SpeechSynthesisVoice> {
+    if uri.is_empty() {
+        return None;
+    }
+
+    synth
+        .get_voices()
+        .iter()
+        .filter_map(|voice| voice.dyn_into::<SpeechSynthesisVoice>().ok())
+        .find(|voice| voice.voice_uri() == uri)
+}
+
+use leptos::*;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{SpeechSynthesis, SpeechSynthesisUtterance, SpeechSynthesisVoice};
+
+use macros::{clone, return_with_try};
+
+use crate::settings::SpeechSetting;
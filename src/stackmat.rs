@@ -0,0 +1,316 @@
+//! Experimental input from a Stackmat timer plugged into the audio jack, so the
+//! hand-timed result can replace the keyboard one.
+
+/// Shows the timer's reading while Stackmat input is enabled, and reports each
+/// time it stops.
+#[component]
+pub fn StackmatInput(#[prop(into)] on_stop: Callback<Duration>) -> impl IntoView {
+    let settings = use_settings();
+    let enabled = memo!(|settings| settings.stackmat);
+    let reading = create_rw_signal(None::<StackmatPacket>);
+    let error = create_rw_signal(None::<String>);
+    let capture = store_value(None::<Capture>);
+    let on_stop = store_value(on_stop);
+
+    let stop_capture = move || {
+        if let Some(Some(capture)) = capture.try_update_value(Option::take) {
+            capture.stop();
+        }
+    };
+
+    create_effect(move |_| {
+        stop_capture();
+        reading.set(None);
+        error.set(None);
+        if !enabled() {
+            return;
+        }
+
+        let on_packet = move |packet: StackmatPacket| {
+            let previous = reading.get_untracked();
+            reading.set(Some(packet));
+
+            let was_running =
+                previous.is_some_and(|previous| previous.status == StackmatStatus::Running);
+            if was_running && packet.status == StackmatStatus::Stopped {
+                on_stop.with_value(|on_stop| on_stop.call(packet.time));
+            }
+        };
+        spawn_local(async move {
+            match Capture::start(on_packet).await {
+                Ok(started) if enabled.get_untracked() => capture.set_value(Some(started)),
+                Ok(started) => started.stop(),
+                Err(err) => error.set(Some(format!("{err:?}"))),
+            }
+        });
+    });
+    on_cleanup(stop_capture);
+
+    let status = move || {
+        if let Some(error) = error() {
+            return format!("no audio input: {error}");
+        }
+        match reading() {
+            Some(StackmatPacket { status, time }) => format!(
+                "{}:{:02}.{:03} {status:?}",
+                time.as_secs() / 60,
+                time.as_secs() % 60,
+                time.subsec_millis()
+            ),
+            None => "waiting for signal...".to_string(),
+        }
+    };
+
+    view! {
+        <Show when=enabled fallback=|| ()>
+            <p class="mt-1 text-center font-mono text-xs opacity-60">"Stackmat: "{status}</p>
+        </Show>
+    }
+}
+
+/// Microphone capture feeding a [`Decoder`]. Runs until [`Capture::stop`].
+struct Capture {
+    context: AudioContext,
+    stream: MediaStream,
+    _on_audio: Closure<dyn FnMut(AudioProcessingEvent)>,
+}
+
+impl Capture {
+    async fn start(on_packet: impl Fn(StackmatPacket) + 'static) -> Result<Self, JsValue> {
+        let mut constraints = MediaStreamConstraints::new();
+        constraints.audio(&JsValue::TRUE);
+        let request = window()
+            .navigator()
+            .media_devices()?
+            .get_user_media_with_constraints(&constraints)?;
+        let stream: MediaStream = JsFuture::from(request).await?.dyn_into()?;
+
+        let context = AudioContext::new()?;
+        let source = context.create_media_stream_source(&stream)?;
+        let processor = context
+            .create_script_processor_with_buffer_size_and_number_of_input_channels_and_number_of_output_channels(4096, 1, 1)?;
+
+        let mut decoder = Decoder::new(context.sample_rate().into());
+        let on_audio =
+            Closure::<dyn FnMut(AudioProcessingEvent)>::new(move |event: AudioProcessingEvent| {
+                let Ok(samples) = event
+                    .input_buffer()
+                    .and_then(|buffer| buffer.get_channel_data(0))
+                else {
+                    return;
+                };
+                decoder
+                    .push_samples(&samples)
+                    .into_iter()
+                    .for_each(&on_packet);
+            });
+        processor.set_onaudioprocess(Some(on_audio.as_ref().unchecked_ref()));
+
+        // The processor only runs while connected to the output.
+        source.connect_with_audio_node(&processor)?;
+        processor.connect_with_audio_node(&context.destination())?;
+
+        Ok(Self {
+            context,
+            stream,
+            _on_audio: on_audio,
+        })
+    }
+
+    fn stop(self) {
+        let _ = self.context.close();
+        for track in self.stream.get_tracks().iter() {
+            if let Ok(track) = track.dyn_into::<MediaStreamTrack>() {
+                track.stop();
+            }
+        }
+    }
+}
+
+/// What the timer is doing, from the first byte of each packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StackmatStatus {
+    Idle,
+    /// Both hands on the pads; releasing them starts the timer.
+    Ready,
+    Running,
+    Stopped,
+    LeftHand,
+    RightHand,
+    Cancelled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StackmatPacket {
+    pub status: StackmatStatus,
+    pub time: Duration,
+}
+
+/// The line is idle (mark) when it holds one level for longer than any byte can.
+const IDLE_BITS: usize = 12;
+const BAUD: f64 = 1200.0;
+
+/// Turns raw audio samples into packets. The timer sends 1200 baud serial
+/// (8N1) over the audio line with unknown polarity, so the idle level is
+/// learned from the first long run.
+pub struct Decoder {
+    samples_per_bit: f64,
+    level: bool,
+    run: usize,
+    idle: Option<bool>,
+    /// Bits read into the current byte and their value, once a start bit is seen.
+    frame: Option<(u8, u8)>,
+    packet: Vec<u8>,
+}
+
+impl Decoder {
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            samples_per_bit: sample_rate / BAUD,
+            level: false,
+            run: 0,
+            idle: None,
+            frame: None,
+            packet: Vec::new(),
+        }
+    }
+
+    pub fn push_samples(&mut self, samples: &[f32]) -> Vec<StackmatPacket> {
+        let mut packets = Vec::new();
+
+        for &sample in samples {
+            let level = sample > 0.0;
+            if level == self.level {
+                self.run += 1;
+                // Flush as soon as the line goes idle instead of at the next packet.
+                if self.run as f64 == (IDLE_BITS as f64 * self.samples_per_bit).round() {
+                    self.push_run(level, self.run, &mut packets);
+                }
+                continue;
+            }
+
+            let (previous, run) = (self.level, self.run);
+            self.level = level;
+            self.run = 1;
+            self.push_run(previous, run, &mut packets);
+        }
+
+        packets
+    }
+
+    fn push_run(&mut self, level: bool, samples: usize, packets: &mut Vec<StackmatPacket>) {
+        let bits = (samples as f64 / self.samples_per_bit).round() as usize;
+
+        if bits >= IDLE_BITS {
+            // The first bit of an idle run may be the stop bit of the last byte.
+            if self.idle == Some(level) {
+                packets.extend(self.push_bit(true));
+            }
+            self.idle = Some(level);
+            self.frame = None;
+            self.packet.clear();
+            return;
+        }
+        let Some(idle) = self.idle else {
+            return;
+        };
+
+        for _ in 0..bits {
+            if let Some(packet) = self.push_bit(level == idle) {
+                packets.push(packet);
+            }
+        }
+    }
+
+    fn push_bit(&mut self, mark: bool) -> Option<StackmatPacket> {
+        match self.frame {
+            None => {
+                if !mark {
+                    self.frame = Some((0, 0)); // start bit
+                }
+                None
+            }
+            Some((read @ ..=7, value)) => {
+                self.frame = Some((read + 1, value | u8::from(mark) << read));
+                None
+            }
+            Some((_, value)) => {
+                self.frame = None;
+                if !mark {
+                    self.packet.clear(); // framing error
+                    return None;
+                }
+                self.push_byte(value)
+            }
+        }
+    }
+
+    fn push_byte(&mut self, byte: u8) -> Option<StackmatPacket> {
+        self.packet.push(byte);
+
+        if byte == b'\r' {
+            let packet = parse(&self.packet);
+            self.packet.clear();
+            packet
+        } else {
+            if self.packet.len() > 10 {
+                self.packet.clear();
+            }
+            None
+        }
+    }
+}
+
+/// `status, M, S, S, h, h, checksum, \n, \r` from older timers, or one more
+/// digit (thousandths) from gen 3+ ones. The checksum is 64 plus the digit sum.
+pub fn parse(packet: &[u8]) -> Option<StackmatPacket> {
+    let digits = match packet.len() {
+        9 => 5,
+        10 => 6,
+        _ => return None,
+    };
+    let (&status, rest) = packet.split_first()?;
+    let (digits, rest) = rest.split_at(digits);
+
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let digits: Vec<u64> = digits.iter().map(|digit| u64::from(digit - b'0')).collect();
+    if 64 + digits.iter().sum::<u64>() != u64::from(rest[0]) {
+        return None;
+    }
+
+    let fraction = digits[3..].iter().fold(0, |n, digit| n * 10 + digit);
+    let millis = match digits.len() {
+        5 => fraction * 10,
+        _ => fraction,
+    };
+    let time =
+        Duration::from_millis(digits[0] * 60_000 + (digits[1] * 10 + digits[2]) * 1000 + millis);
+
+    let status = match status {
+        b'I' => StackmatStatus::Idle,
+        b'A' => StackmatStatus::Ready,
+        b' ' => StackmatStatus::Running,
+        b'S' => StackmatStatus::Stopped,
+        b'L' => StackmatStatus::LeftHand,
+        b'R' => StackmatStatus::RightHand,
+        b'C' => StackmatStatus::Cancelled,
+        _ => return None,
+    };
+
+    Some(StackmatPacket { status, time })
+}
+
+use core::time::Duration;
+
+use leptos::*;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AudioContext, AudioProcessingEvent, MediaStream, MediaStreamConstraints, MediaStreamTrack,
+};
+
+use macros::memo;
+
+use crate::settings::use_settings;
@@ -0,0 +1,71 @@
+//! Milestones of page startup, timed from navigation start, so changes to
+//! loading and mounting can be measured against each other. Each is also left
+//! as a `performance.mark`, to line up with the browser's own profiler.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Milestone {
+    /// `main` has started, i.e. the wasm module is instantiated.
+    Instantiated,
+    /// The app has been mounted into the page.
+    Mounted,
+    /// The first frame with a board in it has been painted.
+    BoardPainted,
+}
+
+impl Milestone {
+    pub fn label(self) -> &'static str {
+        match self {
+            Milestone::Instantiated => "instantiated",
+            Milestone::Mounted => "mounted",
+            Milestone::BoardPainted => "board painted",
+        }
+    }
+}
+
+thread_local! {
+    static MILESTONES: RefCell<Vec<(Milestone, f64)>> = RefCell::new(Vec::new());
+}
+
+/// Records `milestone` as reached now, unless it was already.
+pub fn mark(milestone: Milestone) {
+    let Some(performance) = window().performance() else {
+        return;
+    };
+    MILESTONES.with(|milestones| {
+        let mut milestones = milestones.borrow_mut();
+        if milestones.iter().any(|&(reached, _)| reached == milestone) {
+            return;
+        }
+        milestones.push((milestone, performance.now()));
+        _ = performance.mark(&format!("jugo:{}", milestone.label()));
+    });
+}
+
+/// Marks [`Milestone::BoardPainted`] once the current changes are on screen:
+/// the second animation frame from now runs after the first has painted.
+pub fn mark_board_painted() {
+    request_animation_frame(|| request_animation_frame(|| mark(Milestone::BoardPainted)));
+}
+
+pub fn milestones() -> Vec<(Milestone, f64)> {
+    MILESTONES.with(|milestones| milestones.borrow().clone())
+}
+
+/// How much of the wasm binary was downloaded and how big it is unpacked, in
+/// bytes, from the resource timing entry for it.
+pub fn wasm_size() -> Option<(f64, f64)> {
+    let entries = window().performance()?.get_entries_by_type("resource");
+    let entry = entries.iter().find(|entry| {
+        Reflect::get(entry, &"name".into())
+            .ok()
+            .and_then(|name| name.as_string())
+            .is_some_and(|name| name.ends_with("_bg.wasm"))
+    })?;
+    let field = |name: &str| Reflect::get(&entry, &name.into()).ok()?.as_f64();
+    Some((field("transferSize")?, field("decodedBodySize")?))
+}
+
+use std::cell::RefCell;
+
+use js_sys::Reflect;
+use leptos::{request_animation_frame, window};
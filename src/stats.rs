@@ -0,0 +1,426 @@
+/// A finished solve.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Solve {
+    /// Identifies the solve for as long as it's kept. Solves stored before
+    /// ids existed get a fresh one when they're read.
+    #[serde(default = "Solve::new_id")]
+    pub id: u64,
+    /// Includes the scramble mode, so random-move solves can be told apart
+    /// from random-state ones.
+    pub scramble: Scramble,
+    pub time: Duration,
+    pub moves: usize,
+    /// Milliseconds since the Unix epoch.
+    pub finished_at: f64,
+    /// Whether `time` came from a hardware timer rather than the keyboard.
+    #[serde(default)]
+    pub hand_timed: bool,
+    #[serde(default)]
+    pub penalty: Penalty,
+    /// Every move of the solve, for replays and analysis. Empty for solves
+    /// recorded before moves were kept.
+    #[serde(default)]
+    pub replay: Vec<TimedMove>,
+    /// The original scramble of a solve imported from another timer, in which
+    /// case `scramble` is only a placeholder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_scramble: Option<String>,
+    /// Set for attempts at a daily challenge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<DailyAttempt>,
+    /// The scramble's place in a seeded practice session, from zero.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_index: Option<u32>,
+    /// From the scramble appearing, or the countdown ending, to the first
+    /// move. Unknown for solves resumed after a reload.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reaction: Option<Duration>,
+    /// What [`crate::integrity::check`] found when the solve was recorded. Empty for
+    /// solves recorded before flags were kept.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<Flag>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Penalty {
+    #[default]
+    None,
+    PlusTwo,
+    Dnf,
+}
+
+impl Solve {
+    /// A fresh [`Solve::id`], kept below 2^53 so it survives being a
+    /// JavaScript number.
+    pub fn new_id() -> u64 {
+        rand::thread_rng().gen_range(1..1 << 53)
+    }
+
+    /// The time that counts, after the penalty; `None` for a DNF.
+    pub fn result(&self) -> Option<Duration> {
+        match self.penalty {
+            Penalty::None => Some(self.time),
+            Penalty::PlusTwo => Some(self.time + Duration::from_secs(2)),
+            Penalty::Dnf => None,
+        }
+    }
+}
+
+/// Reads solves one at a time, so one that no longer parses doesn't take the
+/// rest down with it. Returns the ones that parsed, and the others as they were.
+pub fn parse_each(values: Vec<Value>) -> (Vec<Solve>, Vec<Value>) {
+    let mut solves = Vec::with_capacity(values.len());
+    let mut unreadable = Vec::new();
+    for value in values {
+        match Solve::deserialize(&value) {
+            Ok(solve) => solves.push(solve),
+            Err(err) => {
+                log::warn!("skipping a solve that doesn't parse: {err}");
+                unreadable.push(value);
+            }
+        }
+    }
+    (solves, unreadable)
+}
+
+/// For `#[serde(deserialize_with)]`: a list of solves, less any that don't
+/// parse.
+pub fn deserialize_each<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Solve>, D::Error> {
+    let (solves, _) = parse_each(Vec::deserialize(deserializer)?);
+    Ok(solves)
+}
+
+/// [`format_time`] of the result, with `+` after +2s and `DNF` for DNFs.
+pub fn format_result(solve: &Solve) -> String {
+    match (solve.penalty, solve.result()) {
+        (Penalty::PlusTwo, Some(time)) => format!("{}+", format_time(time)),
+        (_, Some(time)) => format_time(time),
+        (_, None) => "DNF".to_string(),
+    }
+}
+
+/// A slide from `cell`, `at` into the solve. Stored as `[millis, x, y]` to keep
+/// persisted histories small.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "(u64, usize, usize)", into = "(u64, usize, usize)")]
+pub struct TimedMove {
+    pub at: Duration,
+    pub cell: (usize, usize),
+}
+
+impl From<(u64, usize, usize)> for TimedMove {
+    fn from((millis, x, y): (u64, usize, usize)) -> Self {
+        Self {
+            at: Duration::from_millis(millis),
+            cell: (x, y),
+        }
+    }
+}
+
+impl From<TimedMove> for (u64, usize, usize) {
+    fn from(TimedMove { at, cell: (x, y) }: TimedMove) -> Self {
+        (at.as_millis() as u64, x, y)
+    }
+}
+
+/// `12.345`, or `1:02.345` from a minute up.
+pub fn format_time(time: Duration) -> String {
+    let (minutes, secs, millis) = (
+        time.as_secs() / 60,
+        time.as_secs() % 60,
+        time.subsec_millis(),
+    );
+    match minutes {
+        0 => format!("{secs}.{millis:03}"),
+        _ => format!("{minutes}:{secs:02}.{millis:03}"),
+    }
+}
+
+/// The fastest time among `solves` on a board of `shape`.
+pub fn personal_best(solves: &[Solve], shape: (usize, usize)) -> Option<Duration> {
+    solves
+        .iter()
+        .filter(|solve| solve.scramble.shape == shape)
+        .filter_map(Solve::result)
+        .min()
+}
+
+/// The WCA-style average of the last `n` solves: the best and worst are dropped
+/// and the rest averaged, a DNF counting as the worst. `None` with fewer than
+/// `n` solves, `Some(None)` if it's a DNF itself.
+pub fn average_of(solves: &[Solve], n: usize) -> Option<Option<Duration>> {
+    let last = solves.get(solves.len().checked_sub(n)?..)?;
+    let mut results: Vec<Option<Duration>> = last.iter().map(Solve::result).collect();
+    // `None` sorts first, so move DNFs to the end.
+    results.sort_by_key(|result| (result.is_none(), *result));
+
+    let trim = trimmed(n);
+    let counted = &results[trim..n - trim];
+    let Some(sum) = counted.iter().copied().sum::<Option<Duration>>() else {
+        return Some(None);
+    };
+    Some(Some(sum / counted.len() as u32))
+}
+
+/// ` <url>` for the solve's scramble; nothing for imported ones, whose scramble
+/// can't be linked.
+fn link(solve: &Solve) -> String {
+    match solve.imported_scramble {
+        Some(_) => String::new(),
+        None => format!(" <{}>", scramble_url(&solve.scramble)),
+    }
+}
+
+/// How many solves are dropped from each end of an average of `n`.
+fn trimmed(n: usize) -> usize {
+    match n {
+        ..=2 => 0,
+        _ => (n as f64 * 0.05).ceil() as usize,
+    }
+}
+
+/// A summary for pasting into Discord or a forum: the best single, ao5 and
+/// ao12 on `shape`, with each time linking to its scramble.
+pub fn forum_summary(solves: &[Solve], shape: (usize, usize)) -> String {
+    let solves: Vec<Solve> = solves
+        .iter()
+        .filter(|solve| solve.scramble.shape == shape)
+        .cloned()
+        .collect();
+    let (width, height) = shape;
+    let mut text = format!("**{width}x{height}** ({} solves)\n", solves.len());
+
+    let best = solves
+        .iter()
+        .filter(|solve| solve.result().is_some())
+        .min_by_key(|solve| solve.result());
+    if let Some(best) = best {
+        text += &format!("Best single: {}{}\n", format_result(best), link(best));
+    }
+
+    for n in [5, 12] {
+        let Some(average) = average_of(&solves, n) else {
+            continue;
+        };
+        let last = &solves[solves.len() - n..];
+        let average = average.map_or("DNF".to_string(), format_time);
+        text += &format!("\nao{n}: {average}\n");
+
+        // Parenthesize the dropped times, as is customary.
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by_key(|&i| (last[i].result().is_none(), last[i].result()));
+        let trim = trimmed(n);
+        let dropped = [&order[..trim], &order[n - trim..]].concat();
+
+        for (i, solve) in last.iter().enumerate() {
+            let result = match dropped.contains(&i) {
+                true => format!("({})", format_result(solve)),
+                false => format_result(solve),
+            };
+            text += &format!("{}. {result}{}\n", i + 1, link(solve));
+        }
+    }
+
+    text
+}
+
+thread_local! {
+    static SESSION_START: f64 = js_sys::Date::now();
+}
+
+/// When the current session began, in milliseconds since the Unix epoch:
+/// this page load, unless the previous session has been continued.
+#[derive(Clone, Copy)]
+struct SessionStart {
+    start: RwSignal<f64>,
+    /// Where the previous page load's session began, as it was saved.
+    previous: Option<f64>,
+}
+
+/// Tracked when called in a reactive context.
+pub fn session_start() -> f64 {
+    match use_context::<SessionStart>() {
+        Some(session) => session.start.get(),
+        None => SESSION_START.with(|start| *start),
+    }
+}
+
+/// Whether the stored solves have been read in yet.
+#[derive(Clone, Copy)]
+struct SolvesLoaded(RwSignal<bool>);
+
+/// Offers to carry on with the session from before the last reload or crash,
+/// so session averages pick up where they left off. Waits for the stored
+/// solves to load.
+pub fn offer_previous_session(toasts: Toasts) {
+    let SessionStart { start, previous } = expect_context();
+    let SolvesLoaded(loaded) = expect_context();
+    let Some(previous) = previous else {
+        return;
+    };
+    let solves = use_solves();
+
+    create_effect(move |offered: Option<bool>| {
+        if offered == Some(true) || !loaded() {
+            return offered.unwrap_or(false);
+        }
+        let page_load = SESSION_START.with(|start| *start);
+        let count = solves.with_untracked(|solves| {
+            solves
+                .iter()
+                .filter(|solve| (previous..page_load).contains(&solve.finished_at))
+                .count()
+        });
+        if count > 0 {
+            toasts.push(Toast {
+                message: format!("Continue your last session ({count} solves)?"),
+                action: Some(("Continue", Callback::new(move |()| start.set(previous)))),
+            });
+        }
+        true
+    });
+}
+
+/// The `p`th percentile (0 to 100) of `sorted` by nearest rank.
+pub fn percentile(sorted: &[Duration], p: f64) -> Option<Duration> {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.clamp(1, sorted.len().max(1)) - 1).copied()
+}
+
+pub fn provide_solves() -> RwSignal<Vec<Solve>> {
+    let solves = create_rw_signal(Vec::<Solve>::new());
+    let loaded = create_rw_signal(false);
+    provide_context(solves);
+    provide_context(SolvesLoaded(loaded));
+    solve_store::sync(solves, loaded);
+
+    let start = create_rw_signal(SESSION_START.with(|start| *start));
+    provide_context(SessionStart {
+        start,
+        previous: load_persisted("session_start"),
+    });
+    create_effect(move |_| store_persisted("session_start", &start.get()));
+
+    solves
+}
+
+pub fn use_solves() -> RwSignal<Vec<Solve>> {
+    expect_context()
+}
+
+/// How often each mapped key has been pressed, whether or not it moved anything.
+#[derive(Clone, Copy)]
+pub struct KeyUsage(pub RwSignal<BTreeMap<String, u64>>);
+
+pub fn provide_key_usage() -> KeyUsage {
+    let usage = KeyUsage(create_persisted_signal("key_usage", BTreeMap::new()));
+    provide_context(usage);
+    usage
+}
+
+pub fn use_key_usage() -> KeyUsage {
+    expect_context()
+}
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use leptos::*;
+use rand::Rng;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::daily::DailyAttempt;
+use crate::integrity::Flag;
+use crate::location::scramble_url;
+use crate::seed::Scramble;
+use crate::signal_ext::{create_persisted_signal, load_persisted, store_persisted};
+use crate::solve_store;
+use crate::toast::{Toast, Toasts};
+
+#[cfg(test)]
+mod tests {
+    fn solve() -> Solve {
+        Solve {
+            id: 1,
+            scramble: Scramble::new((4, 4), [0; 32]),
+            time: Duration::from_secs(10),
+            moves: 50,
+            finished_at: 1_697_450_000_000.0,
+            hand_timed: false,
+            penalty: Penalty::None,
+            replay: Vec::new(),
+            imported_scramble: None,
+            daily: None,
+            session_index: None,
+            reaction: None,
+            flags: Vec::new(),
+        }
+    }
+
+    fn results(results: &[Option<u64>]) -> Vec<Solve> {
+        results
+            .iter()
+            .map(|result| Solve {
+                time: Duration::from_millis(result.unwrap_or(0)),
+                penalty: match result {
+                    Some(_) => Penalty::None,
+                    None => Penalty::Dnf,
+                },
+                ..solve()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn averages() {
+        let full = results(&[
+            Some(10_000),
+            Some(12_000),
+            Some(8_000),
+            Some(11_000),
+            Some(9_000),
+        ]);
+        assert_eq!(
+            average_of(&full, 5),
+            Some(Some(Duration::from_millis(10_000)))
+        );
+        assert_eq!(average_of(&full[1..], 5), None);
+
+        // the one DNF is dropped as the worst
+        let one_dnf = results(&[Some(10_000), None, Some(8_000), Some(11_000), Some(9_000)]);
+        assert_eq!(
+            average_of(&one_dnf, 5),
+            Some(Some(Duration::from_millis(10_000)))
+        );
+
+        let two_dnfs = results(&[Some(10_000), None, Some(8_000), None, Some(9_000)]);
+        assert_eq!(average_of(&two_dnfs, 5), Some(None));
+    }
+
+    #[test]
+    fn unreadable_solves_are_set_aside() {
+        let good = serde_json::to_value(solve()).unwrap();
+        let bad = json!({ "scramble": "not a scramble" });
+
+        let (solves, unreadable) = parse_each(vec![bad.clone(), good]);
+        assert_eq!(solves, [solve()]);
+        assert_eq!(unreadable, [bad]);
+    }
+
+    #[test]
+    fn solves_without_an_id_get_one() {
+        let mut value = serde_json::to_value(solve()).unwrap();
+        value.as_object_mut().unwrap().remove("id");
+
+        let (solves, _) = parse_each(vec![value.clone(), value]);
+        assert_ne!(solves[0].id, 0);
+        assert_ne!(solves[0].id, solves[1].id);
+    }
+
+    use serde_json::json;
+
+    use super::*;
+}
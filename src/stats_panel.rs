@@ -0,0 +1,350 @@
+const PAGE: usize = 50;
+
+/// The solve history, with a side-by-side comparison of any two solves on the
+/// same shape.
+#[component]
+pub fn StatsPanel() -> impl IntoView {
+    let solves = use_solves();
+    let toasts = use_toasts();
+    let settings = use_settings();
+    // Solves don't record their keymap, so they're read with today's.
+    let keymap = memo!(|settings| settings.keymap);
+    let open = create_rw_signal(false);
+    // Indices into `solves`, at most two.
+    let selected = create_rw_signal(Vec::<usize>::new());
+
+    let toggle = move |index: usize| {
+        selected.update(|selected| match selected.iter().position(|&i| i == index) {
+            Some(position) => {
+                selected.remove(position);
+            }
+            None => {
+                if selected.len() == 2 {
+                    selected.remove(0);
+                }
+                selected.push(index);
+            }
+        })
+    };
+
+    // Both look the solve up by id, then again on undo, since the list may
+    // have changed in between.
+    let delete = move |id: u64| {
+        let removed = solves.try_update(|solves| {
+            let index = solves.iter().position(|solve| solve.id == id)?;
+            Some(solves.remove(index))
+        });
+        let Some(Some(removed)) = removed else {
+            return;
+        };
+        selected.set(Vec::new());
+        toasts.show_undo("Solve deleted", move || {
+            let removed = removed.clone();
+            solves.update(|solves| {
+                if solves.iter().any(|solve| solve.id == removed.id) {
+                    return;
+                }
+                let index =
+                    solves.partition_point(|solve| solve.finished_at <= removed.finished_at);
+                solves.insert(index, removed);
+            });
+        });
+    };
+    let set_penalty = move |id: u64, penalty: Penalty| {
+        let previous = solves.try_update(|solves| {
+            let solve = solves.iter_mut().find(|solve| solve.id == id)?;
+            Some(std::mem::replace(&mut solve.penalty, penalty))
+        });
+        if let Some(Some(previous)) = previous {
+            toasts.show_undo("Penalty changed", move || {
+                solves.update(|solves| {
+                    if let Some(solve) = solves.iter_mut().find(|solve| solve.id == id) {
+                        solve.penalty = previous;
+                    }
+                })
+            });
+        }
+    };
+
+    let copy_summary = move |_| {
+        let summary = solves.with(|solves| {
+            let shape = solves.last()?.scramble.shape;
+            Some(forum_summary(solves, shape))
+        });
+        let Some(summary) = summary else {
+            toasts.show("No solves yet");
+            return;
+        };
+        spawn_local(async move {
+            match copy_to_clipboard(&summary).await {
+                true => toasts.show("Copied stats to the clipboard"),
+                false => toasts.show("Couldn't access the clipboard"),
+            }
+        });
+    };
+
+    let copy_replay_link = move |file: &ReplayFile| {
+        let Some(url) = replay_url(file) else {
+            toasts.show("Too many moves to fit the replay in a link");
+            return;
+        };
+        spawn_local(async move {
+            match copy_to_clipboard(&url).await {
+                true => toasts.show("Copied a link to the replay"),
+                false => toasts.show("Couldn't access the clipboard"),
+            }
+        });
+    };
+
+    let file_input = create_node_ref::<html::Input>();
+    let import = move |_| {
+        spawn_local(async move {
+            let Some(text) = read_selected_file(file_input, toasts).await else {
+                return;
+            };
+            match parse_slidysim(&text) {
+                Ok(imported) => {
+                    let previous = solves.get_untracked();
+                    let added = solves
+                        .try_update(|solves| merge(solves, imported))
+                        .unwrap_or(0);
+                    selected.set(Vec::new());
+                    toasts.show_undo(format!("Imported {added} solves"), move || {
+                        solves.set(previous.clone())
+                    });
+                }
+                Err(err) => toasts.show(format!("Import failed: {err}")),
+            }
+        });
+    };
+
+    let comparison = move || {
+        let pair = selected.with(|selected| match selected[..] {
+            [a, b] => Some((a, b)),
+            _ => None,
+        });
+        let Some((a, b)) = pair else {
+            return view! { <p class="opacity-60">"Select two solves to compare them."</p> }
+                .into_view();
+        };
+        let (Some(a), Some(b)) =
+            solves.with(|solves| (solves.get(a).cloned(), solves.get(b).cloned()))
+        else {
+            return ().into_view();
+        };
+
+        match (a.scramble.shape == b.scramble.shape, a.replay.is_empty() || b.replay.is_empty()) {
+            (false, _) => view! { <p class="opacity-60">"Only solves on the same shape can be compared."</p> }.into_view(),
+            (_, true) => view! { <p class="opacity-60">"One of these solves was recorded without its moves."</p> }.into_view(),
+            _ => view! { <SolveComparison a b /> }.into_view(),
+        }
+    };
+
+    // Long histories are shown a page at a time, newest first.
+    let shown = create_rw_signal(PAGE);
+    let rows = move || {
+        solves.with(|solves| {
+            solves
+                .iter()
+                .enumerate()
+                .rev()
+                .take(shown())
+                .map(|(index, solve)| {
+                    let id = solve.id;
+                    let (width, height) = solve.scramble.shape;
+                    let finished_at = String::from(
+                        js_sys::Date::new(&solve.finished_at.into())
+                            .to_locale_string("default", &JsValue::UNDEFINED),
+                    );
+                    view! {
+                        <tr class="cursor-pointer hover:bg-neutral-200 dark:hover:bg-neutral-700" on:click=move |_| toggle(index)>
+                            <td>
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || selected.with(|selected| selected.contains(&index))
+                                />
+                            </td>
+                            <td class="text-right">{index + 1}</td>
+                            <td class="text-right">
+                                {flag_mark(solve)}
+                                {format_result(solve)}
+                            </td>
+                            <td class="text-right">{solve.moves}</td>
+                            <td class="text-right">
+                                {hand_stats(&solve.replay, keymap()).map(|stats| view! {
+                                    <span title=format!(
+                                        "Longest run with one hand: left {}, right {}",
+                                        stats.longest_left,
+                                        stats.longest_right,
+                                    )>
+                                        {format!("{:.0}%", stats.alternation * 100.0)}
+                                    </span>
+                                })}
+                            </td>
+                            <td class="text-right">
+                                {solve.session_index.map(|index| view! {
+                                    <span class="opacity-60" title="Scramble of the seeded session">
+                                        {format!("#{} ", index + 1)}
+                                    </span>
+                                })}
+                                {match solve.daily {
+                                    Some(DailyAttempt { late: false, .. }) => "daily ",
+                                    Some(DailyAttempt { late: true, .. }) => "late ",
+                                    None => "",
+                                }}
+                                {format!("{width}x{height}")}
+                            </td>
+                            <td class="text-right opacity-60">{finished_at}</td>
+                            <td on:click=|event| event.stop_propagation()>
+                                <select
+                                    class="bg-transparent"
+                                    on:change=move |event| set_penalty(id, match event_target_value(&event).as_str() {
+                                        "+2" => Penalty::PlusTwo,
+                                        "dnf" => Penalty::Dnf,
+                                        _ => Penalty::None,
+                                    })
+                                >
+                                    <option value="ok" selected=solve.penalty == Penalty::None>"OK"</option>
+                                    <option value="+2" selected=solve.penalty == Penalty::PlusTwo>"+2"</option>
+                                    <option value="dnf" selected=solve.penalty == Penalty::Dnf>"DNF"</option>
+                                </select>
+                            </td>
+                            <td on:click=|event| event.stop_propagation()>
+                                {(!solve.replay.is_empty()).then(|| {
+                                    let file = ReplayFile::new(solve.scramble, solve.replay.clone());
+                                    let link_file = file.clone();
+                                    view! {
+                                        <button
+                                            class="px-1 opacity-60 hover:opacity-100"
+                                            title="Copy a link to the replay"
+                                            on:click=move |_| copy_replay_link(&link_file)
+                                        >
+                                            "🔗"
+                                        </button>
+                                        <button
+                                            class="px-1 opacity-60 hover:opacity-100"
+                                            title="Download the replay"
+                                            on:click=move |_| download_bytes(
+                                                &format!("solve-{}.{EXTENSION}", index + 1),
+                                                &file.to_bytes(),
+                                                "application/octet-stream",
+                                            )
+                                        >
+                                            "⤓"
+                                        </button>
+                                    }
+                                })}
+                            </td>
+                            <td on:click=|event| event.stop_propagation()>
+                                <button
+                                    class="px-1 opacity-60 hover:opacity-100"
+                                    title="Delete"
+                                    on:click=move |_| delete(id)
+                                >
+                                    "✕"
+                                </button>
+                            </td>
+                        </tr>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <button
+            class="fixed top-3 right-[5.25rem] w-8 h-8 rounded-md text-xl opacity-60 hover:opacity-100"
+            title="Stats"
+            on:click=move |_| open.update(|open| *open = !*open)
+        >
+            "≡"
+        </button>
+        <Show when=open fallback=|| ()>
+            <div class="fixed inset-x-3 top-12 bottom-3 max-w-3xl mx-auto p-4 overflow-y-auto rounded-lg shadow-lg
+                        bg-neutral-100 dark:bg-neutral-800 flex flex-col gap-4 text-sm">
+                <div class="flex justify-end gap-2">
+                    <input
+                        type="file"
+                        accept=".csv,.tsv,.txt"
+                        class="hidden"
+                        _ref=file_input
+                        on:change=import
+                    />
+                    <button
+                        class="px-3 py-1 rounded-md ring-1 ring-violet-500"
+                        title="Import solves from a slidysim CSV export"
+                        on:click=move |_| {
+                            if let Some(input) = file_input.get_untracked() {
+                                input.click();
+                            }
+                        }
+                    >
+                        "Import"
+                    </button>
+                    <button
+                        class="px-3 py-1 rounded-md bg-violet-500 text-white"
+                        title="Best single, ao5 and ao12 of the current board size, formatted for Discord or forums"
+                        on:click=copy_summary
+                    >
+                        "Copy summary"
+                    </button>
+                </div>
+                <GoalsDashboard />
+                <TimeHistogram />
+                <ReactionChart />
+                {comparison}
+                <KeyHeatmap />
+                <details>
+                    <summary class="cursor-pointer opacity-60">"Session log"</summary>
+                    <SessionLogList />
+                </details>
+                <table class="font-mono text-xs child:child:child:px-1">
+                    <thead class="opacity-60">
+                        <tr>
+                            <th />
+                            <th class="text-right">"#"</th>
+                            <th class="text-right">"time"</th>
+                            <th class="text-right">"moves"</th>
+                            <th class="text-right" title="How often consecutive moves switch hands">"alt."</th>
+                            <th class="text-right">"shape"</th>
+                            <th class="text-right">"finished"</th>
+                            <th />
+                            <th />
+                            <th />
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+                <Show when=move || solves.with(Vec::len) > shown() fallback=|| ()>
+                    <button
+                        class="self-center px-3 py-1 rounded-md opacity-60 hover:opacity-100"
+                        on:click=move |_| shown.update(|shown| *shown += PAGE)
+                    >
+                        {move || format!("Show more ({} hidden)", solves.with(Vec::len) - shown())}
+                    </button>
+                </Show>
+            </div>
+        </Show>
+    }
+}
+
+use leptos::*;
+use wasm_bindgen::JsValue;
+
+use macros::memo;
+
+use crate::daily::DailyAttempt;
+use crate::goals::GoalsDashboard;
+use crate::hands::hand_stats;
+use crate::heatmap::KeyHeatmap;
+use crate::histogram::TimeHistogram;
+use crate::import::{merge, parse_slidysim};
+use crate::integrity::flag_mark;
+use crate::location::{copy_to_clipboard, download_bytes, read_selected_file, replay_url};
+use crate::reaction::ReactionChart;
+use crate::replay::SolveComparison;
+use crate::replay_file::{ReplayFile, EXTENSION};
+use crate::session_log::SessionLogList;
+use crate::settings::use_settings;
+use crate::stats::{format_result, forum_summary, use_solves, Penalty};
+use crate::toast::use_toasts;
@@ -0,0 +1,54 @@
+//! The latest solve and the current ao5, kept in the page title and a small
+//! footer, so streaming overlays and screenshots show them without the stats
+//! panel open.
+
+/// `12.345 · ao5 13.210` for the latest solve, on its board size.
+fn summary(solves: &[Solve]) -> Option<String> {
+    let last = solves.last()?;
+    let same_shape: Vec<Solve> = solves
+        .iter()
+        .filter(|solve| solve.scramble.shape == last.scramble.shape)
+        .cloned()
+        .collect();
+
+    let mut text = format_result(last);
+    if let Some(average) = average_of(&same_shape, 5) {
+        let average = average.map_or("DNF".to_string(), format_time);
+        text += &format!(" · ao5 {average}");
+    }
+    Some(text)
+}
+
+#[component]
+pub fn SolveSummary() -> impl IntoView {
+    let settings = use_settings();
+    let solves = use_solves();
+    let enabled = memo!(|settings| settings.solve_summary);
+    let text = create_memo(move |_| {
+        enabled()
+            .then(|| solves.with(|solves| summary(solves)))
+            .flatten()
+    });
+
+    let title = document().title();
+    create_effect(clone!(title => move |_| match text() {
+        Some(text) => document().set_title(&format!("{text} — {title}")),
+        None => document().set_title(&title),
+    }));
+    on_cleanup(move || document().set_title(&title));
+
+    view! {
+        <Show when=move || text.with(Option::is_some) fallback=|| ()>
+            <footer class="fixed bottom-1 left-1/2 -translate-x-1/2 font-mono text-xs opacity-60 pointer-events-none">
+                {text}
+            </footer>
+        </Show>
+    }
+}
+
+use leptos::*;
+
+use macros::{clone, memo};
+
+use crate::settings::use_settings;
+use crate::stats::{average_of, format_result, format_time, use_solves, Solve};
@@ -0,0 +1,199 @@
+//! Anonymous usage counts, sent only when turned on in settings. Events carry
+//! no identifier, scramble or time: just which puzzle sizes get solved and
+//! which options get switched. They queue up in localStorage and go out in
+//! batches, so nothing is lost while offline.
+
+const QUEUE_KEY: &str = "telemetry_queue";
+/// Beyond this the oldest events are dropped, e.g. after weeks offline.
+const MAX_QUEUED: usize = 500;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Where batches go unless settings say otherwise; set at build time.
+pub const DEFAULT_ENDPOINT: &str = match option_env!("JUGO_TELEMETRY_ENDPOINT") {
+    Some(endpoint) => endpoint,
+    None => "",
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    Solve {
+        width: usize,
+        height: usize,
+    },
+    /// A boolean setting changed, named by its path, e.g. `speech.enabled`.
+    Feature {
+        name: String,
+        on: bool,
+    },
+}
+
+#[derive(Serialize)]
+struct Batch<'a> {
+    version: &'static str,
+    events: &'a [Event],
+}
+
+#[derive(Clone, Copy)]
+pub struct Telemetry {
+    queue: StoredValue<Vec<Event>>,
+    sending: StoredValue<bool>,
+    settings: RwSignal<Settings>,
+}
+
+impl Telemetry {
+    fn enabled(&self) -> bool {
+        self.settings
+            .with_untracked(|settings| settings.telemetry.enabled)
+    }
+
+    pub fn record(&self, event: Event) {
+        if !self.enabled() {
+            return;
+        }
+        self.queue.update_value(|queue| {
+            queue.push(event);
+            let overflow = queue.len().saturating_sub(MAX_QUEUED);
+            queue.drain(..overflow);
+            store_persisted(QUEUE_KEY, queue);
+        });
+    }
+
+    fn clear(&self) {
+        self.queue.set_value(Vec::new());
+        store_persisted(QUEUE_KEY, &Vec::<Event>::new());
+    }
+
+    /// Sends everything queued so far, keeping it queued if that fails.
+    fn flush(self) {
+        let endpoint = self
+            .settings
+            .with_untracked(|settings| settings.telemetry.endpoint().to_owned());
+        if !self.enabled()
+            || endpoint.is_empty()
+            || self.sending.get_value()
+            || self.queue.with_value(Vec::is_empty)
+            || !window().navigator().on_line()
+        {
+            return;
+        }
+
+        let events = self.queue.get_value();
+        let Ok(body) = serde_json::to_string(&Batch {
+            version: env!("CARGO_PKG_VERSION"),
+            events: &events,
+        }) else {
+            return;
+        };
+
+        self.sending.set_value(true);
+        spawn_local(async move {
+            if post(&endpoint, &body).await {
+                // Anything recorded while the request was out stays queued.
+                self.queue.update_value(|queue| {
+                    queue.drain(..events.len().min(queue.len()));
+                    store_persisted(QUEUE_KEY, queue);
+                });
+            }
+            self.sending.set_value(false);
+        });
+    }
+}
+
+/// Posts as `text/plain`, which needs no CORS preflight.
+async fn post(endpoint: &str, body: &str) -> bool {
+    let mut init = RequestInit::new();
+    init.method("POST").body(Some(&JsValue::from_str(body)));
+
+    let response = JsFuture::from(window().fetch_with_str_and_init(endpoint, &init)).await;
+    match response.map(JsCast::unchecked_into::<Response>) {
+        Ok(response) if response.ok() => true,
+        Ok(response) => {
+            log::warn!("telemetry endpoint answered {}", response.status());
+            false
+        }
+        Err(_) => false,
+    }
+}
+
+/// The boolean leaves of `value`, by dotted path.
+fn switches(value: &Value, path: &str, out: &mut BTreeMap<String, bool>) {
+    match value {
+        Value::Bool(on) => {
+            out.insert(path.to_owned(), *on);
+        }
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                let path = match path {
+                    "" => name.clone(),
+                    path => format!("{path}.{name}"),
+                };
+                switches(field, &path, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn settings_switches(settings: &Settings) -> BTreeMap<String, bool> {
+    let mut out = BTreeMap::new();
+    if let Ok(value) = serde_json::to_value(settings) {
+        switches(&value, "", &mut out);
+    }
+    out
+}
+
+pub fn provide_telemetry(settings: RwSignal<Settings>) -> Telemetry {
+    let telemetry = Telemetry {
+        queue: store_value(load_persisted(QUEUE_KEY).unwrap_or_default()),
+        sending: store_value(false),
+        settings,
+    };
+    provide_context(telemetry);
+
+    create_effect(move |previous: Option<BTreeMap<String, bool>>| {
+        let current = settings.with(settings_switches);
+        match settings.with(|settings| settings.telemetry.enabled) {
+            false => telemetry.clear(),
+            true => {
+                for (name, &on) in &current {
+                    if previous
+                        .as_ref()
+                        .is_some_and(|previous| previous.get(name) != Some(&on))
+                    {
+                        telemetry.record(Event::Feature {
+                            name: name.clone(),
+                            on,
+                        });
+                    }
+                }
+            }
+        }
+        current
+    });
+
+    if let Ok(handle) = set_interval_with_handle(move || telemetry.flush(), FLUSH_INTERVAL) {
+        on_cleanup(move || handle.clear());
+    }
+    let online_handle = window_event_listener(online, move |_| telemetry.flush());
+    on_cleanup(move || online_handle.remove());
+
+    telemetry
+}
+
+pub fn use_telemetry() -> Telemetry {
+    expect_context()
+}
+
+use core::time::Duration;
+use std::collections::BTreeMap;
+
+use leptos::{ev::online, *};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{RequestInit, Response};
+
+use crate::settings::Settings;
+use crate::signal_ext::{load_persisted, store_persisted};
@@ -0,0 +1,93 @@
+/// A short-lived message at the bottom of the screen, optionally with one action.
+#[derive(Clone)]
+pub struct Toast {
+    pub message: String,
+    pub action: Option<(&'static str, Callback<()>)>,
+}
+
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy)]
+pub struct Toasts {
+    toasts: RwSignal<Vec<(u64, Toast)>>,
+    next_id: StoredValue<u64>,
+}
+
+impl Toasts {
+    pub fn show(&self, message: impl Into<String>) {
+        self.push(Toast {
+            message: message.into(),
+            action: None,
+        });
+    }
+
+    /// Shows `message` with an "Undo" button running `undo`.
+    pub fn show_undo(&self, message: impl Into<String>, undo: impl Fn() + 'static) {
+        self.push(Toast {
+            message: message.into(),
+            action: Some(("Undo", Callback::new(move |()| undo()))),
+        });
+    }
+
+    pub fn push(&self, toast: Toast) {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        self.toasts.update(|toasts| toasts.push((id, toast)));
+
+        let this = *self;
+        set_timeout(move || this.dismiss(id), TOAST_DURATION);
+    }
+
+    fn dismiss(&self, id: u64) {
+        self.toasts
+            .update(|toasts| toasts.retain(|&(other, _)| other != id));
+    }
+}
+
+pub fn provide_toasts() -> Toasts {
+    let toasts = Toasts {
+        toasts: create_rw_signal(Vec::new()),
+        next_id: store_value(0),
+    };
+    provide_context(toasts);
+    toasts
+}
+
+pub fn use_toasts() -> Toasts {
+    expect_context()
+}
+
+#[component]
+pub fn ToastList() -> impl IntoView {
+    let toasts = use_toasts();
+
+    view! {
+        <div class="fixed bottom-3 inset-x-0 flex flex-col items-center gap-2 pointer-events-none">
+            <For
+                each=move || toasts.toasts.get()
+                key=|(id, _)| *id
+                children=move |(id, toast)| view! {
+                    <div class="px-4 py-2 rounded-lg shadow-lg bg-neutral-800 text-neutral-100
+                                dark:bg-neutral-100 dark:text-neutral-900 flex gap-4 text-sm pointer-events-auto">
+                        {toast.message}
+                        {toast.action.map(|(label, action)| view! {
+                            <button
+                                class="font-bold text-violet-400 dark:text-violet-600"
+                                on:click=move |_| {
+                                    action.call(());
+                                    toasts.dismiss(id);
+                                }
+                            >
+                                {label}
+                            </button>
+                        })}
+                    </div>
+                }
+            />
+        </div>
+    }
+}
+
+use std::time::Duration;
+
+use leptos::*;
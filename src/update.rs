@@ -0,0 +1,88 @@
+//! Notices when a newer build has been deployed, so a tab left open for days
+//! doesn't keep running old code. Trunk names the wasm after a hash of its
+//! contents, so a fresh copy of the page that no longer mentions ours means
+//! there's a new build.
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// The wasm this page was loaded with, as linked from `index.html`.
+fn current_build() -> Option<String> {
+    document()
+        .query_selector(r#"link[href$="_bg.wasm"]"#)
+        .ok()??
+        .get_attribute("href")
+}
+
+async fn fetch_page() -> Result<String, JsValue> {
+    let mut init = RequestInit::new();
+    init.cache(RequestCache::NoStore);
+    let url = window().location().pathname()?;
+    let response: Response = JsFuture::from(window().fetch_with_str_and_init(&url, &init))
+        .await?
+        .dyn_into()?;
+    if !response.ok() {
+        return Err(format!("{url} answered {}", response.status()).into());
+    }
+    Ok(JsFuture::from(response.text()?)
+        .await?
+        .as_string()
+        .unwrap_or_default())
+}
+
+/// Checks every few minutes, and whenever the tab comes back into view, until
+/// it finds a new build and offers to reload once.
+pub fn watch_for_updates(toasts: Toasts) {
+    let Some(build) = current_build() else {
+        // Not a Trunk build, e.g. `trunk serve` without hashing.
+        return;
+    };
+    let checking = store_value(false);
+    let notified = store_value(false);
+
+    let check = move || {
+        if notified.get_value() || checking.get_value() || !window().navigator().on_line() {
+            return;
+        }
+        checking.set_value(true);
+        let build = build.clone();
+        spawn_local(async move {
+            match fetch_page().await {
+                Ok(page) if !page.contains(&build) => {
+                    notified.set_value(true);
+                    toasts.push(Toast {
+                        message: "A new version is available".to_owned(),
+                        action: Some((
+                            "Reload",
+                            Callback::new(|()| _ = window().location().reload()),
+                        )),
+                    });
+                }
+                Ok(_) => {}
+                Err(err) => log::debug!("update check failed: {err:?}"),
+            }
+            checking.set_value(false);
+        });
+    };
+
+    let check = store_value(check);
+    if let Ok(handle) =
+        set_interval_with_handle(move || check.with_value(|check| check()), CHECK_INTERVAL)
+    {
+        on_cleanup(move || handle.clear());
+    }
+    let handle = window_event_listener(visibilitychange, move |_| {
+        if document().visibility_state() == VisibilityState::Visible {
+            check.with_value(|check| check());
+        }
+    });
+    on_cleanup(move || handle.remove());
+}
+
+use core::time::Duration;
+
+use leptos::{ev::visibilitychange, *};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{RequestCache, RequestInit, Response, VisibilityState};
+
+use crate::toast::{Toast, Toasts};
@@ -0,0 +1,405 @@
+//! Two players on one keyboard, left hand against right, racing the same
+//! scramble from a shared countdown. Nothing here is recorded as a solve.
+//!
+//! Each player can be tied to a profile, whose personal best then sets a
+//! handicap so that players of different speeds finish close together.
+
+const COUNTDOWN: Duration = Duration::from_secs(3);
+const TICK: Duration = Duration::from_millis(16);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Race {
+    Idle,
+    Countdown { go: Instant },
+    Racing { started: Instant },
+}
+
+/// How the gap between the players' personal bests is made up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Handicap {
+    #[default]
+    Off,
+    /// The faster player starts later.
+    DelayedStart,
+    /// The faster player's time is counted longer.
+    AddedTime,
+}
+
+#[derive(Clone, Copy)]
+struct Player {
+    name: &'static str,
+    keymap: Keymap,
+    board: BoardController,
+    finished: RwSignal<Option<Duration>>,
+    profile: RwSignal<String>,
+    /// On the current shape, from `profile`.
+    best: RwSignal<Option<Duration>>,
+    /// Set at the start of each race.
+    handicap: RwSignal<Duration>,
+}
+
+impl Player {
+    fn new(name: &'static str, keymap: Keymap, shape: (usize, usize)) -> Self {
+        Self {
+            name,
+            keymap,
+            board: BoardController::new(AnyPuzzle::new(shape)),
+            finished: create_rw_signal(None),
+            profile: create_rw_signal(active_profile()),
+            best: create_rw_signal(None),
+            handicap: create_rw_signal(Duration::ZERO),
+        }
+    }
+}
+
+#[component]
+pub fn Versus(#[prop(into)] shape: Signal<(usize, usize)>) -> impl IntoView {
+    let clock = use_clock();
+    let settings = use_settings();
+    let layout = use_layout_map();
+
+    let shape_now = shape.get_untracked();
+    let players = [
+        Player::new("Left", Keymap::LeftHand, shape_now),
+        Player::new("Right", Keymap::RightHand, shape_now),
+    ];
+    let race = create_rw_signal(Race::Idle);
+    let handicap = create_rw_signal(Handicap::Off);
+    for player in players {
+        create_effect(move |_| {
+            let (profile, shape) = (player.profile.get(), shape.get());
+            spawn_local(async move {
+                match solve_store::load_profile(&profile).await {
+                    Ok(solves) => player.best.set(personal_best(&solves, shape)),
+                    Err(err) => log::error!("couldn't load the solves of {profile:?}: {err:?}"),
+                }
+            });
+        });
+    }
+    // The player whose solve is being replayed from the results.
+    let watching = create_rw_signal(None::<usize>);
+    let now = create_rw_signal(clock.now());
+
+    let interval = store_value(None::<IntervalHandle>);
+    create_effect(move |_| {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+        if race() == Race::Idle {
+            interval.set_value(None);
+            return;
+        }
+
+        let tick = move || {
+            let time = clock.now();
+            now.set(time);
+            if let Race::Countdown { go } = race.get_untracked() {
+                if time >= go {
+                    race.set(Race::Racing { started: go });
+                }
+            }
+        };
+        interval.set_value(set_interval_with_handle(tick, TICK).ok());
+    });
+    on_cleanup(move || {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+    });
+
+    // Seconds left on the countdown, beeping on each one and higher on "go".
+    let count = move || match race() {
+        Race::Countdown { go } => Some(((go - now()).as_millis().div_ceil(1000) as u64).max(1)),
+        _ => None,
+    };
+    create_effect(move |previous: Option<Option<u64>>| {
+        let count = count();
+        if previous.is_some_and(|previous| previous != count) {
+            match count {
+                Some(_) => audio::beep(440.0, Duration::from_millis(120)),
+                None if matches!(race.get_untracked(), Race::Racing { .. }) => {
+                    audio::beep(880.0, Duration::from_millis(300))
+                }
+                None => {}
+            }
+        }
+        count
+    });
+
+    let start = move || {
+        let scramble =
+            settings.with_untracked(|settings| settings.scramble.random(shape.get_untracked()));
+        for player in players {
+            player.board.load(scramble.puzzle());
+            player.finished.set(None);
+        }
+        watching.set(None);
+
+        let bests = players.map(|player| player.best.get_untracked());
+        let slowest = bests.iter().flatten().max().copied();
+        for (player, best) in players.into_iter().zip(bests) {
+            let gap = match (handicap.get_untracked(), slowest, best) {
+                (Handicap::Off, _, _) | (_, None, _) | (_, _, None) => Duration::ZERO,
+                (_, Some(slowest), Some(best)) => slowest - best,
+            };
+            player.handicap.set(gap);
+        }
+
+        let time = clock.now();
+        now.set(time);
+        race.set(Race::Countdown {
+            go: time + COUNTDOWN,
+        });
+    };
+
+    let slide = move |player: Player, idx, at: Instant| {
+        let Race::Racing { started } = race.get_untracked() else {
+            return;
+        };
+        let (delay, added) = match handicap.get_untracked() {
+            Handicap::Off => (Duration::ZERO, Duration::ZERO),
+            Handicap::DelayedStart => (player.handicap.get_untracked(), Duration::ZERO),
+            Handicap::AddedTime => (Duration::ZERO, player.handicap.get_untracked()),
+        };
+        if at < started + delay {
+            return;
+        }
+        if player.finished.get_untracked().is_some() || player.board.slide(idx) == 0 {
+            return;
+        }
+        player.board.record(TimedMove {
+            at: at - started,
+            cell: idx,
+        });
+        if player.board.is_solved_untracked() {
+            player.finished.set(Some(at - started + added));
+            if players
+                .iter()
+                .all(|player| player.finished.get_untracked().is_some())
+            {
+                race.set(Race::Idle);
+            }
+        }
+    };
+
+    let keydown_handle = window_event_listener(keydown, move |event| {
+        let target = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlElement>().ok());
+        if target.is_some_and(|target| {
+            matches!(
+                target.tag_name().as_str(),
+                "INPUT" | "SELECT" | "TEXTAREA" | "BUTTON"
+            )
+        }) {
+            return;
+        }
+
+        let key = event.key();
+        if key == " " {
+            event.prevent_default();
+            start();
+            return;
+        }
+        for player in players {
+            if let Some(idx) = player.keymap.lookup(&key) {
+                event.prevent_default();
+                slide(player, idx, clock.event_time(&event));
+            }
+        }
+    });
+    on_cleanup(move || keydown_handle.remove());
+
+    let winner = move || {
+        let [left, right] = players.map(|player| player.finished.get());
+        match (left, right) {
+            (Some(left), Some(right)) if right < left => Some(players[1]),
+            (Some(_), _) => Some(players[0]),
+            (None, Some(_)) => Some(players[1]),
+            (None, None) => None,
+        }
+    };
+
+    let format = move |time: Duration| {
+        settings.with(|settings| {
+            format!(
+                "{}.{}",
+                time.as_secs(),
+                settings.timer_precision.format_fraction(time)
+            )
+        })
+    };
+
+    let side = move |player: Player| {
+        let time = move || match (player.finished.get(), race()) {
+            (Some(time), _) => time,
+            (None, Race::Racing { started }) => now() - started,
+            _ => Duration::ZERO,
+        };
+
+        let handicap_label = move || {
+            let gap = player.handicap.get();
+            (gap > Duration::ZERO).then(|| match handicap() {
+                Handicap::Off => String::new(),
+                Handicap::DelayedStart => format!("starts {:.2}s late", gap.as_secs_f64()),
+                Handicap::AddedTime => format!("+{:.2}s", gap.as_secs_f64()),
+            })
+        };
+
+        view! {
+            <div class="flex flex-col items-center gap-2">
+                <div class="text-sm opacity-60">
+                    {player.name}
+                    " — "
+                    {move || layout.with(|layout| home_row(player.keymap, layout.as_ref()))}
+                </div>
+                <div class="flex gap-2 text-xs">
+                    <select
+                        class="bg-transparent"
+                        disabled=move || race() != Race::Idle
+                        on:change=move |event| player.profile.set(event_target_value(&event))
+                    >
+                        {profile_names()
+                            .into_iter()
+                            .map(|name| view! {
+                                <option value=name.clone() selected=player.profile.get_untracked() == name>
+                                    {name.clone()}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
+                    <span class="opacity-60">
+                        "PB "{move || player.best.get().map_or("-".to_string(), format_time)}
+                    </span>
+                    <span class="text-amber-500">{handicap_label}</span>
+                </div>
+                <div class="font-mono text-4xl">{move || format(time())}</div>
+                <BoardView
+                    puzzle=player.board.puzzle
+                    on_slide=move |(idx, at): ((usize, usize), Instant)| slide(player, idx, at)
+                />
+            </div>
+        }
+    };
+
+    // Fastest first, once everyone is done.
+    let results = move || {
+        let mut finished: Vec<(usize, Duration)> = players
+            .iter()
+            .enumerate()
+            .filter_map(|(i, player)| Some((i, player.finished.get()?)))
+            .collect();
+        if race() != Race::Idle || finished.len() < players.len() {
+            return None;
+        }
+        finished.sort_by_key(|&(_, time)| time);
+
+        let rows = finished
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (i, time))| {
+                let player = players[i];
+                let moves = player.board.moves.get_untracked();
+                let tps = moves as f64 / time.as_secs_f64().max(0.001);
+                view! {
+                    <tr
+                        class=move || classes!(
+                            "cursor-pointer hover:bg-neutral-200 dark:hover:bg-neutral-700",
+                            watching() == Some(i) => "bg-neutral-200 dark:bg-neutral-700",
+                        )
+                        on:click=move |_| watching.set(Some(i))
+                    >
+                        <td class="text-right">{rank + 1}</td>
+                        <td>{player.name}</td>
+                        <td class="text-right">{format_time(time)}</td>
+                        <td class="text-right">{moves}</td>
+                        <td class="text-right">{format!("{tps:.2}")}</td>
+                    </tr>
+                }
+            })
+            .collect_view();
+        Some(view! {
+            <table class="font-mono text-xs child:child:child:px-2">
+                <thead class="opacity-60">
+                    <tr>
+                        <th class="text-right">"#"</th>
+                        <th class="text-left">"player"</th>
+                        <th class="text-right">"time"</th>
+                        <th class="text-right">"moves"</th>
+                        <th class="text-right">"tps"</th>
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+        })
+    };
+    let replay = move || {
+        let player = players[watching()?];
+        let scramble = player.board.puzzle.with_untracked(AnyPuzzle::scramble);
+        let replay = player.board.replay.get_untracked();
+        Some(view! { <SolveReplay scramble replay /> })
+    };
+
+    view! {
+        <div class="relative flex flex-col items-center my-auto gap-4">
+            <div class="h-16 flex items-center text-3xl font-bold">
+                {move || match (count(), winner()) {
+                    (Some(count), _) => count.to_string(),
+                    (None, Some(winner)) => format!("{} wins!", winner.name),
+                    (None, None) if race() == Race::Idle => "Press space to start".to_owned(),
+                    (None, None) => "Go!".to_owned(),
+                }}
+            </div>
+            <label class="text-sm">
+                "Handicap from PBs "
+                <select
+                    class="bg-transparent"
+                    disabled=move || race() != Race::Idle
+                    on:change=move |event| handicap.set(match event_target_value(&event).as_str() {
+                        "delay" => Handicap::DelayedStart,
+                        "time" => Handicap::AddedTime,
+                        _ => Handicap::Off,
+                    })
+                >
+                    <option value="off">"Off"</option>
+                    <option value="delay">"Delayed start"</option>
+                    <option value="time">"Added time"</option>
+                </select>
+            </label>
+            <div class="flex gap-16">{players.into_iter().map(side).collect_view()}</div>
+            {results}
+            {replay}
+        </div>
+    }
+}
+
+/// The keys of a keymap's second row, in order, to tell players which is theirs.
+/// Labelled as they are on `layout`, as in the heatmap.
+fn home_row(keymap: Keymap, layout: Option<&LayoutMap>) -> String {
+    let mut keys: Vec<_> = keymap.entries().filter(|&(_, (_, y))| y == 1).collect();
+    keys.sort_by_key(|&(_, (x, _))| x);
+    keys.into_iter()
+        .map(|(key, _)| display_key(key, layout).to_uppercase())
+        .collect()
+}
+
+use core::time::Duration;
+
+use leptos::{ev::keydown, *};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+use macros::classes;
+
+use crate::audio;
+use crate::board_view::BoardView;
+use crate::clock::{use_clock, Instant};
+use crate::controller::BoardController;
+use crate::keymap::Keymap;
+use crate::layout_map::{display_key, use_layout_map, LayoutMap};
+use crate::profile::{active_profile, profile_names};
+use crate::puzzle::AnyPuzzle;
+use crate::replay::SolveReplay;
+use crate::settings::use_settings;
+use crate::solve_store;
+use crate::stats::{format_time, personal_best, TimedMove};
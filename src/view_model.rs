@@ -0,0 +1,152 @@
+//! What a board looks like at any moment, apart from how it's drawn: where
+//! each piece is, part-way through an animation or a drag included, and which
+//! cells are marked. The DOM and canvas renderers both draw from this, so what
+//! goes on the board is worked out once.
+
+const ENTRANCE_DURATION: Duration = Duration::from_millis(450);
+/// The share of the entrance over which pieces set off one after another.
+const ENTRANCE_STAGGER: f64 = 0.4;
+
+#[derive(Clone, Copy)]
+pub struct BoardViewModel {
+    pub puzzle: RwSignal<AnyPuzzle>,
+    pub shape: Memo<(usize, usize)>,
+    goal: Memo<Arrangement>,
+    /// The cell each piece is on, indexed by piece.
+    cells: Memo<Box<[(usize, usize)]>>,
+    solved_prefix: Memo<(usize, usize)>,
+    solved_region: Memo<SolvedRegionStyle>,
+    /// New scrambles fly in from the solved position.
+    pub entrance: Tween,
+    pub drag: RwSignal<Option<Drag>>,
+    pub hovered: RwSignal<Option<(usize, usize)>>,
+    pub overlays: Overlays,
+}
+
+impl BoardViewModel {
+    /// While `instant` holds, e.g. with the timer running, the board is shown
+    /// as it is without animating.
+    pub fn new(
+        puzzle: RwSignal<AnyPuzzle>,
+        instant: Option<Signal<bool>>,
+        overlays: Overlays,
+    ) -> Self {
+        let settings = use_settings();
+        let model = Self {
+            puzzle,
+            shape: memo!(|puzzle| puzzle.shape()),
+            goal: memo!(|puzzle| puzzle.goal()),
+            cells: create_memo(move |_| {
+                with!(|puzzle| {
+                    let (width, height) = puzzle.shape();
+                    puzzle.iter_indexed().fold(
+                        vec![Default::default(); width * height].into_boxed_slice(),
+                        |mut cells, (idx, piece)| {
+                            cells[piece] = idx;
+                            cells
+                        },
+                    )
+                })
+            }),
+            solved_prefix: memo!(|puzzle| puzzle.solved_prefix()),
+            solved_region: memo!(|settings| settings.solved_region),
+            entrance: Tween::new(ENTRANCE_DURATION, ease_out_cubic),
+            drag: create_rw_signal(None),
+            hovered: create_rw_signal(None),
+            overlays,
+        };
+
+        let scramble = memo!(|puzzle| puzzle.scramble());
+        create_effect(move |_| {
+            scramble.track();
+            if !instant.is_some_and(|instant| instant.get_untracked()) {
+                model.entrance.play();
+            }
+        });
+        create_effect(move |_| {
+            if instant.is_some_and(|instant| instant()) {
+                model.entrance.finish();
+            }
+        });
+
+        model
+    }
+
+    pub fn cell_of(&self, piece: usize) -> (usize, usize) {
+        self.cells.with(|cells| cells[piece])
+    }
+
+    /// Where `piece` belongs in the puzzle's goal arrangement.
+    pub fn home_of(&self, piece: usize) -> (usize, usize) {
+        self.goal.get().home(piece, self.shape.get())
+    }
+
+    pub fn entering(&self) -> bool {
+        self.entrance.is_running()
+    }
+
+    /// How far `piece` has faded in, from 0 to 1.
+    pub fn opacity(&self, piece: usize) -> f64 {
+        match self.entering() {
+            true => {
+                let (width, height) = self.shape.get();
+                self.entrance
+                    .staggered(piece, width * height, ENTRANCE_STAGGER)
+            }
+            false => 1.0,
+        }
+    }
+
+    /// How far the pointer has pulled the piece on `cell` along, in cells.
+    pub fn dragged(&self, cell: (usize, usize)) -> Option<(f64, f64)> {
+        self.drag
+            .with(|drag| drag.as_ref().and_then(|drag| drag.displacement(cell)))
+    }
+
+    /// Where `piece` is drawn, in cells, which is between two cells while it's
+    /// flying in or being dragged.
+    pub fn position(&self, piece: usize) -> (f64, f64) {
+        let (x, y) = self.cell_of(piece);
+        match (self.entering(), self.dragged((x, y))) {
+            (true, _) => {
+                let (home_x, home_y) = self.home_of(piece);
+                let t = self.opacity(piece);
+                (
+                    lerp(home_x as f64, x as f64, t),
+                    lerp(home_y as f64, y as f64, t),
+                )
+            }
+            (false, Some((dx, dy))) => (x as f64 + dx, y as f64 + dy),
+            (false, None) => (x as f64, y as f64),
+        }
+    }
+
+    /// Whether `piece` is moved frame by frame rather than by a transition.
+    pub fn is_driven(&self, piece: usize) -> bool {
+        self.entering() || self.dragged(self.cell_of(piece)).is_some()
+    }
+
+    /// How `cell` is marked as part of the finished rows and columns.
+    pub fn region_style(&self, cell: (usize, usize)) -> SolvedRegionStyle {
+        let (columns, rows) = self.solved_prefix.get();
+        let column = self.goal.get().column(cell.0, self.shape.get().0);
+        let finished = column < columns || cell.1 < rows;
+        match finished && self.cells.with(|cells| cells[0]) != cell {
+            true => self.solved_region.get(),
+            false => SolvedRegionStyle::Off,
+        }
+    }
+}
+
+use core::time::Duration;
+
+use leptos::*;
+
+use macros::memo;
+
+use crate::animation::{ease_out_cubic, lerp, Tween};
+use crate::arrangement::Arrangement;
+use crate::drag::Drag;
+use crate::overlay::Overlays;
+use crate::puzzle::AnyPuzzle;
+use crate::settings::{use_settings, SolvedRegionStyle};
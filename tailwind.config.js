@@ -6,6 +6,19 @@ module.exports = {
             transitionTimingFunction: {
                 'out-circ': 'cubic-bezier(0, 0.55, 0.45, 1)',
             },
+            // `scale` rather than `transform`, which already places the pieces.
+            keyframes: {
+                'solve-wave': {
+                    '40%': { scale: '1.08', filter: 'brightness(1.35)' },
+                },
+                'solve-pulse': {
+                    '50%': { scale: '1.12' },
+                },
+            },
+            animation: {
+                'solve-wave': 'solve-wave 450ms ease-out both',
+                'solve-pulse': 'solve-pulse 300ms ease-in-out 2',
+            },
         },
     },
     plugins: [